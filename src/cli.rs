@@ -2,7 +2,7 @@
 //!
 //! Uses clap derive macros for type-safe argument handling.
 
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand};
 
 // ─────────────────────────────────────────────────────────────
 // AddTarget: Subcommands for `evnx add`
@@ -46,6 +46,50 @@ pub enum AddTarget {
     Custom,
 }
 
+// ─────────────────────────────────────────────────────────────
+// HooksAction: Subcommands for `evnx hooks`
+// ─────────────────────────────────────────────────────────────
+
+/// Git integration to install.
+#[derive(Subcommand, Debug, Clone)]
+pub enum HooksAction {
+    /// Install git integration in the current repository.
+    ///
+    /// Example: evnx hooks install --merge-driver
+    Install {
+        /// Register `evnx merge` as the git merge driver for .env* files.
+        #[arg(long)]
+        merge_driver: bool,
+    },
+}
+
+// ─────────────────────────────────────────────────────────────
+// KubernetesArgs: Kubernetes options for `evnx convert`
+// ─────────────────────────────────────────────────────────────
+
+/// Names and metadata of the resources Kubernetes outputs refer to.
+#[derive(Args, Debug, Clone)]
+pub struct KubernetesArgs {
+    /// Name of the Secret; for ecs, the Secrets Manager secret holding secret values.
+    #[arg(long, default_value = "app-secrets")]
+    pub name: String,
+    /// Name of the ConfigMap for non-secret values.
+    #[arg(long, default_value = "app-config")]
+    pub config_name: String,
+    /// Namespace of the generated resources.
+    #[arg(long)]
+    pub namespace: Option<String>,
+    /// Label for the generated resources, as key=value (repeatable).
+    #[arg(long = "label", value_name = "KEY=VALUE")]
+    pub labels: Vec<String>,
+    /// Sealed Secrets controller certificate, from `kubeseal --fetch-cert`.
+    #[arg(long, value_name = "FILE")]
+    pub cert: Option<String>,
+    /// Where a SealedSecret may be unsealed: strict, namespace-wide or cluster-wide.
+    #[arg(long, default_value = "strict")]
+    pub scope: String,
+}
+
 // ─────────────────────────────────────────────────────────────
 // Cli: Top-level CLI structure
 // ─────────────────────────────────────────────────────────────
@@ -134,6 +178,13 @@ pub enum Commands {
         format: String,
         #[arg(long)]
         exit_zero: bool,
+        /// Also check that the example file holds only placeholders, empty values
+        /// or allowed defaults.
+        #[arg(long)]
+        check_example: bool,
+        /// Key whose literal default is accepted in the example file; repeatable.
+        #[arg(long, value_name = "KEY")]
+        allow_default: Vec<String>,
     },
 
     /// Detect secrets that look real (AWS keys, tokens, etc.).
@@ -150,9 +201,48 @@ pub enum Commands {
         format: String,
         #[arg(long)]
         exit_zero: bool,
+        /// Also search for the real values of this env file (verbatim, base64, URL-encoded).
+        #[arg(long, value_name = "ENV_FILE")]
+        known_values: Option<String>,
+        /// Also scan a `docker save` image archive (layers, Env and build history); repeatable.
+        #[arg(long, value_name = "IMAGE_TAR")]
+        image_tar: Vec<String>,
+        /// Scan running process environments and shell history on this Linux host instead of files.
+        #[arg(long)]
+        host: bool,
+        /// Check only example/sample/template files: every value must be a placeholder,
+        /// empty or an allowed default.
+        #[arg(long, conflicts_with = "host")]
+        examples: bool,
+        /// Key whose literal default is accepted in example files (with --examples); repeatable.
+        #[arg(long, value_name = "KEY")]
+        allow_default: Vec<String>,
+        /// Offer to move each hardcoded secret in Python, JS/TS, Rust or Go source into .env.
+        #[arg(long, conflicts_with_all = ["host", "examples"])]
+        remediate: bool,
+        /// Apply remediations without asking (with --remediate).
+        #[arg(long, short, requires = "remediate")]
+        yes: bool,
+    },
+
+    /// Mask secrets in logs or other text read from stdin or files.
+    Redact {
+        /// Files to redact (default: stdin).
+        files: Vec<String>,
+        /// Env file whose secret values are masked (default: .env if present).
+        #[arg(long)]
+        env: Option<String>,
+        /// Extra regex to mask (the first capture group if any); repeatable.
+        #[arg(long)]
+        extra: Vec<String>,
+        /// Mask format: full, partial or hash.
+        #[arg(long, default_value = "full")]
+        mask: String,
     },
 
     /// Compare .env vs .env.example — show missing/extra vars.
+    ///
+    /// Either file may be a git revision spec (HEAD~3:.env.example).
     Diff {
         #[arg(long, default_value = ".env")]
         env: String,
@@ -160,10 +250,50 @@ pub enum Commands {
         example: String,
         #[arg(long)]
         show_values: bool,
+        /// Output format: pretty, json, patch or markdown (release-notes changelog).
         #[arg(long, default_value = "pretty")]
         format: String,
         #[arg(long)]
         reverse: bool,
+        /// Compare the example file at this git revision (tag, branch, commit) ...
+        #[arg(long, value_name = "REV")]
+        from: Option<String>,
+        /// ... with this revision (default: the working tree).
+        #[arg(long, value_name = "REV", requires = "from")]
+        to: Option<String>,
+        /// Compare keyed digests: show only same/different and shape changes, never values.
+        #[arg(long, conflicts_with = "show_values")]
+        hashed: bool,
+        /// Print a fingerprint of --env (keyed digests) to compare elsewhere.
+        #[arg(long, conflicts_with_all = ["from", "to", "show_values"])]
+        fingerprint: bool,
+        /// Also export value lengths and charsets with --fingerprint.
+        #[arg(long, requires = "fingerprint")]
+        shapes: bool,
+        /// Secret key for the digests, never written to a fingerprint (default: random).
+        #[arg(long, env = "EVNX_FINGERPRINT_KEY", hide_env_values = true)]
+        key: Option<String>,
+    },
+
+    /// Three-way merge of env files by key (keeps comments, reports conflicts).
+    ///
+    /// Example: evnx merge base.env ours.env theirs.env --output .env.example
+    Merge {
+        /// Common ancestor.
+        base: String,
+        /// Our version; its layout is kept.
+        ours: String,
+        /// Their version.
+        theirs: String,
+        /// Write the result here instead of stdout.
+        #[arg(long, short)]
+        output: Option<String>,
+    },
+
+    /// Git integration (merge driver for .env files).
+    Hooks {
+        #[command(subcommand)]
+        action: HooksAction,
     },
 
     /// Transform to different formats (JSON, YAML, shell, etc.).
@@ -184,6 +314,55 @@ pub enum Commands {
         prefix: Option<String>,
         #[arg(long)]
         transform: Option<String>,
+        /// Example file whose comments describe variables the env file leaves undescribed.
+        #[arg(long, default_value = ".env.example")]
+        example: String,
+        /// Order output by key instead of source order.
+        #[arg(long)]
+        sort: bool,
+        /// Split keys on this separator into nested JSON/YAML (e.g. `__` or `_`),
+        /// or dotted keys for properties.
+        #[arg(long, value_name = "SEPARATOR")]
+        nest: Option<String>,
+        /// Casing of nested key segments: preserve, lower, camel or kebab.
+        #[arg(long, default_value = "preserve")]
+        key_case: String,
+        /// Write nested values that read as booleans, numbers or JSON arrays typed.
+        #[arg(long)]
+        coerce: bool,
+        /// Environment or profile the output is for; without --output, writes
+        /// appsettings.{PROFILE}.json (dotnet) or application-{PROFILE}.yml (spring).
+        #[arg(long)]
+        profile: Option<String>,
+        #[command(flatten)]
+        k8s: Box<KubernetesArgs>,
+    },
+
+    /// Import JSON, YAML, Kubernetes, Compose, tfvars or shell exports into .env.
+    Import {
+        /// File to import, or `-` for stdin.
+        file: String,
+        /// Source format; detected from the file name and content when omitted.
+        #[arg(long)]
+        from: Option<String>,
+        /// The .env file to write into, or `-` for stdout.
+        #[arg(long, short, default_value = ".env")]
+        output: String,
+        /// Keys the output already has with another value: ask, keep or overwrite.
+        #[arg(long, default_value = "ask")]
+        on_conflict: String,
+        /// Compose service to read when several set variables.
+        #[arg(long)]
+        service: Option<String>,
+        /// Key transformation (uppercase/lowercase/camelCase/snake_case).
+        #[arg(long)]
+        transform: Option<String>,
+        /// Flatten nested JSON/YAML, joining key segments with this separator.
+        #[arg(long, value_name = "SEPARATOR")]
+        nest: Option<String>,
+        /// Casing the nested keys were written in: preserve, lower, camel or kebab.
+        #[arg(long, default_value = "preserve")]
+        key_case: String,
     },
 
     /// Full migration workflow to secret managers.
@@ -219,6 +398,28 @@ pub enum Commands {
         placeholder: bool,
     },
 
+    /// Generate a typed config loader from .env.example.
+    ///
+    /// Comment tags refine each variable: `@type string|int|float|bool|url|list`,
+    /// `@optional` and `@default [VALUE]`; the rest of the comment becomes the
+    /// field's doc comment.
+    ///
+    /// Example: evnx codegen --lang rust --output src/config.rs
+    Codegen {
+        /// Language: rust, typescript, python or go.
+        #[arg(long)]
+        lang: String,
+        /// Example file listing the variables.
+        #[arg(long, default_value = ".env.example")]
+        example: String,
+        /// File to write; prints to stdout when omitted.
+        #[arg(long, short)]
+        output: Option<String>,
+        /// Package name of the Go file.
+        #[arg(long, default_value = "config")]
+        package: String,
+    },
+
     /// Generate config files from templates.
     Template {
         #[arg(long)]
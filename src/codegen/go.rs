@@ -0,0 +1,174 @@
+//! Go: a `Config` struct with tags for `github.com/caarlos0/env`.
+
+use super::{comment_lines, Field, FieldType, Presence};
+
+/// Go source for `fields` in package `package`, generated from `source`.
+pub fn generate(fields: &[Field], source: &str, package: &str) -> String {
+    // The `Code generated … DO NOT EDIT.` form tells Go tools the file is
+    // generated.
+    let mut out = format!(
+        "// Code generated by evnx from {}; DO NOT EDIT.\n// Regenerate with `evnx codegen`.\n\n",
+        source
+    );
+    out.push_str(&format!("package {}\n\n", package));
+    out.push_str("import \"github.com/caarlos0/env/v11\"\n\n");
+
+    out.push_str("// Config is the configuration read from the environment.\n");
+    out.push_str("type Config struct {\n");
+    for (i, field) in fields.iter().enumerate() {
+        // One field per block, so gofmt has no columns to align.
+        if i > 0 {
+            out.push('\n');
+        }
+        if let Some(description) = &field.description {
+            out.push_str(&comment_lines(description, "\t//"));
+        }
+        out.push_str(&format!(
+            "\t{} {} {}\n",
+            ident(&field.key),
+            ty(field),
+            tag(field)
+        ));
+    }
+    out.push_str("}\n\n");
+
+    out.push_str("// Load reads the Config from the environment.\n");
+    out.push_str("func Load() (Config, error) {\n\treturn env.ParseAs[Config]()\n}\n");
+    out
+}
+
+fn ty(field: &Field) -> String {
+    let ty = match field.ty {
+        FieldType::String | FieldType::Url => "string",
+        FieldType::Int => "int",
+        FieldType::Float => "float64",
+        FieldType::Bool => "bool",
+        FieldType::List => "[]string",
+    };
+    match field.presence {
+        // Left nil when the variable is unset.
+        Presence::Optional => format!("*{}", ty),
+        _ => ty.to_string(),
+    }
+}
+
+/// The struct tag literal of `field`.
+fn tag(field: &Field) -> String {
+    let mut tag = match field.presence {
+        Presence::Required => format!("env:\"{},required,notEmpty\"", field.key),
+        _ => format!("env:\"{}\"", field.key),
+    };
+    if let Presence::Default(value) = &field.presence {
+        // strconv.ParseBool rejects `yes`, `no`, `on` and `off`.
+        let value = match field.default_bool() {
+            Some(value) if field.ty == FieldType::Bool => value.to_string(),
+            _ => value.clone(),
+        };
+        tag.push_str(&format!(" envDefault:{}", quote(&value)));
+    }
+    if field.ty == FieldType::List {
+        tag.push_str(" envSeparator:\",\"");
+    }
+
+    if tag.contains('`') {
+        quote(&tag)
+    } else {
+        format!("`{}`", tag)
+    }
+}
+
+/// A Go interpreted string literal; JSON escapes are all valid Go.
+fn quote(value: &str) -> String {
+    serde_json::to_string(value).expect("strings serialize")
+}
+
+/// Exported field name for `key`: Pascal case, with Go's initialisms in
+/// capitals (`DATABASE_URL` → `DatabaseURL`).
+fn ident(key: &str) -> String {
+    const INITIALISMS: &[&str] = &[
+        "ACL", "API", "ASCII", "AWS", "CPU", "CSS", "DB", "DNS", "EOF", "GCP", "GUID", "HTML",
+        "HTTP", "HTTPS", "ID", "IP", "JSON", "JWT", "LHS", "QPS", "RAM", "RHS", "RPC", "SDK",
+        "SLA", "SMTP", "SQL", "SSH", "SSL", "TCP", "TLS", "TTL", "UDP", "UI", "UID", "URI", "URL",
+        "UTF8", "UUID", "VM", "XML", "XMPP", "XSRF", "XSS",
+    ];
+    let mut name = String::new();
+    for part in key.split('_').filter(|part| !part.is_empty()) {
+        let upper = part.to_ascii_uppercase();
+        if INITIALISMS.contains(&upper.as_str()) {
+            name.push_str(&upper);
+        } else {
+            let lower = part.to_ascii_lowercase();
+            let mut chars = lower.chars();
+            if let Some(first) = chars.next() {
+                name.push(first.to_ascii_uppercase());
+                name.push_str(chars.as_str());
+            }
+        }
+    }
+    // Unexported or empty names (`_`, `_1`) cannot be set by the parser.
+    if !name.starts_with(|c: char| c.is_ascii_uppercase()) {
+        name.insert(0, 'X');
+    }
+    name
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(key: &str, ty: FieldType, presence: Presence) -> Field {
+        Field {
+            key: key.into(),
+            ty,
+            presence,
+            description: None,
+        }
+    }
+
+    #[test]
+    fn test_struct() {
+        let mut url = field("DATABASE_URL", FieldType::Url, Presence::Required);
+        url.description = Some("Primary database".into());
+        let output = generate(
+            &[
+                field("PORT", FieldType::Int, Presence::Default("3000".into())),
+                url,
+                field("DEBUG", FieldType::Bool, Presence::Optional),
+                field("CACHE", FieldType::Bool, Presence::Default("on".into())),
+                field("MODE", FieldType::String, Presence::Default("on".into())),
+                field("HOSTS", FieldType::List, Presence::Default("a,b".into())),
+                field(
+                    "GREETING",
+                    FieldType::String,
+                    Presence::Default("`hi`".into()),
+                ),
+            ],
+            ".env.example",
+            "config",
+        );
+
+        assert!(output.starts_with("// Code generated by evnx from .env.example; DO NOT EDIT.\n"));
+        assert!(output.contains("package config\n"));
+        assert!(output.contains("\tPort int `env:\"PORT\" envDefault:\"3000\"`\n"));
+        assert!(output.contains(
+            "\n\t// Primary database\n\tDatabaseURL string `env:\"DATABASE_URL,required,notEmpty\"`\n"
+        ));
+        assert!(output.contains("\tDebug *bool `env:\"DEBUG\"`\n"));
+        assert!(output.contains("\tCache bool `env:\"CACHE\" envDefault:\"true\"`\n"));
+        assert!(output.contains("\tMode string `env:\"MODE\" envDefault:\"on\"`\n"));
+        assert!(output
+            .contains("\tHosts []string `env:\"HOSTS\" envDefault:\"a,b\" envSeparator:\",\"`\n"));
+        assert!(
+            output.contains("\tGreeting string \"env:\\\"GREETING\\\" envDefault:\\\"`hi`\\\"\"\n")
+        );
+        assert!(output.contains("return env.ParseAs[Config]()"));
+    }
+
+    #[test]
+    fn test_ident() {
+        assert_eq!(ident("DATABASE_URL"), "DatabaseURL");
+        assert_eq!(ident("STRIPE_API_KEY"), "StripeAPIKey");
+        assert_eq!(ident("user_id"), "UserID");
+        assert_eq!(ident("_1"), "X1");
+    }
+}
@@ -0,0 +1,319 @@
+//! Typed configuration loaders generated from `.env.example`.
+//!
+//! Each variable of the example file becomes a field. Comment tags on the
+//! variable, next to `@secret`/`@config`, refine it:
+//!
+//! - `@type string|int|float|bool|url|list` sets the type; without it the
+//!   type is inferred from the example value (`true` → bool, `3000` → int,
+//!   `0.5` → float, `https://…` → url, anything else string). Lists are
+//!   comma-separated.
+//! - `@optional` makes the field optional.
+//! - `@default VALUE` gives it a default; a bare `@default` makes the
+//!   example value the default.
+//! - Anything else is required.
+//!
+//! The rest of the comment is the field's doc comment. Variables the file
+//! leaves undescribed take their description from the bundled service
+//! schema, when it knows them.
+
+pub mod go;
+pub mod python;
+pub mod rust;
+pub mod typescript;
+
+use anyhow::{bail, Result};
+
+use crate::core::converter::Variable;
+use crate::formats::import::is_identifier;
+use crate::schema::describe_var;
+
+/// Language to generate a loader for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lang {
+    Rust,
+    TypeScript,
+    Python,
+    Go,
+}
+
+impl Lang {
+    /// Parse a `--lang` name
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "rust" | "rs" => Some(Lang::Rust),
+            "typescript" | "ts" => Some(Lang::TypeScript),
+            "python" | "py" => Some(Lang::Python),
+            "go" | "golang" => Some(Lang::Go),
+            _ => None,
+        }
+    }
+}
+
+/// Type of a field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldType {
+    String,
+    Int,
+    Float,
+    Bool,
+    Url,
+    /// Comma-separated strings
+    List,
+}
+
+impl FieldType {
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "string" | "str" => Some(FieldType::String),
+            "int" | "integer" | "number" => Some(FieldType::Int),
+            "float" | "decimal" => Some(FieldType::Float),
+            "bool" | "boolean" => Some(FieldType::Bool),
+            "url" => Some(FieldType::Url),
+            "list" | "array" => Some(FieldType::List),
+            _ => None,
+        }
+    }
+
+    /// Type of an example value.
+    fn infer(value: &str) -> Self {
+        if value.eq_ignore_ascii_case("true") || value.eq_ignore_ascii_case("false") {
+            FieldType::Bool
+        } else if parse_int(value).is_some() {
+            FieldType::Int
+        } else if value.contains('.') && parse_float(value).is_some() {
+            FieldType::Float
+        } else if value.contains("://") && !value.contains(char::is_whitespace) {
+            FieldType::Url
+        } else {
+            FieldType::String
+        }
+    }
+}
+
+/// Whether a field must be set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Presence {
+    Required,
+    Optional,
+    Default(String),
+}
+
+/// A field of the generated config.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Field {
+    /// The environment variable
+    pub key: String,
+    pub ty: FieldType,
+    pub presence: Presence,
+    /// Doc comment, without tags
+    pub description: Option<String>,
+}
+
+impl Field {
+    /// The default as a boolean, for bool fields.
+    pub fn default_bool(&self) -> Option<bool> {
+        match &self.presence {
+            Presence::Default(value) => parse_bool(value),
+            _ => None,
+        }
+    }
+}
+
+/// Fields of the example file's variables.
+pub fn fields(vars: &[Variable]) -> Result<Vec<Field>> {
+    vars.iter().map(field).collect()
+}
+
+fn field(var: &Variable) -> Result<Field> {
+    if !is_identifier(&var.key) {
+        bail!("{}: not an identifier, so it cannot be a field", var.key);
+    }
+    let words: Vec<&str> = var
+        .description
+        .as_deref()
+        .map(|d| d.split_whitespace().collect())
+        .unwrap_or_default();
+    let tag_value = |tag: &str| {
+        let at = words.iter().position(|w| *w == tag)?;
+        Some(words.get(at + 1).filter(|w| !w.starts_with('@')).copied())
+    };
+
+    let ty = match tag_value("@type") {
+        Some(Some(name)) => match FieldType::parse(name) {
+            Some(ty) => ty,
+            None => bail!(
+                "{}: unknown @type '{}'; expected string, int, float, bool, url or list",
+                var.key,
+                name
+            ),
+        },
+        Some(None) => bail!("{}: @type needs a type", var.key),
+        None => FieldType::infer(&var.value),
+    };
+
+    let presence = match tag_value("@default") {
+        Some(value) => Presence::Default(value.unwrap_or(&var.value).to_string()),
+        None if words.contains(&"@optional") => Presence::Optional,
+        None => Presence::Required,
+    };
+    if let Presence::Default(value) = &presence {
+        let valid = match ty {
+            FieldType::Int => parse_int(value).is_some(),
+            FieldType::Float => parse_float(value).is_some(),
+            FieldType::Bool => parse_bool(value).is_some(),
+            _ => true,
+        };
+        if !valid {
+            bail!("{}: default '{}' is not a valid {:?}", var.key, value, ty);
+        }
+    }
+
+    Ok(Field {
+        key: var.key.clone(),
+        ty,
+        presence,
+        description: description(var).or_else(|| describe_var(&var.key)),
+    })
+}
+
+/// The variable's description with its tags (and the values of `@type`
+/// and `@default`) removed, line by line.
+fn description(var: &Variable) -> Option<String> {
+    let text = var.description.as_deref()?;
+    let lines: Vec<String> = text
+        .lines()
+        .map(|line| {
+            let mut kept = Vec::new();
+            let mut words = line.split_whitespace().peekable();
+            while let Some(word) = words.next() {
+                if !word.starts_with('@') {
+                    kept.push(word);
+                    continue;
+                }
+                if word == "@type" || word == "@default" {
+                    words.next_if(|next| !next.starts_with('@'));
+                }
+            }
+            kept.join(" ")
+        })
+        .collect();
+
+    let text = lines.join("\n").trim().to_string();
+    (!text.is_empty()).then_some(text)
+}
+
+/// Integers as the example writes them: no sign but `-`, no leading zeros.
+pub(crate) fn parse_int(value: &str) -> Option<i64> {
+    let digits = value.strip_prefix('-').unwrap_or(value);
+    if digits.is_empty()
+        || !digits.bytes().all(|b| b.is_ascii_digit())
+        || (digits.len() > 1 && digits.starts_with('0'))
+    {
+        return None;
+    }
+    value.parse().ok()
+}
+
+pub(crate) fn parse_float(value: &str) -> Option<f64> {
+    let unsigned = value.strip_prefix('-').unwrap_or(value);
+    if unsigned.is_empty() || !unsigned.bytes().all(|b| b.is_ascii_digit() || b == b'.') {
+        return None;
+    }
+    value.parse().ok()
+}
+
+/// Booleans as the Rust, Python and TypeScript loaders read them. Go's
+/// `strconv.ParseBool` accepts fewer spellings, so the Go loader is given
+/// bool defaults as `true`/`false`.
+pub(crate) fn parse_bool(value: &str) -> Option<bool> {
+    match value.to_ascii_lowercase().as_str() {
+        "true" | "1" | "yes" | "on" => Some(true),
+        "false" | "0" | "no" | "off" => Some(false),
+        _ => None,
+    }
+}
+
+/// Items of a list value.
+pub(crate) fn list_items(value: &str) -> Vec<&str> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+/// `prefix`ed comment lines for `text`, e.g. `///` doc comments.
+pub(crate) fn comment_lines(text: &str, prefix: &str) -> String {
+    text.lines()
+        .map(|line| format!("{} {}", prefix, line).trim_end().to_string() + "\n")
+        .collect()
+}
+
+/// Header naming the example file the code was generated from.
+pub(crate) fn header(comment: &str, source: &str) -> String {
+    format!(
+        "{} Generated by evnx from {}; do not edit.\n{} Regenerate with `evnx codegen`.\n",
+        comment, source, comment
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn var(key: &str, value: &str, description: Option<&str>) -> Variable {
+        let mut var = Variable::new(key, value);
+        var.description = description.map(String::from);
+        var
+    }
+
+    #[test]
+    fn test_fields_from_tags_and_values() {
+        let fields = fields(&[
+            var("PORT", "3000", Some("HTTP port @default")),
+            var("DEBUG", "false", Some("@optional")),
+            var(
+                "DATABASE_URL",
+                "postgres://localhost/app",
+                Some("Primary database"),
+            ),
+            var("RATIO", "0.5", None),
+            var(
+                "HOSTS",
+                "a,b",
+                Some("@type list @default a,b Allowed hosts"),
+            ),
+            var("EVNX_TEST_TOKEN", "", Some("@secret")),
+        ])
+        .unwrap();
+
+        assert_eq!(fields[0].ty, FieldType::Int);
+        assert_eq!(fields[0].presence, Presence::Default("3000".into()));
+        assert_eq!(fields[0].description.as_deref(), Some("HTTP port"));
+        assert_eq!(fields[1].ty, FieldType::Bool);
+        assert_eq!(fields[1].presence, Presence::Optional);
+        assert_eq!(fields[1].description, None);
+        assert_eq!(fields[2].ty, FieldType::Url);
+        assert_eq!(fields[2].presence, Presence::Required);
+        assert_eq!(fields[3].ty, FieldType::Float);
+        assert_eq!(fields[4].ty, FieldType::List);
+        assert_eq!(fields[4].presence, Presence::Default("a,b".into()));
+        assert_eq!(fields[4].description.as_deref(), Some("Allowed hosts"));
+        assert_eq!(fields[5].ty, FieldType::String);
+        assert_eq!(fields[5].description, None);
+    }
+
+    #[test]
+    fn test_field_errors() {
+        assert!(fields(&[var("PORT", "x", Some("@type int @default"))]).is_err());
+        assert!(fields(&[var("PORT", "x", Some("@type port"))]).is_err());
+        assert!(fields(&[var("PORT", "x", Some("@type"))]).is_err());
+        assert!(fields(&[var("APP-PORT", "1", None)]).is_err());
+    }
+
+    #[test]
+    fn test_schema_descriptions() {
+        let fields = fields(&[var("DB_PASSWORD", "", None)]).unwrap();
+        assert!(fields[0].description.is_some());
+    }
+}
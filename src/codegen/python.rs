@@ -0,0 +1,201 @@
+//! Python: a pydantic-settings `Settings` class.
+
+use super::{header, list_items, Field, FieldType, Presence};
+use crate::core::converter::to_snake_case;
+use crate::formats::escape::python_string;
+
+/// Python source for `fields`, generated from `source`.
+pub fn generate(fields: &[Field], source: &str) -> String {
+    let lists: Vec<String> = fields
+        .iter()
+        .filter(|f| f.ty == FieldType::List)
+        .map(|f| python_string(&ident(&f.key)))
+        .collect();
+    let needs_url = fields.iter().any(|f| f.ty == FieldType::Url);
+    let needs_alias = fields.iter().any(|f| ident(&f.key) != f.key.to_lowercase());
+
+    let mut pydantic = Vec::new();
+    if needs_url {
+        pydantic.push("AnyUrl");
+    }
+    if needs_alias {
+        pydantic.push("Field");
+    }
+    if !lists.is_empty() {
+        pydantic.push("field_validator");
+    }
+
+    let mut out = header("#", source);
+    out.push('\n');
+    if !lists.is_empty() {
+        out.push_str("from typing import Annotated\n\n");
+    }
+    if !pydantic.is_empty() {
+        out.push_str(&format!("from pydantic import {}\n", pydantic.join(", ")));
+    }
+    if lists.is_empty() {
+        out.push_str("from pydantic_settings import BaseSettings, SettingsConfigDict\n");
+    } else {
+        out.push_str("from pydantic_settings import BaseSettings, NoDecode, SettingsConfigDict\n");
+    }
+
+    out.push_str("\n\nclass Settings(BaseSettings):\n");
+    out.push_str("    \"\"\"Configuration read from the environment.\"\"\"\n\n");
+    out.push_str(
+        "    model_config = SettingsConfigDict(env_ignore_empty=True, validate_default=True)\n",
+    );
+
+    for field in fields {
+        out.push('\n');
+        out.push_str(&format!(
+            "    {}: {}{}\n",
+            ident(&field.key),
+            annotation(field),
+            default(field)
+        ));
+        if let Some(description) = &field.description {
+            out.push_str(&format!("    {}\n", python_string(description)));
+        }
+    }
+
+    if !lists.is_empty() {
+        out.push_str(&format!(
+            r#"
+    @field_validator({}, mode="before")
+    @classmethod
+    def _split_list(cls, value):
+        if isinstance(value, str):
+            return [item.strip() for item in value.split(",") if item.strip()]
+        return value
+"#,
+            lists.join(", ")
+        ));
+    }
+
+    out
+}
+
+fn annotation(field: &Field) -> String {
+    let ty = match field.ty {
+        FieldType::String => "str",
+        FieldType::Int => "int",
+        FieldType::Float => "float",
+        FieldType::Bool => "bool",
+        FieldType::Url => "AnyUrl",
+        // NoDecode keeps pydantic-settings from reading the value as JSON.
+        FieldType::List => "Annotated[list[str], NoDecode]",
+    };
+    match field.presence {
+        Presence::Optional => format!("{} | None", ty),
+        _ => ty.to_string(),
+    }
+}
+
+/// ` = …` after the annotation: the default, wrapped in `Field` when the
+/// attribute name is not the variable's.
+fn default(field: &Field) -> String {
+    let value = match &field.presence {
+        Presence::Required => None,
+        Presence::Optional => Some("None".to_string()),
+        Presence::Default(value) => Some(literal(field, value)),
+    };
+
+    let name = ident(&field.key);
+    if name == field.key.to_lowercase() {
+        return value.map(|v| format!(" = {}", v)).unwrap_or_default();
+    }
+    let alias = format!("validation_alias={}", python_string(&field.key));
+    match value {
+        Some(value) => format!(" = Field({}, {})", value, alias),
+        None => format!(" = Field({})", alias),
+    }
+}
+
+/// The default value as a Python literal of the field's type.
+fn literal(field: &Field, value: &str) -> String {
+    match field.ty {
+        FieldType::Int | FieldType::Float => value.to_string(),
+        FieldType::Bool => match field.default_bool() {
+            Some(true) => "True".to_string(),
+            _ => "False".to_string(),
+        },
+        FieldType::List => {
+            let items: Vec<String> = list_items(value).into_iter().map(python_string).collect();
+            format!("[{}]", items.join(", "))
+        }
+        FieldType::String | FieldType::Url => python_string(value),
+    }
+}
+
+/// Attribute name for `key`: snake case, with `_` after keywords.
+fn ident(key: &str) -> String {
+    const KEYWORDS: &[&str] = &[
+        "and", "as", "assert", "async", "await", "break", "class", "continue", "def", "del",
+        "elif", "else", "except", "finally", "for", "from", "global", "if", "import", "in", "is",
+        "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while", "with",
+        "yield",
+    ];
+    let name = to_snake_case(key).to_lowercase();
+    if KEYWORDS.contains(&name.as_str()) {
+        format!("{}_", name)
+    } else {
+        name
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(key: &str, ty: FieldType, presence: Presence) -> Field {
+        Field {
+            key: key.into(),
+            ty,
+            presence,
+            description: None,
+        }
+    }
+
+    #[test]
+    fn test_settings() {
+        let mut url = field("DATABASE_URL", FieldType::Url, Presence::Required);
+        url.description = Some("Primary \"db\"".into());
+        let output = generate(
+            &[
+                url,
+                field("PORT", FieldType::Int, Presence::Default("3000".into())),
+                field("DEBUG", FieldType::Bool, Presence::Default("yes".into())),
+                field("RATIO", FieldType::Float, Presence::Optional),
+                field("HOSTS", FieldType::List, Presence::Default("a, b".into())),
+                field("CLASS", FieldType::String, Presence::Required),
+                field("apiKey", FieldType::String, Presence::Default("x".into())),
+            ],
+            ".env.example",
+        );
+
+        assert!(output.starts_with("# Generated by evnx from .env.example; do not edit.\n"));
+        assert!(output.contains("from typing import Annotated\n"));
+        assert!(output.contains("from pydantic import AnyUrl, Field, field_validator\n"));
+        assert!(output.contains("import BaseSettings, NoDecode, SettingsConfigDict\n"));
+        assert!(output.contains("    database_url: AnyUrl\n    \"Primary \\\"db\\\"\"\n"));
+        assert!(output.contains("    port: int = 3000\n"));
+        assert!(output.contains("    debug: bool = True\n"));
+        assert!(output.contains("    ratio: float | None = None\n"));
+        assert!(output.contains("    hosts: Annotated[list[str], NoDecode] = [\"a\", \"b\"]\n"));
+        assert!(output.contains("    class_: str = Field(validation_alias=\"CLASS\")\n"));
+        assert!(output.contains("    api_key: str = Field(\"x\", validation_alias=\"apiKey\")\n"));
+        assert!(output.contains("    @field_validator(\"hosts\", mode=\"before\")\n"));
+    }
+
+    #[test]
+    fn test_minimal_imports() {
+        let output = generate(
+            &[field("NAME", FieldType::String, Presence::Required)],
+            ".env.example",
+        );
+        assert!(!output.contains("from typing"));
+        assert!(!output.contains("from pydantic import"));
+        assert!(output.contains("from pydantic_settings import BaseSettings, SettingsConfigDict\n"));
+        assert!(output.contains("    name: str\n"));
+    }
+}
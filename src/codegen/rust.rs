@@ -0,0 +1,258 @@
+//! Rust: a `Config` struct with `from_env`, using only the standard library.
+
+use super::{comment_lines, header, Field, FieldType, Presence};
+use crate::core::converter::to_snake_case;
+
+/// Rust source for `fields`, generated from `source`.
+pub fn generate(fields: &[Field], source: &str) -> String {
+    let uses = |f: &dyn Fn(&Field) -> bool| fields.iter().any(f);
+    let needs_required = uses(&|f| f.presence == Presence::Required);
+    let needs_parse = uses(&|f| matches!(f.ty, FieldType::Int | FieldType::Float));
+    let needs_bool = uses(&|f| f.ty == FieldType::Bool);
+    let needs_list = uses(&|f| f.ty == FieldType::List);
+
+    let mut out = header("//", source);
+    out.push_str("\nuse std::env;\nuse std::fmt;\n\n");
+
+    out.push_str("/// Configuration read from the environment.\n");
+    out.push_str("#[derive(Debug, Clone, PartialEq)]\n");
+    out.push_str("pub struct Config {\n");
+    for field in fields {
+        if let Some(description) = &field.description {
+            out.push_str(&comment_lines(description, "    ///"));
+        }
+        out.push_str(&format!("    pub {}: {},\n", ident(&field.key), ty(field)));
+    }
+    out.push_str("}\n\n");
+
+    out.push_str("impl Config {\n");
+    out.push_str("    /// Read the configuration from the process environment.\n");
+    out.push_str("    pub fn from_env() -> Result<Self, ConfigError> {\n");
+    out.push_str("        Ok(Self {\n");
+    for field in fields {
+        out.push_str(&format!(
+            "            {}: {},\n",
+            ident(&field.key),
+            expression(field)
+        ));
+    }
+    out.push_str("        })\n    }\n}\n\n");
+
+    out.push_str(
+        r#"/// Error reading [`Config`] from the environment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigError {
+    /// A required variable is unset or empty.
+    Missing(&'static str),
+    /// A variable's value cannot be read as its type.
+    Invalid { key: &'static str, message: String },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Missing(key) => write!(f, "{} is not set", key),
+            ConfigError::Invalid { key, message } => write!(f, "{} is invalid: {}", key, message),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// The variable's value; unset and empty are the same.
+fn var(key: &'static str) -> Option<String> {
+    env::var(key).ok().filter(|value| !value.is_empty())
+}
+"#,
+    );
+
+    if needs_required {
+        out.push_str(
+            r#"
+fn required(key: &'static str) -> Result<String, ConfigError> {
+    var(key).ok_or(ConfigError::Missing(key))
+}
+"#,
+        );
+    }
+    if needs_parse {
+        out.push_str(
+            r#"
+fn parse<T>(key: &'static str, value: String) -> Result<T, ConfigError>
+where
+    T: std::str::FromStr,
+    T::Err: fmt::Display,
+{
+    value.parse().map_err(|e: T::Err| ConfigError::Invalid {
+        key,
+        message: e.to_string(),
+    })
+}
+"#,
+        );
+    }
+    if needs_bool {
+        out.push_str(
+            r#"
+fn parse_bool(key: &'static str, value: String) -> Result<bool, ConfigError> {
+    match value.to_ascii_lowercase().as_str() {
+        "true" | "1" | "yes" | "on" => Ok(true),
+        "false" | "0" | "no" | "off" => Ok(false),
+        _ => Err(ConfigError::Invalid {
+            key,
+            message: format!("expected true or false, got {:?}", value),
+        }),
+    }
+}
+"#,
+        );
+    }
+    if needs_list {
+        out.push_str(
+            r#"
+fn parse_list(_key: &'static str, value: String) -> Result<Vec<String>, ConfigError> {
+    Ok(value
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(String::from)
+        .collect())
+}
+"#,
+        );
+    }
+
+    out
+}
+
+fn ty(field: &Field) -> String {
+    let ty = match field.ty {
+        FieldType::String | FieldType::Url => "String",
+        FieldType::Int => "i64",
+        FieldType::Float => "f64",
+        FieldType::Bool => "bool",
+        FieldType::List => "Vec<String>",
+    };
+    match field.presence {
+        Presence::Optional => format!("Option<{}>", ty),
+        _ => ty.to_string(),
+    }
+}
+
+/// The expression reading `field` in `from_env`.
+fn expression(field: &Field) -> String {
+    let key = format!("{:?}", field.key);
+    let raw = match &field.presence {
+        Presence::Required => format!("required({})?", key),
+        Presence::Optional => format!("var({})", key),
+        Presence::Default(value) => {
+            format!("var({}).unwrap_or_else(|| {:?}.to_string())", key, value)
+        }
+    };
+
+    let parser = match field.ty {
+        FieldType::String | FieldType::Url => return raw,
+        FieldType::Int | FieldType::Float => "parse",
+        FieldType::Bool => "parse_bool",
+        FieldType::List => "parse_list",
+    };
+    match &field.presence {
+        Presence::Optional => format!(
+            "{}.map(|value| {}({}, value)).transpose()?",
+            raw, parser, key
+        ),
+        _ => format!("{}({}, {})?", parser, key, raw),
+    }
+}
+
+/// Field name for `key`: snake case, escaped if it is a keyword.
+fn ident(key: &str) -> String {
+    const KEYWORDS: &[&str] = &[
+        "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do",
+        "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in",
+        "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+        "return", "static", "struct", "trait", "true", "try", "type", "typeof", "unsafe",
+        "unsized", "use", "virtual", "where", "while", "yield",
+    ];
+    let name = to_snake_case(key).to_lowercase();
+    if matches!(name.as_str(), "self" | "super" | "crate") {
+        format!("{}_", name)
+    } else if KEYWORDS.contains(&name.as_str()) {
+        format!("r#{}", name)
+    } else {
+        name
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Command;
+
+    fn field(key: &str, ty: FieldType, presence: Presence) -> Field {
+        Field {
+            key: key.into(),
+            ty,
+            presence,
+            description: None,
+        }
+    }
+
+    fn sample() -> Vec<Field> {
+        let mut url = field("DATABASE_URL", FieldType::Url, Presence::Required);
+        url.description = Some("Primary database\n\nPostgres only".into());
+        vec![
+            url,
+            field("PORT", FieldType::Int, Presence::Default("3000".into())),
+            field("RATIO", FieldType::Float, Presence::Optional),
+            field("DEBUG", FieldType::Bool, Presence::Default("off".into())),
+            field("HOSTS", FieldType::List, Presence::Required),
+            field("TYPE", FieldType::String, Presence::Default("a\"b".into())),
+        ]
+    }
+
+    #[test]
+    fn test_struct_and_loader() {
+        let output = generate(&sample(), ".env.example");
+        assert!(output.starts_with("// Generated by evnx from .env.example; do not edit.\n"));
+        assert!(output.contains(
+            "    /// Primary database\n    ///\n    /// Postgres only\n    pub database_url: String,\n"
+        ));
+        assert!(output.contains("    pub port: i64,\n"));
+        assert!(output.contains("    pub ratio: Option<f64>,\n"));
+        assert!(output.contains("    pub r#type: String,\n"));
+        assert!(output.contains("            database_url: required(\"DATABASE_URL\")?,\n"));
+        assert!(output.contains(
+            "            port: parse(\"PORT\", var(\"PORT\").unwrap_or_else(|| \"3000\".to_string()))?,\n"
+        ));
+        assert!(output
+            .contains("            ratio: var(\"RATIO\").map(|value| parse(\"RATIO\", value)).transpose()?,\n"));
+        assert!(
+            output.contains("            hosts: parse_list(\"HOSTS\", required(\"HOSTS\")?)?,\n")
+        );
+
+        let minimal = generate(
+            &[field("NAME", FieldType::String, Presence::Optional)],
+            ".env.example",
+        );
+        assert!(!minimal.contains("fn required"));
+        assert!(!minimal.contains("fn parse"));
+    }
+
+    #[test]
+    fn test_generated_code_compiles() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.rs");
+        std::fs::write(&path, generate(&sample(), ".env.example")).unwrap();
+
+        let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".into());
+        let status = Command::new(rustc)
+            .args(["--edition", "2021", "--crate-type", "lib", "-D", "warnings"])
+            .arg("--out-dir")
+            .arg(dir.path())
+            .arg(&path)
+            .status()
+            .unwrap();
+        assert!(status.success());
+    }
+}
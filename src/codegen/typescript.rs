@@ -0,0 +1,150 @@
+//! TypeScript: a zod schema, its inferred type and a `loadEnv` function.
+
+use super::{comment_lines, header, Field, FieldType, Presence};
+use crate::formats::import::is_identifier;
+
+/// TypeScript source for `fields`, generated from `source`.
+pub fn generate(fields: &[Field], source: &str) -> String {
+    let mut out = header("//", source);
+    out.push_str("\nimport { z } from \"zod\";\n\n");
+
+    if fields.iter().any(|f| f.ty == FieldType::Bool) {
+        out.push_str(
+            r#"const flag = z
+  .string()
+  .transform((value) => value.toLowerCase())
+  .pipe(z.enum(["true", "1", "yes", "on", "false", "0", "no", "off"]))
+  .transform((value) => ["true", "1", "yes", "on"].includes(value));
+
+"#,
+        );
+    }
+    if fields.iter().any(|f| f.ty == FieldType::List) {
+        out.push_str(
+            r#"const list = z.string().transform((value) =>
+  value
+    .split(",")
+    .map((item) => item.trim())
+    .filter((item) => item !== ""),
+);
+
+"#,
+        );
+    }
+
+    out.push_str("export const EnvSchema = z.object({\n");
+    for field in fields {
+        if let Some(description) = &field.description {
+            out.push_str(&doc_comment(description));
+        }
+        // Keys are identifiers already, but `__proto__` must stay quoted.
+        let key = if is_identifier(&field.key) && field.key != "__proto__" {
+            field.key.clone()
+        } else {
+            string(&field.key)
+        };
+        out.push_str(&format!("  {}: {},\n", key, schema(field)));
+    }
+    out.push_str("});\n\n");
+
+    out.push_str(
+        r#"export type Env = z.infer<typeof EnvSchema>;
+
+/** Parse the environment; unset and empty variables are the same. */
+export function loadEnv(
+  source: Record<string, string | undefined> = process.env,
+): Env {
+  const values = Object.fromEntries(
+    Object.entries(source).filter(([, value]) => value !== ""),
+  );
+  return EnvSchema.parse(values);
+}
+"#,
+    );
+    out
+}
+
+/// The zod schema of `field`.
+fn schema(field: &Field) -> String {
+    let base = match field.ty {
+        FieldType::String => "z.string()",
+        FieldType::Url => "z.string().url()",
+        FieldType::Int => "z.coerce.number().int()",
+        FieldType::Float => "z.coerce.number()",
+        FieldType::Bool => "flag",
+        FieldType::List => "list",
+    };
+    match &field.presence {
+        Presence::Required => base.to_string(),
+        Presence::Optional => format!("{}.optional()", base),
+        // Defaults go in as input, before the schema parses them.
+        Presence::Default(value) => match field.ty {
+            FieldType::Int | FieldType::Float => format!("{}.default({})", base, value),
+            _ => format!("{}.default({})", base, string(value)),
+        },
+    }
+}
+
+/// A JavaScript string literal.
+fn string(value: &str) -> String {
+    serde_json::to_string(value).expect("strings serialize")
+}
+
+/// A `/** … */` comment, indented for an object member.
+fn doc_comment(description: &str) -> String {
+    let text = description.replace("*/", "*\\/");
+    if !text.contains('\n') {
+        return format!("  /** {} */\n", text);
+    }
+    format!("  /**\n{}   */\n", comment_lines(&text, "   *"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(key: &str, ty: FieldType, presence: Presence) -> Field {
+        Field {
+            key: key.into(),
+            ty,
+            presence,
+            description: None,
+        }
+    }
+
+    #[test]
+    fn test_schema() {
+        let mut port = field("PORT", FieldType::Int, Presence::Default("3000".into()));
+        port.description = Some("HTTP port */".into());
+        let output = generate(
+            &[
+                port,
+                field("DEBUG", FieldType::Bool, Presence::Default("no".into())),
+                field("SENTRY_DSN", FieldType::Url, Presence::Optional),
+                field("HOSTS", FieldType::List, Presence::Required),
+                field("NAME", FieldType::String, Presence::Default("a\"b".into())),
+            ],
+            ".env.example",
+        );
+
+        assert!(output.starts_with("// Generated by evnx from .env.example; do not edit.\n"));
+        assert!(output
+            .contains("  /** HTTP port *\\/ */\n  PORT: z.coerce.number().int().default(3000),\n"));
+        assert!(output.contains("  DEBUG: flag.default(\"no\"),\n"));
+        assert!(output.contains("  SENTRY_DSN: z.string().url().optional(),\n"));
+        assert!(output.contains("  HOSTS: list,\n"));
+        assert!(output.contains("  NAME: z.string().default(\"a\\\"b\"),\n"));
+        assert!(output.contains("const flag = z"));
+        assert!(output.contains("const list = z"));
+    }
+
+    #[test]
+    fn test_helpers_only_when_used() {
+        let mut name = field("NAME", FieldType::String, Presence::Required);
+        name.description = Some("First\nSecond".into());
+        let output = generate(&[name], ".env.example");
+        assert!(!output.contains("const flag"));
+        assert!(!output.contains("const list"));
+        assert!(output.contains("  /**\n   * First\n   * Second\n   */\n  NAME: z.string(),\n"));
+    }
+}
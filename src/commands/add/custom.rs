@@ -194,7 +194,7 @@ mod tests {
 
     #[test]
     fn test_handle_creates_custom_section() {
-        let dir = TempDir::new().unwrap();
+        let _dir = TempDir::new().unwrap();
 
         // Simulate adding one variable (non-interactive test)
         // Note: Full interactive testing requires mocking dialoguer
@@ -60,7 +60,9 @@
 //! - Integrity manifest: store a SHA-256 hash of the plaintext so the restore
 //!   command can verify it was not silently corrupted after writing.
 
-use anyhow::{anyhow, Context, Result};
+use anyhow::Result;
+#[cfg(feature = "backup")]
+use anyhow::{anyhow, Context};
 use colored::*;
 
 /// Entry point for the `backup` subcommand.
@@ -76,6 +78,7 @@ use colored::*;
 pub fn run(env: String, output: Option<String>, verbose: bool) -> Result<()> {
     // ── Feature-disabled stub ────────────────────────────────────────────────
     #[cfg(not(feature = "backup"))]
+    #[allow(clippy::needless_return)]
     {
         // Reference parameters explicitly to silence unused-variable warnings
         // without renaming them, keeping the signature consistent with main.rs.
@@ -451,6 +454,7 @@ pub struct BackupMetadata {
 ///
 /// This check is intentionally lenient — it exists to warn the user if they
 /// accidentally pass the wrong file, not to enforce strict `.env` grammar.
+#[cfg_attr(not(feature = "backup"), allow(dead_code))]
 fn looks_like_dotenv(content: &str) -> bool {
     if content.trim().is_empty() {
         return true;
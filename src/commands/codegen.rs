@@ -0,0 +1,70 @@
+//! Codegen command - generate a typed config loader from .env.example
+use anyhow::{bail, Context, Result};
+use colored::*;
+use std::fs;
+
+use crate::codegen::{self, Lang};
+use crate::core::{converter::document_variables, Document, Parser, ParserConfig};
+
+// Run the codegen command
+//
+// # Arguments
+//
+// * `lang` - Language to generate: rust, typescript, python or go
+// * `example` - Example file listing the variables, with type tags in comments
+// * `output` - Optional output file (None = stdout)
+// * `package` - Go package name
+pub fn run(
+    lang: String,
+    example: String,
+    output: Option<String>,
+    package: String,
+    verbose: bool,
+) -> Result<()> {
+    let Some(lang) = Lang::parse(&lang.to_lowercase()) else {
+        bail!(
+            "Unknown language '{}'; expected rust, typescript, python or go",
+            lang
+        );
+    };
+
+    // Example values are samples, not references to expand.
+    let parser = Parser::new(ParserConfig {
+        allow_expansion: false,
+        ..ParserConfig::default()
+    });
+    let env_file = parser
+        .parse_file(&example)
+        .with_context(|| format!("Failed to parse {}", example))?;
+    let content =
+        fs::read_to_string(&example).with_context(|| format!("Failed to read {}", example))?;
+    let vars = document_variables(&env_file.vars, &Document::parse(&content), None);
+
+    let fields = codegen::fields(&vars)?;
+    if verbose {
+        eprintln!("Generating {:?} for {} variables", lang, fields.len());
+    }
+
+    let source = match lang {
+        Lang::Rust => codegen::rust::generate(&fields, &example),
+        Lang::TypeScript => codegen::typescript::generate(&fields, &example),
+        Lang::Python => codegen::python::generate(&fields, &example),
+        Lang::Go => codegen::go::generate(&fields, &example, &package),
+    };
+
+    match output {
+        Some(path) => {
+            fs::write(&path, &source).with_context(|| format!("Failed to write to {}", path))?;
+            println!(
+                "{} Generated {} fields from {}",
+                "✓".green(),
+                fields.len(),
+                example
+            );
+            println!("Output written to: {}", path);
+        }
+        None => print!("{}", source),
+    }
+
+    Ok(())
+}
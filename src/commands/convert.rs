@@ -1,14 +1,16 @@
 //! Convert command - transform .env to different formats
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use colored::*;
 use dialoguer::Select;
 use std::fs;
+use std::path::Path;
 
+use crate::cli::KubernetesArgs;
 use crate::core::{
-    converter::{ConvertOptions, Converter, KeyTransform},
-    Parser,
+    converter::{document_variables, ConvertOptions, Converter, KeyTransform},
+    Document, KeyPath, Parser, SegmentCase,
 };
-use crate::formats;
+use crate::formats::{self, KubernetesOutput};
 
 // Run the convert command
 //
@@ -24,16 +26,30 @@ use crate::formats;
 // * `base64` - Base64-encode all values
 // * `prefix` - Add prefix to all keys
 // * `transform` - Key transformation (uppercase/lowercase/camelCase/snake_case)
+// * `example` - Example file whose comments describe variables `env` leaves undescribed
+// * `sort` - Order output by key instead of source order
+// * `nest` - Separator splitting keys into nested paths (json, yaml, properties,
+//   appsettings, spring)
+// * `key_case` - Casing of nested path segments (preserve/lower/camel/kebab)
+// * `coerce` - Type booleans, numbers and JSON arrays in nested and Python output
+// * `profile` - Environment/profile naming the appsettings or application file
+// * `k8s` - Names, namespace and labels for Kubernetes outputs
 // * `verbose` - Enable verbose output
 //
-// # Supported Formats (14)
+// # Supported Formats (40)
 //
-// **Generic:** json, yaml, shell
+// **Generic:** json, yaml
+// **Shells:** shell, fish, powershell, cmd, nushell, direnv, direnv-dotenv
+//   (`shell:auto` picks the dialect of `$SHELL`)
 // **Cloud:** aws-secrets, gcp-secrets, azure-keyvault
 // **CI/CD:** github-actions
-// **Containers:** docker-compose, kubernetes
+// **Containers:** docker-compose, kubernetes, kubernetes-split, kubernetes-env,
+//   kubernetes-envfrom, kustomize, helm, sealed-secret
 // **IaC:** terraform
+// **App config:** properties, appsettings, spring, python
 // **Secret Managers:** doppler, heroku, vercel, railway
+// **Deployment platforms:** fly, netlify, netlify-toml, wrangler, wrangler-secrets,
+//   render, nomad, systemd, systemd-dropin, ecs
 #[allow(clippy::too_many_arguments)]
 pub fn run(
     env: String,
@@ -44,6 +60,13 @@ pub fn run(
     base64: bool,
     prefix: Option<String>,
     transform: Option<String>,
+    example: String,
+    sort: bool,
+    nest: Option<String>,
+    key_case: String,
+    coerce: bool,
+    profile: Option<String>,
+    k8s: KubernetesArgs,
     verbose: bool,
 ) -> Result<()> {
     if verbose {
@@ -60,6 +83,22 @@ pub fn run(
         println!("Loaded {} variables from {}", env_file.vars.len(), env);
     }
 
+    // Order and comments come from the file itself; descriptions it lacks are
+    // taken from the example file when there is one.
+    let source = fs::read_to_string(&env).with_context(|| format!("Failed to read {}", env))?;
+    let example_doc = if example != env && Path::new(&example).exists() {
+        let content =
+            fs::read_to_string(&example).with_context(|| format!("Failed to read {}", example))?;
+        Some(Document::parse(&content))
+    } else {
+        None
+    };
+    let vars = document_variables(
+        &env_file.vars,
+        &Document::parse(&source),
+        example_doc.as_ref(),
+    );
+
     // // Build conversion options
     // let mut options = ConvertOptions::default();
     // options.include_pattern = include;
@@ -78,13 +117,10 @@ pub fn run(
         exclude_pattern: exclude,
         base64,
         prefix,
-        transform: transform.as_ref().and_then(|t| match t.as_str() {
-            "uppercase" => Some(KeyTransform::Uppercase),
-            "lowercase" => Some(KeyTransform::Lowercase),
-            "camelCase" => Some(KeyTransform::CamelCase),
-            "snake_case" => Some(KeyTransform::SnakeCase),
-            _ => None,
-        }),
+        transform: transform.as_deref().and_then(KeyTransform::parse),
+        sort,
+        nest: key_path(nest, &key_case)?,
+        coerce,
     };
 
     // Determine format
@@ -110,6 +146,12 @@ pub fn run(
                 "json - Generic JSON key-value object",
                 "yaml - Generic YAML key-value format",
                 "shell - Shell export script (bash/zsh)",
+                "fish - fish script (set -gx)",
+                "powershell - PowerShell script ($env:)",
+                "cmd - Windows batch file (set)",
+                "nushell - Nushell script ($env.)",
+                "direnv - direnv .envrc with the values inlined",
+                "direnv-dotenv - direnv .envrc loading the .env file (dotenv_if_exists)",
                 // Cloud providers
                 "aws-secrets - AWS Secrets Manager (CLI commands)",
                 "gcp-secrets - GCP Secret Manager (gcloud commands)",
@@ -119,13 +161,35 @@ pub fn run(
                 // Container platforms
                 "docker-compose - Docker Compose YAML environment",
                 "kubernetes - Kubernetes Secret YAML (base64 encoded)",
+                "kubernetes-split - Kubernetes ConfigMap + Secret, split by secret detection",
+                "kubernetes-env - Kubernetes container env with secretKeyRef/configMapKeyRef",
+                "kubernetes-envfrom - Kubernetes container envFrom references",
+                "kustomize - kustomization.yaml with configMap/secret generators",
+                "helm - Helm values.yaml fragment (config + secrets)",
+                "sealed-secret - Bitnami SealedSecret, sealed offline with --cert",
                 // Infrastructure as Code
                 "terraform - Terraform .tfvars file",
+                // Application config files
+                "properties - Java .properties file",
+                "appsettings - .NET appsettings.json (__ hierarchy)",
+                "spring - Spring Boot application.yml (relaxed binding)",
+                "python - Python settings module",
                 // Secret management platforms
                 "doppler - Doppler secrets JSON format",
                 "heroku - Heroku config vars (CLI commands)",
                 "vercel - Vercel environment variables JSON",
                 "railway - Railway variables JSON format",
+                // Deployment platforms
+                "fly - Fly.io secrets (fly secrets import)",
+                "netlify - Netlify env:set commands",
+                "netlify-toml - netlify.toml [build.environment] (secrets left out)",
+                "wrangler - wrangler.toml [vars] (secrets left out)",
+                "wrangler-secrets - Cloudflare secrets JSON (wrangler secret bulk)",
+                "render - Render blueprint envVars",
+                "nomad - Nomad task env/template blocks",
+                "systemd - systemd EnvironmentFile",
+                "systemd-dropin - systemd drop-in unit (Environment=)",
+                "ecs - AWS ECS container environment/secrets",
             ];
 
             let selection = Select::new()
@@ -136,7 +200,7 @@ pub fn run(
 
             // Extract format name (everything before first dash and space)
             formats[selection]
-                .split('-')
+                .split(" - ")
                 .next()
                 .unwrap()
                 .trim()
@@ -144,6 +208,36 @@ pub fn run(
         }
     };
 
+    let labels = k8s
+        .labels
+        .iter()
+        .map(|label| match label.split_once('=') {
+            Some((k, v)) if !k.is_empty() => Ok((k.to_string(), v.to_string())),
+            _ => bail!("Invalid label '{}'; expected key=value", label),
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let kubernetes = |output| formats::KubernetesSecretConverter {
+        secret_name: k8s.name.clone(),
+        config_name: k8s.config_name.clone(),
+        namespace: k8s.namespace.clone(),
+        labels: labels.clone(),
+        output,
+    };
+
+    // `shell:auto` is the dialect of the user's shell; `shell:fish` is `fish`.
+    let format = match format.strip_prefix("shell:") {
+        Some("auto") => {
+            let shell = std::env::var("SHELL").ok();
+            let detected = formats::shell::detect(shell.as_deref());
+            if verbose {
+                println!("Detected {} from $SHELL", detected);
+            }
+            detected.to_string()
+        }
+        Some(dialect) => dialect.to_string(),
+        None => format,
+    };
+
     // Get converter
     // Note: Using qualified paths (formats::JsonConverter) instead of
     // full paths (formats::json::JsonConverter) to avoid import warnings
@@ -151,7 +245,15 @@ pub fn run(
         // Generic formats
         "json" => Box::new(formats::JsonConverter),
         "yaml" | "yml" => Box::new(formats::YamlConverter),
-        "shell" | "bash" | "export" => Box::new(formats::ShellExportConverter),
+        "shell" | "bash" | "zsh" | "sh" | "export" => Box::new(formats::ShellExportConverter),
+        "fish" => Box::new(formats::FishConverter),
+        "powershell" | "pwsh" | "ps1" => Box::new(formats::PowerShellConverter),
+        "cmd" | "bat" | "batch" => Box::new(formats::CmdConverter),
+        "nushell" | "nu" => Box::new(formats::NushellConverter),
+        "direnv" | "envrc" => Box::new(formats::DirenvConverter::default()),
+        "direnv-dotenv" => Box::new(formats::DirenvConverter {
+            dotenv: Some(env.clone()),
+        }),
 
         // Cloud providers
         "aws" | "aws-secrets" | "aws-secrets-manager" => Box::new(formats::AwsSecretsConverter),
@@ -169,17 +271,43 @@ pub fn run(
 
         // Container platforms
         "docker" | "docker-compose" | "compose" => Box::new(formats::DockerComposeConverter),
-        "kubernetes" | "k8s" | "kubectl" => Box::new(formats::KubernetesSecretConverter::default()),
+        "kubernetes" | "k8s" | "kubectl" => Box::new(kubernetes(KubernetesOutput::Secret)),
+        "kubernetes-split" | "k8s-split" => Box::new(kubernetes(KubernetesOutput::Split)),
+        "kubernetes-env" | "k8s-env" => Box::new(kubernetes(KubernetesOutput::Env)),
+        "kubernetes-envfrom" | "k8s-envfrom" => Box::new(kubernetes(KubernetesOutput::EnvFrom)),
+        "kustomize" | "kustomization" => Box::new(kubernetes(KubernetesOutput::Kustomize)),
+        "helm" | "helm-values" => Box::new(kubernetes(KubernetesOutput::Helm)),
+        "sealed-secret" | "sealedsecret" | "kubeseal" => sealed_secret(&k8s, labels.clone())?,
 
         // Infrastructure as Code
         "terraform" | "tfvars" | "tf" => Box::new(formats::TerraformConverter),
 
+        // Application config files
+        "properties" | "java-properties" => Box::new(formats::PropertiesConverter),
+        "appsettings" | "dotnet" | "dotnet-appsettings" => Box::new(formats::AppSettingsConverter),
+        "spring" | "spring-boot" | "application-yml" => Box::new(formats::SpringConverter),
+        "python" | "python-settings" | "django" => Box::new(formats::PythonSettingsConverter),
+
         // Secret management platforms
         "doppler" => Box::new(formats::DopplerConverter),
         "heroku" => Box::new(formats::HerokuConfigConverter::default()),
         "vercel" => Box::new(formats::VercelEnvConverter),
         "railway" => Box::new(formats::RailwayConverter),
 
+        // Deployment platforms
+        "fly" | "flyio" | "fly-secrets" => Box::new(formats::FlySecretsConverter),
+        "netlify" | "netlify-cli" => Box::new(formats::NetlifyCliConverter),
+        "netlify-toml" => Box::new(formats::NetlifyTomlConverter),
+        "wrangler" | "cloudflare" => Box::new(formats::WranglerVarsConverter),
+        "wrangler-secrets" | "cloudflare-secrets" => Box::new(formats::WranglerSecretsConverter),
+        "render" | "render-yaml" => Box::new(formats::RenderConverter),
+        "nomad" => Box::new(formats::NomadConverter),
+        "systemd" | "environment-file" => Box::new(formats::SystemdEnvFileConverter),
+        "systemd-dropin" | "systemd-unit" => Box::new(formats::SystemdDropInConverter),
+        "ecs" | "ecs-task" => Box::new(formats::EcsTaskConverter {
+            secret_name: k8s.name.clone(),
+        }),
+
         // Unknown format
         _ => {
             eprintln!("{} Unknown format: {}", "✗".red(), format);
@@ -187,7 +315,8 @@ pub fn run(
             eprintln!("{}", "Supported formats:".bold());
             eprintln!();
             eprintln!("  {}", "Generic:".yellow());
-            eprintln!("    json, yaml, shell");
+            eprintln!("    json, yaml, shell, fish, powershell, cmd, nushell, direnv,");
+            eprintln!("    direnv-dotenv (shell:auto picks the dialect of $SHELL)");
             eprintln!();
             eprintln!("  {}", "Cloud providers:".yellow());
             eprintln!("    aws-secrets, gcp-secrets, azure-keyvault");
@@ -196,14 +325,22 @@ pub fn run(
             eprintln!("    github-actions");
             eprintln!();
             eprintln!("  {}", "Containers:".yellow());
-            eprintln!("    docker-compose, kubernetes");
+            eprintln!("    docker-compose, kubernetes, kubernetes-split, kubernetes-env,");
+            eprintln!("    kubernetes-envfrom, kustomize, helm, sealed-secret");
             eprintln!();
             eprintln!("  {}", "Infrastructure:".yellow());
             eprintln!("    terraform");
             eprintln!();
+            eprintln!("  {}", "App config:".yellow());
+            eprintln!("    properties, appsettings, spring, python");
+            eprintln!();
             eprintln!("  {}", "Secret managers:".yellow());
             eprintln!("    doppler, heroku, vercel, railway");
             eprintln!();
+            eprintln!("  {}", "Deployment platforms:".yellow());
+            eprintln!("    fly, netlify, netlify-toml, wrangler, wrangler-secrets, render,");
+            eprintln!("    nomad, systemd, systemd-dropin, ecs");
+            eprintln!();
             eprintln!("  {}", "Aliases:".dimmed());
             eprintln!("    k8s → kubernetes, tf → terraform, yml → yaml");
             eprintln!("    gh-actions → github-actions, compose → docker-compose");
@@ -211,12 +348,31 @@ pub fn run(
         }
     };
 
+    if options.nest.is_some()
+        && !matches!(
+            converter.name(),
+            "json" | "yaml" | "properties" | "appsettings" | "spring"
+        )
+    {
+        bail!("--nest applies to json, yaml, properties, appsettings and spring output only");
+    }
+
+    let output = match (output, profile) {
+        (Some(path), _) => Some(path),
+        (None, Some(profile)) => match converter.name() {
+            "appsettings" => Some(format!("appsettings.{}.json", profile)),
+            "spring" => Some(format!("application-{}.yml", profile)),
+            _ => bail!("--profile applies to appsettings and spring output only"),
+        },
+        (None, None) => None,
+    };
+
     if verbose {
         println!("Converting to {} format...", converter.name());
     }
 
     // Convert
-    let result = converter.convert(&env_file.vars, &options)?;
+    let result = converter.convert(&vars, &options)?;
 
     // Output
     match output {
@@ -233,14 +389,74 @@ pub fn run(
     Ok(())
 }
 
+/// SealedSecret converter for `--cert` and `--scope`
+#[cfg(feature = "sealed-secrets")]
+fn sealed_secret(
+    k8s: &KubernetesArgs,
+    labels: Vec<(String, String)>,
+) -> Result<Box<dyn Converter>> {
+    use formats::sealed::{public_key, SealedSecretConverter, SealingScope};
+
+    let Some(cert) = &k8s.cert else {
+        bail!("sealed-secret needs --cert; fetch it with `kubeseal --fetch-cert > cert.pem`");
+    };
+    let scope = SealingScope::parse(&k8s.scope).with_context(|| {
+        format!(
+            "Unknown --scope '{}'; expected strict, namespace-wide or cluster-wide",
+            k8s.scope
+        )
+    })?;
+    if scope != SealingScope::ClusterWide && k8s.namespace.is_none() {
+        bail!("A {} SealedSecret needs --namespace", k8s.scope);
+    }
+    let pem = fs::read_to_string(cert).with_context(|| format!("Failed to read {}", cert))?;
+    let key = public_key(&pem).with_context(|| format!("Failed to load {}", cert))?;
+
+    Ok(Box::new(SealedSecretConverter {
+        key,
+        name: k8s.name.clone(),
+        namespace: k8s.namespace.clone(),
+        labels,
+        scope,
+    }))
+}
+
+#[cfg(not(feature = "sealed-secrets"))]
+fn sealed_secret(
+    _k8s: &KubernetesArgs,
+    _labels: Vec<(String, String)>,
+) -> Result<Box<dyn Converter>> {
+    bail!("sealed-secret output requires evnx built with --features sealed-secrets");
+}
+
+/// Key-path mapping of `--nest` and `--key-case`
+pub(crate) fn key_path(nest: Option<String>, key_case: &str) -> Result<Option<KeyPath>> {
+    let Some(separator) = nest else {
+        return Ok(None);
+    };
+    if separator.is_empty() {
+        bail!("--nest needs a separator, such as __ or _");
+    }
+    let case = SegmentCase::parse(key_case).with_context(|| {
+        format!(
+            "Unknown key case '{}'; expected preserve, lower, camel or kebab",
+            key_case
+        )
+    })?;
+    Ok(Some(KeyPath::new(separator, case)))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_key_transform_uppercase() {
-        let mut options = ConvertOptions::default();
-        options.transform = Some(KeyTransform::Uppercase);
+        let options = ConvertOptions {
+            transform: Some(KeyTransform::Uppercase),
+            ..Default::default()
+        };
+        assert_eq!(options.transform_key("database_url"), "DATABASE_URL");
         // ConvertOptions tests are in core/converter.rs
     }
 }
@@ -6,7 +6,12 @@ use colored::*;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 
-use crate::core::Parser;
+use std::fs;
+use std::path::Path;
+
+use crate::core::fingerprint::{self, Fingerprint, HashedDiff};
+use crate::core::{hygiene, Parser, ParserConfig};
+use crate::utils::git;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DiffResult {
@@ -22,61 +27,194 @@ pub struct DiffItem {
     pub env_value: String,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn run(
     env: String,
     example: String,
     show_values: bool,
     format: String,
     reverse: bool,
+    from: Option<String>,
+    to: Option<String>,
+    hashed: bool,
+    fingerprint: bool,
+    shapes: bool,
+    key: Option<String>,
     verbose: bool,
 ) -> Result<()> {
     if verbose {
         println!("{}", "Running diff in verbose mode".dimmed());
     }
 
-    println!(
-        "\n{}",
-        "┌─ Comparing .env ↔ .env.example ─────────────────────┐".cyan()
-    );
-    println!(
-        "{}\n",
-        "└──────────────────────────────────────────────────────┘".cyan()
-    );
-
-    let parser = Parser::default();
+    if fingerprint {
+        let key = key.unwrap_or_else(|| {
+            let key = fingerprint::random_key();
+            eprintln!("Fingerprint key: {}", key.bold());
+            eprintln!("Share it separately; it is not written to the fingerprint.");
+            key
+        });
+        let print = match load(&env, true)? {
+            Side::Vars(vars) => Fingerprint::new(&vars, &key, shapes),
+            Side::Fingerprint(_) => anyhow::bail!("{} is already a fingerprint", env),
+        };
+        println!("{}", serde_json::to_string_pretty(&print)?);
+        return Ok(());
+    }
 
-    let env_file = parser
-        .parse_file(&env)
-        .with_context(|| format!("Failed to parse {}", env))?;
+    // --from/--to compare one file between revisions; the working tree
+    // stands in for a missing --to.
+    let (left_name, right_name) = match &from {
+        Some(from) => (
+            revision_spec(from, &example),
+            to.as_deref()
+                .map(|to| revision_spec(to, &example))
+                .unwrap_or_else(|| example.clone()),
+        ),
+        None if reverse => (example.clone(), env.clone()),
+        None => (env.clone(), example.clone()),
+    };
+    let revisions = from.is_some();
 
-    let example_file = parser
-        .parse_file(&example)
-        .with_context(|| format!("Failed to parse {}", example))?;
+    // Machine-readable formats must be the only thing on stdout.
+    if format == "pretty" {
+        let title = format!("┌─ Comparing {} ↔ {} ", left_name, right_name);
+        let fill = "─".repeat(54usize.saturating_sub(title.chars().count()));
+        println!("\n{}", format!("{}{}┐", title, fill).cyan());
+        println!(
+            "{}\n",
+            "└──────────────────────────────────────────────────────┘".cyan()
+        );
+    }
 
-    let (left, right, left_name, right_name) = if reverse {
-        (&example_file.vars, &env_file.vars, &example, &env)
-    } else {
-        (&env_file.vars, &example_file.vars, &env, &example)
-    };
+    // Example files hold `${VAR}` placeholders, which are kept as written.
+    let left = load(&left_name, !reverse)?;
+    let right = load(&right_name, reverse && !revisions)?;
+
+    // Compare digests when asked to, or when one side is an exported
+    // fingerprint (whose key the other side must then use).
+    if hashed || left.is_fingerprint() || right.is_fingerprint() {
+        let key = match key {
+            Some(key) => key,
+            None if left.is_fingerprint() || right.is_fingerprint() => anyhow::bail!(
+                "Comparing with a fingerprint needs the key it was made with (--key or EVNX_FINGERPRINT_KEY)"
+            ),
+            None => fingerprint::random_key(),
+        };
+        let diff =
+            fingerprint::compare(&left.into_fingerprint(&key), &right.into_fingerprint(&key))?;
+
+        match format.as_str() {
+            "json" => println!("{}", serde_json::to_string_pretty(&diff)?),
+            "pretty" => output_hashed(&diff, &left_name, &right_name),
+            other => anyhow::bail!(
+                "--format {} needs values; hashed comparisons support pretty and json",
+                other
+            ),
+        }
+        return Ok(());
+    }
 
-    let diff = compute_diff(left, right);
+    let (left, right) = (left.into_vars(&left_name)?, right.into_vars(&right_name)?);
+    let diff = compute_diff(&left, &right);
 
     match format.as_str() {
         "json" => output_json(&diff)?,
-        "patch" => output_patch(&diff, left, right)?,
-        _ => output_pretty(&diff, left, right, left_name, right_name, show_values)?,
+        "patch" => output_patch(&diff, &left, &right)?,
+        "markdown" => {
+            let values = show_values || only_examples(&env, &example, revisions);
+            print!(
+                "{}",
+                changelog(&diff, &right, &left_name, &right_name, values)
+            );
+        }
+        _ if revisions => output_revisions(&diff, &right_name, show_values)?,
+        _ => output_pretty(&diff, &left, &right, &left_name, &right_name, show_values)?,
     }
 
     Ok(())
 }
 
+/// Whether both sides are example files, whose values are defaults rather
+/// than secrets: revisions of the example file, or two example files.
+fn only_examples(env: &str, example: &str, revisions: bool) -> bool {
+    let is_example = |path: &str| hygiene::is_example_file(Path::new(path));
+    is_example(example) && (revisions || is_example(env))
+}
+
+/// `REV:./path`, relative to the current directory like a path on disk.
+fn revision_spec(rev: &str, path: &str) -> String {
+    if path.starts_with("./") || path.starts_with('/') {
+        format!("{}:{}", rev, path)
+    } else {
+        format!("{}:./{}", rev, path)
+    }
+}
+
+/// One side of a comparison: parsed variables or an exported fingerprint.
+enum Side {
+    Vars(HashMap<String, String>),
+    Fingerprint(Fingerprint),
+}
+
+impl Side {
+    fn is_fingerprint(&self) -> bool {
+        matches!(self, Side::Fingerprint(_))
+    }
+
+    /// Local values are fingerprinted with their shapes, which stay on this
+    /// machine.
+    fn into_fingerprint(self, key: &str) -> Fingerprint {
+        match self {
+            Side::Vars(vars) => Fingerprint::new(&vars, key, true),
+            Side::Fingerprint(print) => print,
+        }
+    }
+
+    fn into_vars(self, name: &str) -> Result<HashMap<String, String>> {
+        match self {
+            Side::Vars(vars) => Ok(vars),
+            Side::Fingerprint(_) => {
+                anyhow::bail!("{} is a fingerprint; values are unavailable", name)
+            }
+        }
+    }
+}
+
+/// Load a file on disk or a `REV:PATH` spec read from git; either may be an
+/// exported fingerprint. References are expanded only when `expand` is set
+/// and the file is on disk: a revision is compared as committed.
+fn load(spec: &str, expand: bool) -> Result<Side> {
+    let (content, expand) = match spec.split_once(':') {
+        Some((rev, path)) if !rev.is_empty() && !Path::new(spec).exists() => {
+            (git::show_file(rev, path)?, false)
+        }
+        _ => (
+            fs::read_to_string(spec).with_context(|| format!("Failed to read {}", spec))?,
+            expand,
+        ),
+    };
+
+    if let Some(print) = Fingerprint::parse(&content) {
+        return Ok(Side::Fingerprint(print));
+    }
+    Parser::new(ParserConfig {
+        allow_expansion: expand,
+        ..ParserConfig::default()
+    })
+    .parse_content(&content)
+    .map(Side::Vars)
+    .with_context(|| format!("Failed to parse {}", spec))
+}
+
 fn compute_diff(left: &HashMap<String, String>, right: &HashMap<String, String>) -> DiffResult {
     let left_keys: HashSet<_> = left.keys().cloned().collect();
     let right_keys: HashSet<_> = right.keys().cloned().collect();
 
-    let missing: Vec<String> = right_keys.difference(&left_keys).cloned().collect();
+    let mut missing: Vec<String> = right_keys.difference(&left_keys).cloned().collect();
+    missing.sort();
 
-    let extra: Vec<String> = left_keys.difference(&right_keys).cloned().collect();
+    let mut extra: Vec<String> = left_keys.difference(&right_keys).cloned().collect();
+    extra.sort();
 
     let mut different = Vec::new();
     for key in left_keys.intersection(&right_keys) {
@@ -92,6 +230,8 @@ fn compute_diff(left: &HashMap<String, String>, right: &HashMap<String, String>)
         }
     }
 
+    different.sort_by(|a, b| a.key.cmp(&b.key));
+
     DiffResult {
         missing,
         extra,
@@ -173,6 +313,209 @@ fn output_pretty(
     Ok(())
 }
 
+/// Pretty output for `--from/--to`: what changed going to `right_name`.
+fn output_revisions(diff: &DiffResult, right_name: &str, show_values: bool) -> Result<()> {
+    if diff.missing.is_empty() && diff.extra.is_empty() && diff.different.is_empty() {
+        println!("{} No variable changes", "✓".green());
+        return Ok(());
+    }
+
+    if !diff.missing.is_empty() {
+        println!("{}", "Added:".bold());
+        for key in &diff.missing {
+            println!("  {} {}", "+".green(), key.bold());
+        }
+        println!();
+    }
+
+    if !diff.extra.is_empty() {
+        println!("{}", "Removed:".bold());
+        for key in &diff.extra {
+            println!("  {} {}", "-".red(), key.bold());
+        }
+        println!();
+    }
+
+    if !diff.different.is_empty() {
+        println!("{}", "Default changed:".bold());
+        for item in &diff.different {
+            if show_values {
+                println!(
+                    "  {} {}: {} → {}",
+                    "~".yellow(),
+                    item.key.bold(),
+                    item.env_value.dimmed(),
+                    item.example_value
+                );
+            } else {
+                println!("  {} {}", "~".yellow(), item.key.bold());
+            }
+        }
+        println!();
+    }
+
+    println!("{}", "Summary:".bold());
+    println!(
+        "  {} added, {} removed, {} changed in {}",
+        diff.missing.len(),
+        diff.extra.len(),
+        diff.different.len(),
+        right_name
+    );
+
+    Ok(())
+}
+
+/// Markdown "Environment changes" section for release notes, going from
+/// `left_name` to `right_name`.
+fn changelog(
+    diff: &DiffResult,
+    right: &HashMap<String, String>,
+    left_name: &str,
+    right_name: &str,
+    show_values: bool,
+) -> String {
+    let mut out = format!(
+        "## Environment changes\n\n_{} → {}_\n\n",
+        md_code(left_name),
+        md_code(right_name)
+    );
+
+    if diff.missing.is_empty() && diff.extra.is_empty() && diff.different.is_empty() {
+        out.push_str("No environment variable changes.\n");
+        return out;
+    }
+
+    if !diff.missing.is_empty() {
+        out.push_str("### New variables\n\nSet these before deploying.\n\n");
+        if show_values {
+            out.push_str("| Variable | Default |\n|---|---|\n");
+            for key in &diff.missing {
+                let value = right.get(key).map(String::as_str).unwrap_or("");
+                out.push_str(&format!("| {} | {} |\n", md_code(key), md_value(value)));
+            }
+        } else {
+            for key in &diff.missing {
+                out.push_str(&format!("- {}\n", md_code(key)));
+            }
+        }
+        out.push('\n');
+    }
+
+    if !diff.extra.is_empty() {
+        out.push_str("### Removed variables\n\nNo longer read; safe to delete.\n\n");
+        for key in &diff.extra {
+            out.push_str(&format!("- {}\n", md_code(key)));
+        }
+        out.push('\n');
+    }
+
+    if !diff.different.is_empty() {
+        out.push_str("### Changed defaults\n\n");
+        if show_values {
+            out.push_str("| Variable | Before | After |\n|---|---|---|\n");
+            for item in &diff.different {
+                out.push_str(&format!(
+                    "| {} | {} | {} |\n",
+                    md_code(&item.key),
+                    md_value(&item.env_value),
+                    md_value(&item.example_value)
+                ));
+            }
+        } else {
+            for item in &diff.different {
+                out.push_str(&format!("- {}\n", md_code(&item.key)));
+            }
+        }
+        out.push('\n');
+    }
+
+    out
+}
+
+fn md_code(text: &str) -> String {
+    format!("`{}`", text.replace('`', "'"))
+}
+
+fn md_value(value: &str) -> String {
+    if value.is_empty() {
+        "_(empty)_".to_string()
+    } else {
+        md_code(&value.replace('|', "\\|").replace('\n', " "))
+    }
+}
+
+/// Pretty output of a hashed comparison: equality and shape, never values.
+fn output_hashed(diff: &HashedDiff, left_name: &str, right_name: &str) {
+    if !diff.same.is_empty() {
+        println!("{}", "Same value:".bold());
+        for key in &diff.same {
+            println!("  {} {}", "=".green(), key.bold());
+        }
+        println!();
+    }
+
+    if !diff.different.is_empty() {
+        println!("{}", "Different value:".bold());
+        for item in &diff.different {
+            let (Some(left), Some(right)) = (&item.left, &item.right) else {
+                println!("  {} {}", "≠".yellow(), item.key.bold());
+                continue;
+            };
+            let mut shape = Vec::new();
+            if left.length != right.length {
+                shape.push(format!("length {} → {}", left.length, right.length));
+            }
+            if left.charset != right.charset {
+                shape.push(format!("{} → {}", left.charset, right.charset));
+            }
+            let shape = if shape.is_empty() {
+                "same shape".to_string()
+            } else {
+                shape.join(", ")
+            };
+            println!(
+                "  {} {} ({})",
+                "≠".yellow(),
+                item.key.bold(),
+                shape.dimmed()
+            );
+        }
+        println!();
+    }
+
+    if !diff.missing.is_empty() {
+        println!(
+            "{}",
+            format!("Missing from {} (present in {}):", left_name, right_name).bold()
+        );
+        for key in &diff.missing {
+            println!("  {} {}", "+".green(), key.bold());
+        }
+        println!();
+    }
+
+    if !diff.extra.is_empty() {
+        println!(
+            "{}",
+            format!("Extra in {} (not in {}):", left_name, right_name).bold()
+        );
+        for key in &diff.extra {
+            println!("  {} {}", "-".red(), key.bold());
+        }
+        println!();
+    }
+
+    println!("{}", "Summary:".bold());
+    println!(
+        "  {} same, {} different, {} missing, {} extra",
+        diff.same.len(),
+        diff.different.len(),
+        diff.missing.len(),
+        diff.extra.len()
+    );
+}
+
 fn output_json(diff: &DiffResult) -> Result<()> {
     let json = serde_json::to_string_pretty(diff)?;
     println!("{}", json);
@@ -230,4 +573,48 @@ mod tests {
         assert_eq!(diff.different.len(), 1);
         assert_eq!(diff.different[0].key, "KEY2");
     }
+
+    #[test]
+    fn test_changelog() {
+        let from = HashMap::from([
+            ("PORT".to_string(), "3000".to_string()),
+            ("OLD_FLAG".to_string(), "1".to_string()),
+        ]);
+        let to = HashMap::from([
+            ("PORT".to_string(), "8080".to_string()),
+            ("STRIPE_KEY".to_string(), String::new()),
+            ("REGION".to_string(), "eu|us".to_string()),
+        ]);
+        let diff = compute_diff(&from, &to);
+
+        let markdown = changelog(
+            &diff,
+            &to,
+            "v1.2.0:./.env.example",
+            "v1.3.0:./.env.example",
+            true,
+        );
+        assert_eq!(
+            markdown,
+            "## Environment changes\n\n_`v1.2.0:./.env.example` → `v1.3.0:./.env.example`_\n\n\
+             ### New variables\n\nSet these before deploying.\n\n\
+             | Variable | Default |\n|---|---|\n\
+             | `REGION` | `eu\\|us` |\n| `STRIPE_KEY` | _(empty)_ |\n\n\
+             ### Removed variables\n\nNo longer read; safe to delete.\n\n- `OLD_FLAG`\n\n\
+             ### Changed defaults\n\n| Variable | Before | After |\n|---|---|---|\n\
+             | `PORT` | `3000` | `8080` |\n\n"
+        );
+
+        let markdown = changelog(&diff, &to, "a", "b", false);
+        assert!(markdown.contains("- `STRIPE_KEY`\n"));
+        assert!(!markdown.contains("8080"));
+    }
+
+    #[test]
+    fn test_only_examples() {
+        assert!(only_examples(".env", ".env.example", true));
+        assert!(only_examples(".env.template", ".env.example", false));
+        assert!(!only_examples(".env", ".env.example", false));
+        assert!(!only_examples(".env.production", ".env", true));
+    }
 }
@@ -0,0 +1,57 @@
+/// Hooks command - integrate evnx with git
+///
+/// `install --merge-driver` registers `evnx merge` as the merge driver for
+/// `.env*` files: a `merge.evnx` entry in the repository's git config and a
+/// `.env* merge=evnx` line in `.gitattributes`.
+use anyhow::{bail, Result};
+use colored::*;
+use std::fs;
+
+use crate::utils::git;
+
+/// Line added to `.gitattributes`.
+const ATTRIBUTES_LINE: &str = ".env* merge=evnx";
+
+pub fn run_install(merge_driver: bool, verbose: bool) -> Result<()> {
+    if !merge_driver {
+        bail!("Nothing to install: pass --merge-driver");
+    }
+
+    git::set_config("merge.evnx.name", "evnx key-based env file merge")?;
+    git::set_config("merge.evnx.driver", "evnx merge %O %A %B --output %A")?;
+    if verbose {
+        println!("Registered merge.evnx in .git/config");
+    }
+
+    let attributes = fs::read_to_string(".gitattributes").unwrap_or_default();
+    if attributes
+        .lines()
+        .any(|line| line.trim() == ATTRIBUTES_LINE)
+    {
+        println!(
+            "{} .gitattributes already routes .env* to evnx",
+            "✓".green()
+        );
+    } else {
+        let mut content = attributes;
+        if !content.is_empty() && !content.ends_with('\n') {
+            content.push('\n');
+        }
+        content.push_str(ATTRIBUTES_LINE);
+        content.push('\n');
+        fs::write(".gitattributes", content)?;
+        println!(
+            "{} Added '{}' to .gitattributes",
+            "✓".green(),
+            ATTRIBUTES_LINE
+        );
+    }
+
+    println!("{} Installed the evnx merge driver", "✓".green());
+    println!(
+        "  {}",
+        "Commit .gitattributes; each clone needs `evnx hooks install --merge-driver` once."
+            .dimmed()
+    );
+    Ok(())
+}
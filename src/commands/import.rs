@@ -0,0 +1,237 @@
+//! Import command - convert other formats back into .env
+//!
+//! Reads JSON, YAML (Kubernetes, Compose), tfvars, shell scripts or dotenv
+//! files and writes the variables into a formatted `.env`. Keys the output
+//! already has are compared by value; a different value is resolved with
+//! `--on-conflict`. New keys are appended with their comments, and the rest
+//! of the existing file is left as it was.
+use anyhow::{bail, Context, Result};
+use colored::*;
+use dialoguer::Confirm;
+use std::fs;
+use std::io::{self, IsTerminal, Read};
+use std::path::Path;
+
+use crate::commands::convert::key_path;
+use crate::core::{
+    converter::{ConvertOptions, KeyTransform, Variable},
+    Document, Entry, Parser, ParserConfig,
+};
+use crate::formats::escape::{dotenv_value, hash_comments};
+use crate::formats::import::{import, is_identifier, ImportFormat, ImportOptions};
+use crate::utils::string::redact;
+
+/// What to do when the output already holds a key with another value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OnConflict {
+    Ask,
+    Keep,
+    Overwrite,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn run(
+    file: String,
+    from: Option<String>,
+    output: String,
+    on_conflict: String,
+    service: Option<String>,
+    transform: Option<String>,
+    nest: Option<String>,
+    key_case: String,
+    verbose: bool,
+) -> Result<()> {
+    let on_conflict = match on_conflict.as_str() {
+        "ask" => OnConflict::Ask,
+        "keep" => OnConflict::Keep,
+        "overwrite" => OnConflict::Overwrite,
+        other => bail!(
+            "Unknown --on-conflict '{}'; expected ask, keep or overwrite",
+            other
+        ),
+    };
+    let transform = match transform.as_deref() {
+        Some(name) => Some(KeyTransform::parse(name).with_context(|| {
+            format!(
+                "Unknown transform '{}'; expected uppercase, lowercase, camelCase or snake_case",
+                name
+            )
+        })?),
+        None => None,
+    };
+    let nest = key_path(nest, &key_case)?;
+    let to_stdout = output == "-";
+
+    // With `--output -` stdout is the file, so messages go to stderr.
+    let say = |message: String| {
+        if to_stdout {
+            eprintln!("{}", message);
+        } else {
+            println!("{}", message);
+        }
+    };
+
+    let content = if file == "-" {
+        let mut content = String::new();
+        io::stdin()
+            .read_to_string(&mut content)
+            .context("Failed to read stdin")?;
+        content
+    } else {
+        fs::read_to_string(&file).with_context(|| format!("Failed to read {}", file))?
+    };
+
+    let format = match from.as_deref() {
+        Some(name) => ImportFormat::from_name(name).with_context(|| {
+            format!(
+                "Unknown format '{}'; expected dotenv, json, yaml, kubernetes, docker-compose, \
+                 terraform, shell, heroku, vercel, doppler or aws-secrets",
+                name
+            )
+        })?,
+        None => ImportFormat::detect(&file, &content),
+    };
+    if verbose {
+        say(format!("Reading {} as {:?}", file, format)
+            .dimmed()
+            .to_string());
+    }
+
+    let base_dir = Path::new(&file)
+        .parent()
+        .filter(|_| file != "-")
+        .unwrap_or(Path::new("."));
+    let options = ConvertOptions {
+        transform,
+        ..Default::default()
+    };
+
+    let mut vars: Vec<Variable> = Vec::new();
+    let import_options = ImportOptions {
+        base_dir,
+        service: service.as_deref(),
+        nest: nest.as_ref(),
+    };
+    for var in import(format, &content, &import_options)
+        .with_context(|| format!("Failed to import {}", file))?
+    {
+        let key = options.transform_key(&var.key);
+        if !is_identifier(&key) {
+            say(format!(
+                "{} Skipping {}: not a valid variable name",
+                "⚠️".yellow(),
+                key
+            ));
+            continue;
+        }
+        vars.push(Variable { key, ..var });
+    }
+
+    // The existing file, compared by literal value.
+    let existing = if !to_stdout && Path::new(&output).exists() {
+        fs::read_to_string(&output).with_context(|| format!("Failed to read {}", output))?
+    } else {
+        String::new()
+    };
+    let literal = Parser::new(ParserConfig {
+        allow_expansion: false,
+        ..Default::default()
+    });
+    let current = literal
+        .parse_content(&existing)
+        .with_context(|| format!("Failed to parse {}", output))?;
+    let mut doc = Document::parse(&existing);
+
+    if on_conflict == OnConflict::Ask && !io::stdin().is_terminal() {
+        let conflicting = vars
+            .iter()
+            .any(|v| current.get(&v.key).is_some_and(|c| *c != v.value));
+        if conflicting {
+            bail!(
+                "{} has different values for some keys; choose --on-conflict keep or overwrite",
+                output
+            );
+        }
+    }
+
+    let (mut unchanged, mut updated, mut kept) = (0, 0, 0);
+    let mut added = Vec::new();
+    for var in vars {
+        let Some(old) = current.get(&var.key) else {
+            added.push(var);
+            continue;
+        };
+        if *old == var.value {
+            unchanged += 1;
+            continue;
+        }
+
+        let replace = match on_conflict {
+            OnConflict::Keep => false,
+            OnConflict::Overwrite => true,
+            OnConflict::Ask => Confirm::new()
+                .with_prompt(format!(
+                    "{} is '{}' in {}; replace with '{}'?",
+                    var.key,
+                    redact(old),
+                    output,
+                    redact(&var.value)
+                ))
+                .default(false)
+                .interact()?,
+        };
+        if !replace {
+            kept += 1;
+            continue;
+        }
+
+        // The parser takes the last assignment, so that is the one to rewrite.
+        if let Some(Entry::Var { value, text, .. }) = doc
+            .entries
+            .iter_mut()
+            .rev()
+            .find(|e| e.key() == Some(var.key.as_str()))
+        {
+            let export = if text.trim_start().starts_with("export ") {
+                "export "
+            } else {
+                ""
+            };
+            *value = dotenv_value(&var.value);
+            *text = format!("{}{}={}", export, var.key, value);
+        }
+        updated += 1;
+    }
+
+    let mut result = doc.to_string();
+    if !added.is_empty() {
+        if !result.is_empty() && !result.ends_with('\n') {
+            result.push('\n');
+        }
+        if !result.is_empty() && !result.ends_with("\n\n") {
+            result.push('\n');
+        }
+        for (i, var) in added.iter().enumerate() {
+            result.push_str(&hash_comments(&added, i, ""));
+            result.push_str(&format!("{}={}\n", var.key, dotenv_value(&var.value)));
+        }
+    }
+
+    if to_stdout {
+        print!("{}", result);
+    } else if result != existing {
+        fs::write(&output, &result).with_context(|| format!("Failed to write {}", output))?;
+    }
+
+    say(format!(
+        "{} Imported into {}: {} added, {} updated, {} unchanged, {} kept",
+        "✓".green(),
+        if to_stdout { "stdout" } else { output.as_str() },
+        added.len(),
+        updated,
+        unchanged,
+        kept
+    ));
+
+    Ok(())
+}
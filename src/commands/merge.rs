@@ -0,0 +1,63 @@
+/// Merge command - three-way merge of env files by key
+///
+/// Used directly or as a git merge driver (`evnx hooks install --merge-driver`),
+/// where git calls `evnx merge %O %A %B --output %A`.
+use anyhow::{Context, Result};
+use colored::*;
+use std::fs;
+
+use crate::core::merge::merge;
+use crate::core::Document;
+
+pub fn run(
+    base: String,
+    ours: String,
+    theirs: String,
+    output: Option<String>,
+    verbose: bool,
+) -> Result<()> {
+    let read = |path: &str| -> Result<Document> {
+        let content =
+            fs::read_to_string(path).with_context(|| format!("Failed to read {}", path))?;
+        Ok(Document::parse(&content))
+    };
+
+    let base_doc = read(&base)?;
+    let ours_doc = read(&ours)?;
+    let theirs_doc = read(&theirs)?;
+
+    let merged = merge(&base_doc, &ours_doc, &theirs_doc);
+    let content = merged.document.to_string();
+
+    // Messages go to stderr: without --output, stdout is the merged file.
+    match &output {
+        Some(path) => {
+            fs::write(path, &content).with_context(|| format!("Failed to write {}", path))?;
+            if verbose {
+                eprintln!(
+                    "{} Merged {} variables into {}",
+                    "✓".green(),
+                    merged.document.keys().len(),
+                    path
+                );
+            }
+        }
+        None => print!("{}", content),
+    }
+
+    if merged.conflicts.is_empty() {
+        return Ok(());
+    }
+
+    eprintln!(
+        "{} {} conflicting variable(s) in {}:",
+        "✗".red(),
+        merged.conflicts.len(),
+        output.as_deref().unwrap_or(&ours)
+    );
+    for conflict in &merged.conflicts {
+        eprintln!("  • {} ({})", conflict.key.bold(), conflict.kind);
+    }
+    eprintln!("  Resolve the <<<<<<< markers, then stage the file.");
+    std::process::exit(1);
+}
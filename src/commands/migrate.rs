@@ -51,11 +51,13 @@
 
 use anyhow::{anyhow, Context, Result};
 use colored::*;
-use dialoguer::{Confirm, Input, Password, Select};
+use dialoguer::{Input, Select};
 use std::collections::HashMap;
 
 // Feature-gated imports (only when migrate feature is enabled)
 #[cfg(feature = "migrate")]
+use dialoguer::{Confirm, Password};
+#[cfg(feature = "migrate")]
 use indicatif::{ProgressBar, ProgressStyle};
 #[cfg(feature = "migrate")]
 use reqwest::blocking::Client;
@@ -66,6 +68,7 @@ use std::time::Duration;
 
 use crate::core::Parser;
 
+#[cfg(feature = "migrate")]
 type MigrationDiff = (Vec<(String, String)>, Vec<String>, Vec<String>);
 
 // Main entry point for migrate command
@@ -141,6 +144,7 @@ pub fn run(
 
             #[cfg(not(feature = "migrate"))]
             {
+                let _ = (&repo, &github_token, skip_existing, overwrite);
                 println!(
                     "{} GitHub Actions migration requires the migrate feature.",
                     "✗".red()
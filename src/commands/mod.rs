@@ -1,10 +1,15 @@
 pub mod add;
 pub mod backup;
+pub mod codegen;
 pub mod convert;
 pub mod diff;
 pub mod doctor;
+pub mod hooks;
+pub mod import;
 pub mod init;
+pub mod merge;
 pub mod migrate;
+pub mod redact;
 pub mod restore;
 pub mod scan;
 pub mod sync;
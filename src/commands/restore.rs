@@ -37,7 +37,9 @@
 //! - Support for asymmetric (public-key) backups once `--recipient` is added
 //!   to the `backup` command.
 
-use anyhow::{anyhow, Context, Result};
+use anyhow::Result;
+#[cfg(feature = "backup")]
+use anyhow::{anyhow, Context};
 use colored::*;
 
 /// Entry point for the `restore` subcommand.
@@ -56,6 +58,7 @@ use colored::*;
 pub fn run(backup: String, output: String, verbose: bool) -> Result<()> {
     // ── Feature-disabled stub ────────────────────────────────────────────────
     #[cfg(not(feature = "backup"))]
+    #[allow(clippy::needless_return)]
     {
         // Reference all parameters to silence unused-variable warnings in
         // the non-feature build without renaming them.
@@ -207,6 +210,7 @@ pub fn run(backup: String, output: String, verbose: bool) -> Result<()> {
 /// Count the number of `KEY=VALUE` variable assignments in a `.env` string.
 ///
 /// Comments (`#`) and blank lines are excluded from the count.
+#[cfg_attr(not(feature = "backup"), allow(dead_code))]
 fn count_vars(content: &str) -> usize {
     content
         .lines()
@@ -230,6 +234,7 @@ fn count_vars(content: &str) -> usize {
 ///
 /// > **Note:** This function is duplicated from `backup.rs` to keep `restore.rs`
 /// > self-contained. If the heuristic needs updating, change both copies.
+#[cfg_attr(not(feature = "backup"), allow(dead_code))]
 fn looks_like_dotenv(content: &str) -> bool {
     if content.trim().is_empty() {
         return true;
@@ -0,0 +1,825 @@
+//! Docker support for `scan`: Dockerfile analysis and `docker save` archives.
+//!
+//! Dockerfiles are parsed into instructions so that `ENV`/`ARG` values and
+//! `COPY`/`ADD` of `.env` files are reported with their line. Image archives
+//! (`docker save -o image.tar`, legacy or OCI layout) are read offline: the
+//! image config (`Env`, build history) is checked, and every layer tarball is
+//! walked like a source tree. Findings inside a layer are located as
+//! `image.tar!/<layer>!/<path>`, so the layer that introduced them is explicit.
+
+use anyhow::{Context, Result};
+use colored::*;
+use flate2::read::GzDecoder;
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+
+use super::{
+    detect_assignment, is_scannable_file, record_finding, scan_content, scan_text_file,
+    truncate_value, Finding, ScanResults,
+};
+use crate::utils::patterns::Confidence;
+
+/// Layer members larger than this are skipped.
+const MAX_MEMBER_SIZE: u64 = 4 * 1024 * 1024;
+
+/// Layer paths never scanned: VCS metadata, vendored packages and example
+/// files. Unlike in a source tree, `dist/` and `build/` in an image are the
+/// shipped application, so they are scanned.
+const LAYER_EXCLUDES: &[&str] = &[
+    ".git/",
+    "node_modules/",
+    ".env.example",
+    ".env.sample",
+    ".env.template",
+];
+
+/// Image paths holding OS packages and libraries rather than application
+/// files; scanning them is slow and reports vendored test keys. `usr/src/`
+/// is not among them: the official node and python images build the app
+/// there. Env files are scanned wherever they are.
+const SYSTEM_PREFIXES: &[&str] = &[
+    "usr/bin/",
+    "usr/sbin/",
+    "usr/lib/",
+    "usr/lib64/",
+    "usr/libexec/",
+    "usr/include/",
+    "usr/share/",
+    "usr/local/bin/",
+    "usr/local/include/",
+    "usr/local/lib/",
+    "usr/local/share/",
+    "lib/",
+    "lib64/",
+    "bin/",
+    "sbin/",
+    "proc/",
+    "sys/",
+    "var/lib/",
+    "var/cache/",
+];
+
+/// Check whether `path` is a Dockerfile (`Dockerfile`, `Dockerfile.prod`,
+/// `api.dockerfile`, `Containerfile`).
+pub fn is_dockerfile(path: &Path) -> bool {
+    let name = match path.file_name() {
+        Some(name) => name.to_string_lossy(),
+        None => return false,
+    };
+
+    name == "Dockerfile"
+        || name == "Containerfile"
+        || name.starts_with("Dockerfile.")
+        || name.to_lowercase().ends_with(".dockerfile")
+}
+
+/// A Dockerfile instruction with continuation lines joined.
+#[derive(Debug, Clone, PartialEq)]
+struct Instruction {
+    /// 1-based line of the instruction keyword.
+    line: usize,
+    /// 1-based lines the instruction spans.
+    lines: std::ops::RangeInclusive<usize>,
+    keyword: String,
+    args: String,
+}
+
+fn instructions(content: &str) -> Vec<Instruction> {
+    let mut out = Vec::new();
+    let mut lines = content.lines().enumerate();
+
+    while let Some((idx, line)) = lines.next() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        let mut text = trimmed.to_string();
+        let mut last = idx;
+        while text.ends_with('\\') {
+            text.pop();
+            match lines.next() {
+                Some((next_idx, next)) => {
+                    last = next_idx;
+                    let next = next.trim();
+                    if !next.starts_with('#') {
+                        text.push(' ');
+                        text.push_str(next);
+                    }
+                }
+                None => break,
+            }
+        }
+
+        let (keyword, args) = text.split_once(char::is_whitespace).unwrap_or((&text, ""));
+        out.push(Instruction {
+            line: idx + 1,
+            lines: idx + 1..=last + 1,
+            keyword: keyword.to_uppercase(),
+            args: args.trim().to_string(),
+        });
+    }
+
+    out
+}
+
+/// Split shell-style words, honouring quotes and backslash escapes.
+fn words(args: &str) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut current = String::new();
+    let mut quote: Option<char> = None;
+    let mut in_word = false;
+    let mut chars = args.chars();
+
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (None, '"' | '\'') => {
+                quote = Some(c);
+                in_word = true;
+            }
+            (q, '\\') if q != Some('\'') => {
+                if let Some(next) = chars.next() {
+                    current.push(next);
+                }
+                in_word = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_word {
+                    out.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+            }
+            (_, c) => {
+                current.push(c);
+                in_word = true;
+            }
+        }
+    }
+    if in_word {
+        out.push(current);
+    }
+
+    out
+}
+
+/// `KEY=value` pairs of an `ENV`/`ARG` instruction, including the legacy
+/// `ENV KEY value with spaces` form. `ARG NAME` without default yields nothing.
+fn assignments(keyword: &str, args: &str) -> Vec<(String, String)> {
+    let words = words(args);
+
+    if keyword == "ENV" && words.len() >= 2 && !words[0].contains('=') {
+        return vec![(words[0].clone(), words[1..].join(" "))];
+    }
+
+    words
+        .iter()
+        .filter_map(|w| w.split_once('='))
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+/// Check a single assignment, skipping references to other variables.
+fn detect_env(key: &str, value: &str) -> Option<(String, Confidence, Option<String>)> {
+    let value = value.trim();
+    if value.is_empty() || value.starts_with('$') {
+        return None;
+    }
+    detect_assignment(value, key, key)
+}
+
+/// Check whether a `COPY`/`ADD` source is an env file with real values.
+fn is_env_source(source: &str) -> bool {
+    let name = source
+        .trim_end_matches('/')
+        .rsplit('/')
+        .next()
+        .unwrap_or(source);
+    name.starts_with(".env")
+        && ![".example", ".sample", ".template"]
+            .iter()
+            .any(|suffix| name.ends_with(suffix))
+}
+
+/// Check whether `.dockerignore` next to the Dockerfile keeps `.env` out of
+/// the build context.
+fn dockerignore_excludes_env(dockerfile: &Path) -> bool {
+    let ignore = dockerfile
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join(".dockerignore");
+
+    std::fs::read_to_string(ignore)
+        .map(|content| {
+            content.lines().map(str::trim).any(|l| {
+                matches!(
+                    l,
+                    ".env" | ".env*" | "*.env" | "**/.env" | "**/.env*" | ".env.*"
+                )
+            })
+        })
+        .unwrap_or(false)
+}
+
+/// Scan a Dockerfile.
+///
+/// `ENV`/`ARG` values are checked as assignments and `COPY`/`ADD` of env
+/// files is reported; all other lines go through the regular text scan.
+pub fn scan_dockerfile(
+    path: &Path,
+    content: &str,
+    results: &mut ScanResults,
+    ignore_placeholders: bool,
+) -> Result<()> {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut handled = HashSet::new();
+
+    for instr in instructions(content) {
+        match instr.keyword.as_str() {
+            "ENV" | "ARG" => {
+                handled.extend(instr.lines.clone());
+                for (key, value) in assignments(&instr.keyword, &instr.args) {
+                    let Some((pattern, confidence, action_url)) = detect_env(&key, &value) else {
+                        continue;
+                    };
+                    let column = lines
+                        .get(instr.line - 1)
+                        .and_then(|l| l.find(key.as_str()).map(|i| l[..i].chars().count() + 1))
+                        .unwrap_or(1);
+
+                    let finding = Finding {
+                        pattern,
+                        confidence: format!("{}", confidence),
+                        value_preview: truncate_value(&value),
+                        location: format!("{}:{} ({})", path.display(), instr.line, key),
+                        file: path.display().to_string(),
+                        line: instr.line,
+                        column,
+                        variable: Some(key),
+                        action_url,
+                    };
+                    record_finding(results, finding, &confidence);
+                }
+            }
+            "COPY" | "ADD" => {
+                let words: Vec<String> = words(&instr.args)
+                    .into_iter()
+                    .filter(|w| !w.starts_with("--"))
+                    .collect();
+                let Some((_, sources)) = words.split_last() else {
+                    continue;
+                };
+
+                let pattern = if let Some(source) = sources.iter().find(|s| is_env_source(s)) {
+                    format!("Env file copied into image ({})", source)
+                } else if sources.iter().any(|s| s == "." || s == "./")
+                    && path
+                        .parent()
+                        .unwrap_or(Path::new("."))
+                        .join(".env")
+                        .is_file()
+                    && !dockerignore_excludes_env(path)
+                {
+                    "Build context with .env copied into image (no .dockerignore entry)".to_string()
+                } else {
+                    continue;
+                };
+
+                let finding = Finding {
+                    pattern,
+                    confidence: format!("{}", Confidence::Medium),
+                    value_preview: instr.keyword.clone(),
+                    location: format!("{}:{}", path.display(), instr.line),
+                    file: path.display().to_string(),
+                    line: instr.line,
+                    column: 1,
+                    variable: None,
+                    action_url: None,
+                };
+                record_finding(results, finding, &Confidence::Medium);
+            }
+            _ => {}
+        }
+    }
+
+    // Blank the lines handled above so the text scan does not report them
+    // again, keeping line numbers intact.
+    let rest: Vec<&str> = lines
+        .iter()
+        .enumerate()
+        .map(|(idx, line)| {
+            if handled.contains(&(idx + 1)) {
+                ""
+            } else {
+                *line
+            }
+        })
+        .collect();
+    scan_text_file(path, &rest.join("\n"), results, ignore_placeholders)
+}
+
+/// One entry of a `docker save` `manifest.json`.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ManifestEntry {
+    config: String,
+    #[serde(default)]
+    layers: Vec<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ImageConfig {
+    #[serde(default)]
+    config: Option<ContainerConfig>,
+    #[serde(default)]
+    history: Vec<HistoryEntry>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ContainerConfig {
+    #[serde(default)]
+    env: Option<Vec<String>>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct HistoryEntry {
+    #[serde(default)]
+    created_by: Option<String>,
+    #[serde(default)]
+    empty_layer: bool,
+}
+
+/// A layer of a scanned image, for the pretty-output legend.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayerInfo {
+    /// Member path of the layer inside the image archive.
+    pub path: String,
+    /// History command that created the layer, when recorded.
+    pub created_by: Option<String>,
+}
+
+/// Scan a `docker save` archive.
+///
+/// Returns the image layers in order, with the command that created each.
+pub fn scan_image_tar(
+    image: &Path,
+    results: &mut ScanResults,
+    ignore_placeholders: bool,
+) -> Result<Vec<LayerInfo>> {
+    // Pass 1: the manifest names the config and layer members.
+    let manifest: Vec<ManifestEntry> = {
+        let mut archive = open_archive(image)?;
+        let mut manifest = None;
+        for entry in archive.entries()? {
+            let mut entry = entry?;
+            if entry.path()?.to_string_lossy().trim_start_matches("./") == "manifest.json" {
+                let mut buf = String::new();
+                entry.read_to_string(&mut buf)?;
+                manifest = Some(buf);
+                break;
+            }
+        }
+        let manifest = manifest.with_context(|| {
+            format!(
+                "{} has no manifest.json; is it a `docker save` archive?",
+                image.display()
+            )
+        })?;
+        serde_json::from_str(&manifest)
+            .with_context(|| format!("Invalid manifest.json in {}", image.display()))?
+    };
+
+    let configs: HashSet<&str> = manifest.iter().map(|m| m.config.as_str()).collect();
+    let layer_paths: Vec<String> = manifest.iter().flat_map(|m| m.layers.iter().cloned()).fold(
+        Vec::new(),
+        |mut acc, layer| {
+            if !acc.contains(&layer) {
+                acc.push(layer);
+            }
+            acc
+        },
+    );
+
+    // Pass 2: scan configs and layers wherever they appear in the archive.
+    let mut layer_commands: HashMap<String, String> = HashMap::new();
+    let mut archive = open_archive(image)?;
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let member = entry
+            .path()?
+            .to_string_lossy()
+            .trim_start_matches("./")
+            .to_string();
+
+        if configs.contains(member.as_str()) {
+            let mut buf = String::new();
+            entry.read_to_string(&mut buf)?;
+            let config: ImageConfig = serde_json::from_str(&buf).unwrap_or_default();
+            let label = format!("{}!/{}", image.display(), member);
+
+            scan_image_config(&label, &config, results);
+
+            // Non-empty history entries map onto layers in order.
+            if let Some(entry) = manifest.iter().find(|m| m.config == member) {
+                let commands = config
+                    .history
+                    .iter()
+                    .filter(|h| !h.empty_layer)
+                    .map(|h| h.created_by.clone().unwrap_or_default());
+                for (layer, command) in entry.layers.iter().zip(commands) {
+                    layer_commands.insert(layer.clone(), command);
+                }
+            }
+        } else if layer_paths.contains(&member) {
+            let label = format!("{}!/{}", image.display(), member);
+            scan_layer(&label, entry, results, ignore_placeholders)
+                .with_context(|| format!("Failed to read layer {}", label))?;
+        }
+    }
+
+    Ok(layer_paths
+        .into_iter()
+        .map(|path| LayerInfo {
+            created_by: layer_commands.get(&path).cloned(),
+            path,
+        })
+        .collect())
+}
+
+fn open_archive(image: &Path) -> Result<tar::Archive<BufReader<File>>> {
+    let file = File::open(image).with_context(|| format!("Failed to open {}", image.display()))?;
+    Ok(tar::Archive::new(BufReader::new(file)))
+}
+
+/// Check the image config: final `Env` values and the build history,
+/// where `ARG` values used by `RUN` steps are recorded in clear text.
+fn scan_image_config(label: &str, config: &ImageConfig, results: &mut ScanResults) {
+    let env: Vec<(String, String)> = config
+        .config
+        .as_ref()
+        .and_then(|c| c.env.as_ref())
+        .map(|env| {
+            env.iter()
+                .filter_map(|e| e.split_once('='))
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    for (key, value) in &env {
+        report_config_value(label, &format!("Env {}", key), key, value, results);
+    }
+
+    for (idx, entry) in config.history.iter().enumerate() {
+        let Some(created_by) = &entry.created_by else {
+            continue;
+        };
+        for (key, value) in history_assignments(created_by) {
+            // Final ENV values were reported above.
+            if env.iter().any(|(k, v)| *k == key && *v == value) {
+                continue;
+            }
+            report_config_value(
+                label,
+                &format!("history[{}] {}", idx, key),
+                &key,
+                &value,
+                results,
+            );
+        }
+    }
+}
+
+fn report_config_value(
+    label: &str,
+    context: &str,
+    key: &str,
+    value: &str,
+    results: &mut ScanResults,
+) {
+    let Some((pattern, confidence, action_url)) = detect_env(key, value) else {
+        return;
+    };
+
+    let finding = Finding {
+        pattern,
+        confidence: format!("{}", confidence),
+        value_preview: truncate_value(value),
+        location: format!("{} ({})", label, context),
+        file: label.to_string(),
+        line: 1,
+        column: 1,
+        variable: Some(key.to_string()),
+        action_url,
+    };
+    record_finding(results, finding, &confidence);
+}
+
+/// Assignments recorded in a history `created_by` command.
+///
+/// Handles the classic builder (`/bin/sh -c #(nop)  ENV K=v`,
+/// `|2 A=1 B=2 /bin/sh -c ...`) and BuildKit (`ENV K=v`, `RUN |1 A=1 ...`).
+fn history_assignments(created_by: &str) -> Vec<(String, String)> {
+    let command = created_by
+        .trim()
+        .trim_start_matches("/bin/sh -c #(nop)")
+        .trim()
+        .trim_end_matches("# buildkit")
+        .trim();
+
+    let (keyword, args) = command
+        .split_once(char::is_whitespace)
+        .unwrap_or((command, ""));
+    match keyword.to_uppercase().as_str() {
+        "ENV" | "ARG" => assignments(&keyword.to_uppercase(), args),
+        "RUN" if args.starts_with('|') => build_args(args),
+        _ if keyword.starts_with('|') => build_args(command),
+        _ => Vec::new(),
+    }
+}
+
+/// Parse the `|N K=v ...` build-argument prefix of a `RUN` history entry.
+fn build_args(args: &str) -> Vec<(String, String)> {
+    let words = words(args);
+    let count = words
+        .first()
+        .and_then(|w| w.strip_prefix('|'))
+        .and_then(|n| n.parse::<usize>().ok())
+        .unwrap_or(0);
+
+    words
+        .iter()
+        .skip(1)
+        .take(count)
+        .filter_map(|w| w.split_once('='))
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+/// Walk one layer tarball (plain or gzip-compressed).
+fn scan_layer<R: Read>(
+    label: &str,
+    reader: R,
+    results: &mut ScanResults,
+    ignore_placeholders: bool,
+) -> Result<()> {
+    let mut reader = BufReader::new(reader);
+    let gzip = reader.fill_buf()?.starts_with(&[0x1f, 0x8b]);
+    let reader: Box<dyn Read> = if gzip {
+        Box::new(GzDecoder::new(reader))
+    } else {
+        Box::new(reader)
+    };
+
+    let mut layer = tar::Archive::new(reader);
+    for entry in layer.entries()? {
+        let mut entry = entry?;
+        if !entry.header().entry_type().is_file() || entry.size() > MAX_MEMBER_SIZE {
+            continue;
+        }
+
+        let member = entry
+            .path()?
+            .to_string_lossy()
+            .trim_start_matches("./")
+            .to_string();
+        let member_path = Path::new(&member);
+        let name = member_path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        let env_file = name == ".env" || name.starts_with(".env.") || name.ends_with(".env");
+        if name.starts_with(".wh.")
+            || LAYER_EXCLUDES.iter().any(|p| member.contains(p))
+            || (!env_file
+                && (SYSTEM_PREFIXES.iter().any(|p| member.starts_with(p))
+                    || member.contains("-packages/")
+                    || !is_scannable_file(member_path)))
+        {
+            continue;
+        }
+
+        let mut bytes = Vec::new();
+        entry.read_to_end(&mut bytes)?;
+        let Ok(content) = String::from_utf8(bytes) else {
+            continue; // Skip binary files
+        };
+
+        let path = PathBuf::from(format!("{}!/{}", label, member));
+        results.files_scanned += 1;
+        scan_content(&path, &content, results, ignore_placeholders)?;
+    }
+
+    Ok(())
+}
+
+/// Print the build command of each image layer that has findings.
+pub fn print_layer_legend(image: &Path, layers: &[LayerInfo], results: &ScanResults) {
+    let prefix = format!("{}!/", image.display());
+    let affected: Vec<(usize, &LayerInfo)> = layers
+        .iter()
+        .enumerate()
+        .filter(|(_, layer)| {
+            let layer_prefix = format!("{}{}!/", prefix, layer.path);
+            results
+                .findings
+                .iter()
+                .any(|f| f.file.starts_with(&layer_prefix))
+        })
+        .collect();
+
+    if affected.is_empty() {
+        return;
+    }
+
+    println!("{} {}", "Layers with findings in".bold(), image.display());
+    for (idx, layer) in affected {
+        let command = layer.created_by.as_deref().unwrap_or("(no history)");
+        println!("  {}. {}", idx + 1, layer.path.dimmed());
+        println!(
+            "     Created by: {}",
+            crate::utils::string::truncate(command, 100)
+        );
+    }
+    println!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_dockerfile() {
+        assert!(is_dockerfile(Path::new("Dockerfile")));
+        assert!(is_dockerfile(Path::new("docker/Dockerfile.prod")));
+        assert!(is_dockerfile(Path::new("api.Dockerfile")));
+        assert!(!is_dockerfile(Path::new("docker-compose.yml")));
+    }
+
+    #[test]
+    fn test_instructions_join_continuations() {
+        let content = "FROM alpine\n# comment\nENV A=1 \\\n    B=2\nRUN echo hi\n";
+        let parsed = instructions(content);
+
+        assert_eq!(parsed.len(), 3);
+        assert_eq!(parsed[1].keyword, "ENV");
+        assert_eq!(parsed[1].args, "A=1  B=2");
+        assert_eq!(parsed[1].lines, 3..=4);
+    }
+
+    #[test]
+    fn test_assignments() {
+        assert_eq!(
+            assignments("ENV", r#"A="x y" B=2"#),
+            vec![("A".into(), "x y".into()), ("B".into(), "2".into())]
+        );
+        assert_eq!(
+            assignments("ENV", "LEGACY some value"),
+            vec![("LEGACY".into(), "some value".into())]
+        );
+        assert!(assignments("ARG", "TOKEN").is_empty());
+    }
+
+    #[test]
+    fn test_history_assignments() {
+        assert_eq!(
+            history_assignments("/bin/sh -c #(nop)  ENV DB_PASSWORD=Xk9vQ2mL7pR4"),
+            vec![("DB_PASSWORD".into(), "Xk9vQ2mL7pR4".into())]
+        );
+        assert_eq!(
+            history_assignments("RUN |2 NPM_TOKEN=abc DEBUG=1 /bin/sh -c npm ci # buildkit"),
+            vec![
+                ("NPM_TOKEN".into(), "abc".into()),
+                ("DEBUG".into(), "1".into())
+            ]
+        );
+        assert!(history_assignments("/bin/sh -c apk add curl").is_empty());
+    }
+
+    #[test]
+    fn test_scan_dockerfile() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("Dockerfile");
+        let content = "FROM node:20\nARG NPM_TOKEN\nENV DB_PASSWORD=Xk9vQ2mL7pR4 PORT=3000\nENV API_URL=${API_URL}\nCOPY .env /app/.env\nCOPY .env.example /app/\n";
+        let mut results = ScanResults::default();
+
+        scan_dockerfile(&path, content, &mut results, false).unwrap();
+
+        let found: Vec<(&str, usize)> = results
+            .findings
+            .iter()
+            .map(|f| (f.pattern.as_str(), f.line))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("Hardcoded credential (sensitive key name)", 3),
+                ("Env file copied into image (.env)", 5),
+            ]
+        );
+        assert_eq!(results.findings[0].column, 5);
+    }
+
+    #[test]
+    fn test_copy_build_context_without_dockerignore() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("Dockerfile");
+        std::fs::write(dir.path().join(".env"), "A=1\n").unwrap();
+        let mut results = ScanResults::default();
+
+        scan_dockerfile(&path, "COPY . /app\n", &mut results, false).unwrap();
+        assert_eq!(results.secrets_found, 1);
+
+        std::fs::write(dir.path().join(".dockerignore"), ".env\n").unwrap();
+        let mut results = ScanResults::default();
+        scan_dockerfile(&path, "COPY . /app\n", &mut results, false).unwrap();
+        assert_eq!(results.secrets_found, 0);
+    }
+
+    fn tar_bytes(files: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut builder = tar::Builder::new(Vec::new());
+        for (name, data) in files {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(data.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder.append_data(&mut header, name, *data).unwrap();
+        }
+        builder.into_inner().unwrap()
+    }
+
+    #[test]
+    fn test_scan_image_tar() {
+        let token = format!("ghp_{}", "aB3dE5fG7hJ9kL1mN3pQ5rS7tU9vW1xY3zA5");
+        let base = tar_bytes(&[("usr/lib/python3/test_keys.py", token.as_bytes())]);
+        let app_env = format!("GITHUB_TOKEN={}\n", token);
+        let node_env = format!("NPM_TOKEN=npm_{}\n", "aB3dE5fG7hJ9kL1mN3pQ5rS7tU9vW1xY3zA5");
+        let bundle = format!("const token = \"{}\";\n", token);
+        let app = tar_bytes(&[
+            ("app/.env", app_env.as_bytes()),
+            ("app/dist/main.js", bundle.as_bytes()),
+            ("app/node_modules/pkg/keys.js", bundle.as_bytes()),
+            ("usr/src/app/.env", node_env.as_bytes()),
+            ("usr/share/doc/example.env", node_env.as_bytes()),
+        ]);
+
+        let config = serde_json::json!({
+            "config": { "Env": ["PATH=/usr/bin", "DB_PASSWORD=Xk9vQ2mL7pR4"] },
+            "history": [
+                { "created_by": "/bin/sh -c #(nop) ADD file:abc in /" },
+                { "created_by": "/bin/sh -c #(nop)  ENV DB_PASSWORD=Xk9vQ2mL7pR4", "empty_layer": true },
+                { "created_by": "|1 NPM_TOKEN=Np9mK2xQ7vL4 /bin/sh -c npm ci" }
+            ]
+        })
+        .to_string();
+        let manifest = serde_json::json!([{
+            "Config": "abc123.json",
+            "RepoTags": ["app:latest"],
+            "Layers": ["base/layer.tar", "app/layer.tar"]
+        }])
+        .to_string();
+
+        let image = tar_bytes(&[
+            ("base/layer.tar", &base),
+            ("app/layer.tar", &app),
+            ("abc123.json", config.as_bytes()),
+            ("manifest.json", manifest.as_bytes()),
+        ]);
+        let dir = tempfile::tempdir().unwrap();
+        let image_path = dir.path().join("image.tar");
+        std::fs::write(&image_path, image).unwrap();
+
+        let mut results = ScanResults::default();
+        let layers = scan_image_tar(&image_path, &mut results, false).unwrap();
+
+        let image_label = image_path.display().to_string();
+        let locations: Vec<String> = results
+            .findings
+            .iter()
+            .map(|f| f.location.replace(&image_label, "image.tar"))
+            .collect();
+        assert_eq!(
+            locations,
+            vec![
+                "image.tar!/app/layer.tar!/app/.env:1 (GITHUB_TOKEN)",
+                "image.tar!/app/layer.tar!/app/dist/main.js:1",
+                "image.tar!/app/layer.tar!/usr/src/app/.env:1 (NPM_TOKEN)",
+                "image.tar!/app/layer.tar!/usr/share/doc/example.env:1 (NPM_TOKEN)",
+                "image.tar!/abc123.json (Env DB_PASSWORD)",
+                "image.tar!/abc123.json (history[2] NPM_TOKEN)",
+            ]
+        );
+
+        assert_eq!(layers.len(), 2);
+        assert_eq!(layers[1].path, "app/layer.tar");
+        assert_eq!(
+            layers[1].created_by.as_deref(),
+            Some("|1 NPM_TOKEN=Np9mK2xQ7vL4 /bin/sh -c npm ci")
+        );
+    }
+}
@@ -0,0 +1,107 @@
+//! Example hygiene mode for `scan` (`--examples`).
+//!
+//! The regular scan skips `.env.example` and friends; this mode scans only
+//! them. Env-style templates must hold placeholders, empty values or
+//! allowed defaults (see [`crate::core::hygiene`]); other templates
+//! (`settings.yml.sample`) get the regular content scan.
+
+use anyhow::Result;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::{column_of, record_finding, scan_content, truncate_value, walk, Finding, ScanResults};
+use crate::core::hygiene;
+
+/// Example, sample and template files under `paths`.
+pub fn collect_example_files(paths: &[String]) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+
+    for path_str in paths {
+        let path = Path::new(path_str);
+        if path.is_file() {
+            files.push(path.to_path_buf());
+        } else if path.is_dir() {
+            files.extend(
+                walk(path)
+                    .map(|entry| entry.into_path())
+                    .filter(|p| hygiene::is_example_file(p)),
+            );
+        }
+    }
+
+    files
+}
+
+/// Check example files; `allowed` lists keys whose literal default is fine.
+pub fn scan_examples(
+    files: &[PathBuf],
+    allowed: &[String],
+    results: &mut ScanResults,
+    ignore_placeholders: bool,
+) -> Result<()> {
+    results.files_scanned += files.len();
+
+    for path in files {
+        if !hygiene::is_env_example(path) {
+            if let Ok(content) = fs::read_to_string(path) {
+                scan_content(path, &content, results, ignore_placeholders)?;
+            }
+            continue;
+        }
+
+        let content = fs::read_to_string(path).unwrap_or_default();
+        let lines: Vec<&str> = content.lines().collect();
+
+        for violation in hygiene::check_file(path, allowed)? {
+            let column = lines
+                .get(violation.line.wrapping_sub(1))
+                .and_then(|line| {
+                    line.find(violation.value.as_str())
+                        .map(|i| column_of(line, &line[i..]))
+                })
+                .unwrap_or(1);
+
+            let finding = Finding {
+                pattern: violation.pattern,
+                confidence: format!("{}", violation.confidence),
+                value_preview: truncate_value(&violation.value),
+                location: format!("{}:{} ({})", path.display(), violation.line, violation.key),
+                file: path.display().to_string(),
+                line: violation.line,
+                column,
+                variable: Some(violation.key),
+                action_url: None,
+            };
+            record_finding(results, finding, &violation.confidence);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scan_examples() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join(".env.example"),
+            "PORT=3000\nAPI_KEY=<your-key>\nDB_PASSWORD=postgres\nSESSION_SECRET=Xk9vQ2mL7pR4\n",
+        )
+        .unwrap();
+        fs::write(dir.path().join(".env"), "SESSION_SECRET=Xk9vQ2mL7pR4\n").unwrap();
+
+        let files = collect_example_files(&[dir.path().display().to_string()]);
+        assert_eq!(files, vec![dir.path().join(".env.example")]);
+
+        let mut results = ScanResults::default();
+        scan_examples(&files, &["DB_PASSWORD".to_string()], &mut results, false).unwrap();
+
+        assert_eq!(results.secrets_found, 1);
+        let finding = &results.findings[0];
+        assert_eq!(finding.variable.as_deref(), Some("SESSION_SECRET"));
+        assert_eq!((finding.line, finding.column), (4, 16));
+    }
+}
@@ -0,0 +1,365 @@
+//! Leak detection for the real values of a `.env` file.
+//!
+//! Values copied from `.env` into fixtures, notebooks or logs often don't
+//! look like any known secret format. With `scan --known-values .env` the
+//! secret values of that file are searched for verbatim, and in their base64
+//! and URL-encoded forms, across every scanned file. Values are never printed;
+//! findings only name the variable that leaked.
+
+use aho_corasick::{AhoCorasick, MatchKind};
+use anyhow::{Context, Result};
+use base64::{engine::general_purpose, Engine};
+use std::collections::HashSet;
+use std::fs;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+use super::{record_finding, Finding, ScanResults};
+use crate::core::Parser;
+use crate::utils::patterns::{is_secret_assignment, Confidence};
+
+/// How a known value appeared in a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Encoding {
+    Raw,
+    Base64,
+    Base64Url,
+    UrlEncoded,
+}
+
+impl std::fmt::Display for Encoding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Encoding::Raw => write!(f, "verbatim"),
+            Encoding::Base64 => write!(f, "base64"),
+            Encoding::Base64Url => write!(f, "base64url"),
+            Encoding::UrlEncoded => write!(f, "URL-encoded"),
+        }
+    }
+}
+
+/// Secret values loaded from an env file, compiled into one matcher.
+pub struct KnownValues {
+    source: PathBuf,
+    matcher: AhoCorasick,
+    /// Variable name and encoding for each matcher pattern.
+    needles: Vec<(String, Encoding)>,
+}
+
+impl KnownValues {
+    /// Load the secret values of `path` (see [`is_secret_assignment`]).
+    pub fn load(path: &Path) -> Result<Self> {
+        let env = Parser::default()
+            .parse_file(path)
+            .with_context(|| format!("Failed to parse known values from {}", path.display()))?;
+
+        let mut vars: Vec<(String, String)> = env
+            .vars
+            .into_iter()
+            .filter(|(key, value)| is_secret_assignment(key, value))
+            .collect();
+        vars.sort();
+
+        let source = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+        Self::from_values(source, vars)
+    }
+
+    fn from_values(source: PathBuf, vars: Vec<(String, String)>) -> Result<Self> {
+        let mut patterns = Vec::new();
+        let mut needles = Vec::new();
+        let mut seen = HashSet::new();
+
+        for (key, value) in vars {
+            for (encoding, needle) in encodings(&value) {
+                if seen.insert(needle.clone()) {
+                    patterns.push(needle);
+                    needles.push((key.clone(), encoding));
+                }
+            }
+        }
+
+        let matcher = AhoCorasick::builder()
+            .match_kind(MatchKind::LeftmostLongest)
+            .build(&patterns)
+            .context("Failed to build known-value matcher")?;
+
+        Ok(Self {
+            source,
+            matcher,
+            needles,
+        })
+    }
+
+    /// Number of distinct variables being searched for.
+    pub fn len(&self) -> usize {
+        self.needles
+            .iter()
+            .map(|(key, _)| key)
+            .collect::<HashSet<_>>()
+            .len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.needles.is_empty()
+    }
+
+    /// Search `files` for known values and record a finding per leak.
+    ///
+    /// The env file the values came from is skipped.
+    pub fn scan_files(&self, files: &[PathBuf], results: &mut ScanResults) {
+        for file in files {
+            if fs::canonicalize(file).is_ok_and(|p| p == self.source) {
+                continue;
+            }
+
+            // Logs and notebooks are not always valid UTF-8; match on bytes.
+            if let Ok(content) = fs::read(file) {
+                self.scan_content(file, &content, results);
+            }
+        }
+    }
+
+    /// Search one file's content; findings are located as `path:line (KEY)`.
+    pub(super) fn scan_content(&self, path: &Path, content: &[u8], results: &mut ScanResults) {
+        let mut reported = HashSet::new();
+        let mut line = 1;
+        let mut line_start = 0;
+        let mut counted_to = 0;
+
+        for (range, key, encoding) in self.matches(content) {
+            for (i, &b) in content[counted_to..range.start].iter().enumerate() {
+                if b == b'\n' {
+                    line += 1;
+                    line_start = counted_to + i + 1;
+                }
+            }
+            counted_to = range.start;
+            let column = String::from_utf8_lossy(&content[line_start..range.start])
+                .chars()
+                .count()
+                + 1;
+
+            if !reported.insert((line, key, encoding)) {
+                continue;
+            }
+
+            let finding = leak_finding(path, line, column, key, encoding);
+            record_finding(results, finding, &Confidence::High);
+        }
+    }
+
+    /// Byte range, variable and encoding of every known value in `content`.
+    pub(super) fn matches<'a>(
+        &'a self,
+        content: &'a [u8],
+    ) -> impl Iterator<Item = (Range<usize>, &'a str, Encoding)> + 'a {
+        self.matcher.find_iter(content).map(|m| {
+            let (key, encoding) = &self.needles[m.pattern().as_usize()];
+            (m.range(), key.as_str(), *encoding)
+        })
+    }
+}
+
+/// Finding for the value of `key` at `line`, located as `path:line (KEY)`.
+pub(super) fn leak_finding(
+    path: &Path,
+    line: usize,
+    column: usize,
+    key: &str,
+    encoding: Encoding,
+) -> Finding {
+    Finding {
+        pattern: format!("Known .env value ({})", encoding),
+        confidence: format!("{}", Confidence::High),
+        value_preview: format!("[value of {}]", key),
+        location: format!("{}:{} ({})", path.display(), line, key),
+        file: path.display().to_string(),
+        line,
+        column,
+        variable: Some(key.to_string()),
+        action_url: None,
+    }
+}
+
+/// Shortest base64 fragment searched for; shorter ones match by chance.
+const MIN_BASE64_LEN: usize = 8;
+
+/// The forms a value is searched in.
+///
+/// Inside a longer encoded string (`Basic base64(user:password)`) the value's
+/// encoding depends on its offset modulo 3 and on the bytes around it, so it
+/// is searched as three fragments, one per offset, each holding only the
+/// characters that the value's bytes fully determine.
+fn encodings(value: &str) -> Vec<(Encoding, String)> {
+    let mut forms = vec![(Encoding::Raw, value.to_string())];
+
+    for offset in 0..3 {
+        let Some(standard) = base64_fragment(value, offset, &general_purpose::STANDARD_NO_PAD)
+        else {
+            continue;
+        };
+        let url_safe = base64_fragment(value, offset, &general_purpose::URL_SAFE_NO_PAD)
+            .expect("same length as the standard fragment");
+        if url_safe != standard {
+            forms.push((Encoding::Base64Url, url_safe));
+        }
+        forms.push((Encoding::Base64, standard));
+    }
+
+    let percent = percent_encode(value);
+    if percent != value {
+        forms.push((Encoding::UrlEncoded, percent));
+    }
+
+    forms
+}
+
+/// Base64 characters that depend only on `value`, when it starts `offset`
+/// bytes into a 3-byte group: characters mixing in the bytes before it or
+/// after it are dropped.
+fn base64_fragment(value: &str, offset: usize, engine: &impl Engine) -> Option<String> {
+    let mut bytes = vec![0; offset];
+    bytes.extend_from_slice(value.as_bytes());
+    bytes.truncate(bytes.len() / 3 * 3);
+
+    // Characters carrying bits of the filler: none, 2 or 3.
+    let skip = [0, 2, 3][offset];
+    let encoded = engine.encode(&bytes);
+    let fragment = encoded.get(skip..)?;
+    (fragment.len() >= MIN_BASE64_LEN).then(|| fragment.to_string())
+}
+
+/// Percent-encode everything outside the RFC 3986 unreserved set.
+fn percent_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn known(vars: &[(&str, &str)]) -> KnownValues {
+        let vars = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        KnownValues::from_values(PathBuf::from(".env"), vars).unwrap()
+    }
+
+    #[test]
+    fn test_percent_encode() {
+        assert_eq!(percent_encode("p@ss/w0rd"), "p%40ss%2Fw0rd");
+        assert_eq!(percent_encode("plain-value"), "plain-value");
+    }
+
+    #[test]
+    fn test_load_keeps_only_secret_values() {
+        let dir = tempfile::tempdir().unwrap();
+        let env = dir.path().join(".env");
+        fs::write(
+            &env,
+            "DB_PASSWORD=Xk9vQ2mL7pR4\nDEBUG=true\nAPP_HOST=localhost.internal\nAPI_KEY=your_key_here\n",
+        )
+        .unwrap();
+
+        let known = KnownValues::load(&env).unwrap();
+        assert_eq!(known.len(), 1);
+    }
+
+    #[test]
+    fn test_finds_raw_and_encoded_values() {
+        let known = known(&[("DB_PASSWORD", "Xk9vQ2m/L7p@R4")]);
+        let encoded = general_purpose::STANDARD.encode("Xk9vQ2m/L7p@R4");
+        let content = format!(
+            "fixture = \"Xk9vQ2m/L7p@R4\"\n\nauth: {}\nurl=postgres://u:Xk9vQ2m%2FL7p%40R4@db\n",
+            encoded
+        );
+        let mut results = super::super::scan_files(&[], false).unwrap();
+
+        known.scan_content(Path::new("log.txt"), content.as_bytes(), &mut results);
+
+        let found: Vec<(&str, &str)> = results
+            .findings
+            .iter()
+            .map(|f| (f.pattern.as_str(), f.location.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("Known .env value (verbatim)", "log.txt:1 (DB_PASSWORD)"),
+                ("Known .env value (base64)", "log.txt:3 (DB_PASSWORD)"),
+                ("Known .env value (URL-encoded)", "log.txt:4 (DB_PASSWORD)"),
+            ]
+        );
+        assert!(results
+            .findings
+            .iter()
+            .all(|f| !f.value_preview.contains("Xk9v")));
+    }
+
+    #[test]
+    fn test_finds_base64_at_any_offset() {
+        // Lengths 14 and 13 leave a partial 3-byte group at the end.
+        for value in ["Xk9vQ2mL7pR4z!", "Xk9vQ2m/L7p@R"] {
+            let known = known(&[("DB_PASSWORD", value)]);
+            for prefix in ["", "a", "admin1:", "ab"] {
+                for suffix in ["", "x", "@db:5432"] {
+                    let plain = format!("{}{}{}", prefix, value, suffix);
+                    for encoded in [
+                        general_purpose::STANDARD.encode(&plain),
+                        general_purpose::URL_SAFE_NO_PAD.encode(&plain),
+                    ] {
+                        let content = format!("Authorization: Basic {}\n", encoded);
+                        let mut results = super::super::scan_files(&[], false).unwrap();
+                        known.scan_content(Path::new("log"), content.as_bytes(), &mut results);
+                        assert_eq!(results.secrets_found, 1, "{:?}", plain);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_base64_fragment() {
+        let engine = &general_purpose::STANDARD_NO_PAD;
+        // "abc" + "def" at offset 0 is exact.
+        assert_eq!(
+            base64_fragment("abcdefghi", 0, engine).unwrap(),
+            "YWJjZGVmZ2hp"
+        );
+        // The trailing partial group is dropped.
+        assert_eq!(
+            base64_fragment("abcdefghijk", 0, engine).unwrap(),
+            "YWJjZGVmZ2hp"
+        );
+        // At offset 1 the first two characters carry the byte before.
+        assert!(general_purpose::STANDARD
+            .encode("Zabcdefghijk")
+            .contains(&base64_fragment("abcdefghijk", 1, engine).unwrap()));
+        assert_eq!(base64_fragment("abcd", 2, engine), None);
+    }
+
+    #[test]
+    fn test_source_file_is_skipped() {
+        let dir = tempfile::tempdir().unwrap();
+        let env = dir.path().join(".env");
+        let copy = dir.path().join("fixture.json");
+        fs::write(&env, "SECRET_TOKEN=Xk9vQ2mL7pR4\n").unwrap();
+        fs::write(&copy, "{\"token\": \"Xk9vQ2mL7pR4\"}\n").unwrap();
+
+        let known = KnownValues::load(&env).unwrap();
+        let mut results = super::super::scan_files(&[], false).unwrap();
+        known.scan_files(&[env, copy], &mut results);
+
+        assert_eq!(results.secrets_found, 1);
+        assert!(results.findings[0].location.contains("fixture.json:1"));
+    }
+}
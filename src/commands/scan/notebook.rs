@@ -0,0 +1,243 @@
+//! Jupyter notebook scanning.
+//!
+//! A notebook is one JSON document, so line-based scanning reports escaped
+//! source and base64 images alike. Here each cell's source and its text
+//! outputs (streams, results, tracebacks) are scanned separately and the
+//! findings are located as `notebook.ipynb:<line> (cell N, source|output)`,
+//! with the line pointing into the `.ipynb` file.
+
+use anyhow::Result;
+use serde_json::Value;
+use std::path::Path;
+
+use super::{scan_text_file, ScanResults};
+
+/// Output MIME types that hold text; images and widgets are skipped.
+const TEXT_MIME_TYPES: &[&str] = &[
+    "text/plain",
+    "text/html",
+    "text/markdown",
+    "application/json",
+];
+
+/// One scannable part of a cell.
+struct Part {
+    cell: usize,
+    kind: &'static str,
+    text: String,
+}
+
+/// Scan a notebook. Returns `false` if `content` is not a notebook, so the
+/// caller can fall back to scanning it as text.
+pub fn scan_notebook(
+    path: &Path,
+    content: &str,
+    results: &mut ScanResults,
+    ignore_placeholders: bool,
+) -> Result<bool> {
+    let Ok(notebook) = serde_json::from_str::<Value>(content) else {
+        return Ok(false);
+    };
+    let Some(cells) = notebook.get("cells").and_then(Value::as_array) else {
+        return Ok(false);
+    };
+
+    let cell_offsets: Vec<usize> = content
+        .match_indices("\"cell_type\"")
+        .map(|(i, _)| i)
+        .collect();
+
+    for part in cells
+        .iter()
+        .enumerate()
+        .flat_map(|(idx, cell)| parts(idx, cell))
+    {
+        let mut found = ScanResults::default();
+        scan_text_file(path, &part.text, &mut found, ignore_placeholders)?;
+
+        // Search from the part's key, so a source line echoed in an output
+        // is attributed to the right one.
+        let cell_start = cell_offsets.get(part.cell).copied().unwrap_or(0);
+        let key = if part.kind == "source" {
+            "\"source\""
+        } else {
+            "\"outputs\""
+        };
+        let part_start = content[cell_start..]
+            .find(key)
+            .map_or(cell_start, |i| cell_start + i);
+        for finding in &mut found.findings {
+            let cell_line = part.text.lines().nth(finding.line - 1).unwrap_or("");
+            if let Some((line, column)) = file_position(content, part_start, cell_line) {
+                // The value sits after the escaped form of its prefix.
+                let prefix: String = cell_line.chars().take(finding.column - 1).collect();
+                finding.line = line;
+                finding.column = column + escape(&prefix).chars().count();
+            }
+            finding.location = format!(
+                "{}:{} (cell {}, {})",
+                path.display(),
+                finding.line,
+                part.cell,
+                part.kind
+            );
+        }
+        results.absorb(found);
+    }
+
+    Ok(true)
+}
+
+/// Source and text outputs of a cell.
+fn parts(cell_idx: usize, cell: &Value) -> Vec<Part> {
+    let mut parts = Vec::new();
+
+    if let Some(source) = cell.get("source") {
+        parts.push(Part {
+            cell: cell_idx,
+            kind: "source",
+            text: multiline(source),
+        });
+    }
+
+    for output in cell
+        .get("outputs")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        let mut texts = Vec::new();
+        if let Some(text) = output.get("text") {
+            texts.push(multiline(text));
+        }
+        if let Some(data) = output.get("data").and_then(Value::as_object) {
+            for mime in TEXT_MIME_TYPES {
+                if let Some(value) = data.get(*mime) {
+                    texts.push(match value {
+                        Value::String(_) | Value::Array(_) => multiline(value),
+                        other => other.to_string(),
+                    });
+                }
+            }
+        }
+        if let Some(traceback) = output.get("traceback") {
+            texts.push(multiline(traceback));
+        }
+        if let Some(evalue) = output.get("evalue").and_then(Value::as_str) {
+            texts.push(evalue.to_string());
+        }
+
+        parts.extend(texts.into_iter().map(|text| Part {
+            cell: cell_idx,
+            kind: "output",
+            text,
+        }));
+    }
+
+    parts.retain(|p| !p.text.trim().is_empty());
+    parts
+}
+
+/// nbformat stores multi-line strings either as one string or as a list of
+/// lines (each keeping its `\n`).
+fn multiline(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Array(lines) => {
+            let mut text = String::new();
+            for line in lines.iter().filter_map(Value::as_str) {
+                text.push_str(line);
+                if !line.ends_with('\n') {
+                    text.push('\n');
+                }
+            }
+            text
+        }
+        _ => String::new(),
+    }
+}
+
+/// `text` as it appears inside a JSON string literal.
+fn escape(text: &str) -> String {
+    let quoted = serde_json::Value::from(text).to_string();
+    quoted[1..quoted.len() - 1].to_string()
+}
+
+/// 1-based line and column in the notebook file of `cell_line`, searching
+/// for its JSON-escaped form from `start`.
+fn file_position(content: &str, start: usize, cell_line: &str) -> Option<(usize, usize)> {
+    let escaped = escape(cell_line);
+    if escaped.is_empty() {
+        return None;
+    }
+
+    let offset = start + content.get(start..)?.find(&escaped)?;
+    let before = &content[..offset];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+
+    Some((
+        before.matches('\n').count() + 1,
+        before[line_start..].chars().count() + 1,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_multiline_joins_source_lists() {
+        let value = serde_json::json!(["a = 1\n", "b = 2"]);
+        assert_eq!(multiline(&value), "a = 1\nb = 2\n");
+    }
+
+    #[test]
+    fn test_scan_notebook_locates_cells_and_outputs() {
+        let token = format!("ghp_{}", "aB3dE5fG7hJ9kL1mN3pQ5rS7tU9vW1xY3zA5");
+        let notebook = serde_json::json!({
+            "cells": [
+                { "cell_type": "markdown", "source": ["# Setup\n"] },
+                {
+                    "cell_type": "code",
+                    "source": ["import os\n", format!("token = \"{}\"", token)],
+                    "outputs": [
+                        { "output_type": "stream", "name": "stdout", "text": [format!("using {}\n", token)] },
+                        { "output_type": "display_data", "data": { "image/png": "iVBORw0KGgo=" } }
+                    ]
+                }
+            ],
+            "metadata": {},
+            "nbformat": 4,
+            "nbformat_minor": 5
+        });
+        let content = serde_json::to_string_pretty(&notebook).unwrap();
+        let path = Path::new("analysis.ipynb");
+        let mut results = ScanResults::default();
+
+        assert!(scan_notebook(path, &content, &mut results, false).unwrap());
+
+        let lines: Vec<&str> = content.lines().collect();
+        let locations: Vec<&str> = results
+            .findings
+            .iter()
+            .map(|f| f.location.as_str())
+            .collect();
+        assert_eq!(locations.len(), 2);
+        assert!(locations[0].ends_with("(cell 1, source)"));
+        assert!(locations[1].ends_with("(cell 1, output)"));
+
+        for finding in &results.findings {
+            let line = lines[finding.line - 1];
+            let column: String = line.chars().skip(finding.column - 1).take(4).collect();
+            assert_eq!(column, "ghp_");
+        }
+    }
+
+    #[test]
+    fn test_scan_notebook_rejects_other_json() {
+        let mut results = ScanResults::default();
+        let scanned =
+            scan_notebook(Path::new("x.ipynb"), "{\"a\": 1}", &mut results, false).unwrap();
+        assert!(!scanned);
+    }
+}
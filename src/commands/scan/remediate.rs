@@ -0,0 +1,638 @@
+//! `scan --remediate`: move hardcoded secrets out of source files.
+//!
+//! For each finding inside a string literal of a supported language, the
+//! value is appended to `.env`, a placeholder to `.env.example`, and the
+//! literal is replaced with the language's environment lookup. Every change
+//! is shown as a diff and confirmed (unless `--yes`); afterwards the rotation
+//! page of the credential is printed, since the old value stays in history.
+
+use anyhow::{Context, Result};
+use colored::*;
+use dialoguer::Confirm;
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::{truncate_value, Finding, ScanResults};
+use crate::core::Parser;
+use crate::formats::escape::{dotenv_value, has_reference};
+use crate::utils::patterns::detect_secret;
+
+lazy_static! {
+    /// Name assigned to the literal: `api_key = `, `apiKey: `, `"apiKey": `,
+    /// `let api_key: &str = `, `apiKey := `.
+    static ref ASSIGNED_NAME: Regex = Regex::new(
+        r#"([A-Za-z_][A-Za-z0-9_]*)["']?\s*(?::\s*&?(?:'static\s+)?[A-Za-z_]\w*\s*)?(?::=|=|:)\s*$"#
+    )
+    .unwrap();
+}
+
+/// Languages whose env lookup we know how to write.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    Python,
+    JavaScript,
+    Rust,
+    Go,
+}
+
+impl Language {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "py" => Some(Language::Python),
+            "js" | "jsx" | "mjs" | "cjs" | "ts" | "tsx" => Some(Language::JavaScript),
+            "rs" => Some(Language::Rust),
+            "go" => Some(Language::Go),
+            _ => None,
+        }
+    }
+
+    /// Expression reading `key` from the environment.
+    ///
+    /// In Rust it borrows the `String`, so it stands where the `&str`
+    /// literal stood.
+    pub fn lookup(self, key: &str) -> String {
+        match self {
+            Language::Python => format!("os.environ[\"{key}\"]"),
+            Language::JavaScript => format!("process.env.{key}"),
+            Language::Rust => format!("&std::env::var(\"{key}\").expect(\"{key} must be set\")"),
+            Language::Go => format!("os.Getenv(\"{key}\")"),
+        }
+    }
+
+    /// Whether a lookup can replace a literal on the last of `lines`: Rust
+    /// `const` and `static` items cannot call functions, and a `'static`
+    /// borrow cannot outlive the looked-up `String`. Go constants, declared
+    /// alone or in a `const ( ... )` block, must be known at compile time.
+    fn can_look_up<S: AsRef<str>>(self, lines: &[S]) -> bool {
+        let Some((line, before)) = lines.split_last() else {
+            return false;
+        };
+        let line = line.as_ref();
+        match self {
+            Language::Rust => {}
+            Language::Go => {
+                return !line.trim_start().starts_with("const ") && !in_go_const_block(before)
+            }
+            _ => return true,
+        }
+        let item = line.trim_start();
+        let item = match item.strip_prefix("pub") {
+            Some(rest) if rest.starts_with(char::is_whitespace) => rest.trim_start(),
+            Some(rest) if rest.starts_with('(') => rest
+                .split_once(')')
+                .map_or(rest, |(_, after)| after.trim_start()),
+            _ => item,
+        };
+        !item.starts_with("const ") && !item.starts_with("static ") && !line.contains("'static")
+    }
+}
+
+/// Whether the line after `before` is inside a Go `const ( ... )` block.
+fn in_go_const_block<S: AsRef<str>>(before: &[S]) -> bool {
+    for line in before.iter().rev() {
+        let line = line.as_ref().trim();
+        if line.starts_with(')') {
+            return false;
+        }
+        if let Some(rest) = line.strip_prefix("const") {
+            if rest.trim_start().starts_with('(') && !rest.contains(')') {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+/// One planned replacement.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fix {
+    pub line: usize,
+    pub key: String,
+    pub value: String,
+    pub old_line: String,
+    pub new_line: String,
+    /// Whether `.env` needs a new entry (false when it already holds the value).
+    pub add_to_env: bool,
+}
+
+/// Quoted string literals on `line`: byte range including the quotes, and
+/// the contents.
+fn literals(line: &str) -> Vec<(usize, usize, &str)> {
+    let mut found = Vec::new();
+    let mut rest = 0;
+    while let Some(open) = line[rest..].find(['"', '\'', '`']).map(|i| rest + i) {
+        let quote = &line[open..open + 1];
+        let Some(len) = line[open + 1..].find(quote) else {
+            break;
+        };
+        found.push((open, open + len + 2, &line[open + 1..open + 1 + len]));
+        rest = open + len + 2;
+    }
+    found
+}
+
+/// The literal holding the finding: the one its column points into, else
+/// the first one a secret rule matches (whole-line matches report column 1).
+fn literal_at(line: &str, column: usize) -> Option<(usize, usize, &str)> {
+    let at = line
+        .char_indices()
+        .nth(column.saturating_sub(1))
+        .map_or(line.len(), |(i, _)| i);
+    let all = literals(line);
+
+    let (start, end, value) = all
+        .iter()
+        .find(|(start, end, _)| *start < at && at < *end)
+        .or_else(|| all.iter().find(|(_, _, v)| detect_secret(v, "").is_some()))
+        .copied()?;
+
+    // Escapes or interpolation would change the value once moved to .env.
+    let quote = line[start..].chars().next()?;
+    if value.is_empty() || value.contains('\\') || (quote == '`' && value.contains("${")) {
+        return None;
+    }
+    Some((start, end, value))
+}
+
+/// Env variable name for a literal: the finding's variable, the name it is
+/// assigned to, or the detector pattern.
+fn variable_name(finding: &Finding, prefix: &str) -> String {
+    let raw = finding
+        .variable
+        .clone()
+        .or_else(|| {
+            ASSIGNED_NAME
+                .captures(prefix)
+                .map(|caps| caps[1].to_string())
+        })
+        .unwrap_or_else(|| finding.pattern.clone());
+
+    let mut key = String::new();
+    let mut prev: Option<char> = None;
+    for ch in raw.chars() {
+        if ch.is_ascii_alphanumeric() {
+            if ch.is_ascii_uppercase() && prev.is_some_and(|p| p.is_ascii_lowercase()) {
+                key.push('_');
+            }
+            key.push(ch.to_ascii_uppercase());
+        } else if !key.is_empty() && !key.ends_with('_') {
+            key.push('_');
+        }
+        prev = Some(ch);
+    }
+
+    let key = key.trim_end_matches('_').to_string();
+    if key.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{key}")
+    } else {
+        key
+    }
+}
+
+/// Plan the fix for `finding` in the file `lines`; `env` holds the current
+/// `.env` entries, used to reuse a matching key or avoid clobbering another
+/// one.
+pub fn plan_fix<S: AsRef<str>>(
+    finding: &Finding,
+    language: Language,
+    lines: &[S],
+    env: &HashMap<String, String>,
+) -> Option<Fix> {
+    let lines = lines.get(..finding.line)?;
+    if !language.can_look_up(lines) {
+        return None;
+    }
+    let line = lines.last()?.as_ref();
+    let (start, end, value) = literal_at(line, finding.column)?;
+    // `.env` loaders would expand a `$NAME` in the moved value.
+    if has_reference(value) {
+        return None;
+    }
+    let base = variable_name(finding, &line[..start]);
+    if base.is_empty() {
+        return None;
+    }
+
+    let mut key = base.clone();
+    let mut n = 1;
+    let add_to_env = loop {
+        match env.get(&key) {
+            None => break true,
+            Some(existing) if existing == value => break false,
+            Some(_) => {
+                n += 1;
+                key = format!("{base}_{n}");
+            }
+        }
+    };
+
+    Some(Fix {
+        line: finding.line,
+        new_line: format!(
+            "{}{}{}",
+            &line[..start],
+            language.lookup(&key),
+            &line[end..]
+        ),
+        old_line: line.to_string(),
+        key,
+        value: value.to_string(),
+        add_to_env,
+    })
+}
+
+/// `.env` line for `key`, quoted when the value needs it.
+fn env_line(key: &str, value: &str) -> String {
+    format!("{}={}", key, dotenv_value(value))
+}
+
+/// Placeholder written to `.env.example`.
+fn placeholder(key: &str) -> String {
+    format!("your_{}_here", key.to_lowercase())
+}
+
+/// Add the import the lookup needs, if the file lacks it.
+fn ensure_import(language: Language, lines: &mut Vec<String>) {
+    match language {
+        Language::Python => {
+            if lines
+                .iter()
+                .any(|l| l.trim() == "import os" || l.trim_start().starts_with("import os,"))
+            {
+                return;
+            }
+            // After a shebang, encoding line or `from __future__` imports.
+            let at = lines
+                .iter()
+                .take_while(|l| {
+                    l.starts_with("#!")
+                        || l.starts_with("# -*-")
+                        || l.starts_with("from __future__")
+                })
+                .count();
+            lines.insert(at, "import os".to_string());
+        }
+        Language::Go => {
+            if lines
+                .iter()
+                .any(|l| l.trim() == "\"os\"" || l.trim() == "import \"os\"")
+            {
+                return;
+            }
+            if let Some(i) = lines.iter().position(|l| l.trim() == "import (") {
+                lines.insert(i + 1, "\t\"os\"".to_string());
+            } else if let Some(i) = lines.iter().position(|l| l.starts_with("package ")) {
+                lines.insert(i + 1, String::new());
+                lines.insert(i + 2, "import \"os\"".to_string());
+            }
+        }
+        Language::JavaScript | Language::Rust => {}
+    }
+}
+
+fn append_line(path: &Path, line: &str) -> Result<()> {
+    let mut content = fs::read_to_string(path).unwrap_or_default();
+    if !content.is_empty() && !content.ends_with('\n') {
+        content.push('\n');
+    }
+    content.push_str(line);
+    content.push('\n');
+    fs::write(path, content).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+fn print_fix(file: &str, fix: &Fix, root: &Path) {
+    let masked = |line: &str| line.replace(&fix.value, &truncate_value(&fix.value));
+
+    println!("\n{}", format!("{}:{}", file, fix.line).bold());
+    println!("  {}", format!("- {}", masked(&fix.old_line).trim()).red());
+    println!("  {}", format!("+ {}", fix.new_line.trim()).green());
+    let env = root.join(".env");
+    if fix.add_to_env {
+        println!(
+            "  {} {}",
+            format!("{}:", env.display()).dimmed(),
+            format!("+ {}=<value>", fix.key).green()
+        );
+    } else {
+        println!(
+            "  {} {}",
+            format!("{}:", env.display()).dimmed(),
+            format!("{} already holds this value", fix.key).dimmed()
+        );
+    }
+    println!(
+        "  {} {}",
+        format!("{}:", root.join(".env.example").display()).dimmed(),
+        format!("+ {}={}", fix.key, placeholder(&fix.key)).green()
+    );
+}
+
+/// Walk through the findings and offer a fix for each one we can rewrite.
+/// `root` is where `.env` and `.env.example` live.
+pub fn run(results: &ScanResults, root: &Path, yes: bool) -> Result<()> {
+    // Group by file; within a line, rewrite right to left so the columns of
+    // the remaining findings stay valid.
+    let mut by_file: BTreeMap<&str, Vec<&Finding>> = BTreeMap::new();
+    for finding in &results.findings {
+        let path = Path::new(&finding.file);
+        if finding.line > 0 && !finding.file.contains('!') && Language::from_path(path).is_some() {
+            by_file.entry(&finding.file).or_default().push(finding);
+        }
+    }
+
+    let skipped = results.findings.len() - by_file.values().map(Vec::len).sum::<usize>();
+    if by_file.is_empty() {
+        println!(
+            "\n{} No findings in Python, JavaScript/TypeScript, Rust or Go source to remediate",
+            "ℹ️".cyan()
+        );
+        return Ok(());
+    }
+
+    println!("\n{}", "Remediation:".bold());
+
+    let env_path = root.join(".env");
+    let example_path = root.join(".env.example");
+    let mut env = if env_path.exists() {
+        Parser::default()
+            .parse_file(&env_path)
+            .with_context(|| format!("Failed to parse {}", env_path.display()))?
+            .vars
+    } else {
+        HashMap::new()
+    };
+    let mut example_keys: Vec<String> = if example_path.exists() {
+        Parser::default()
+            .parse_file(&example_path)
+            .map(|f| f.vars.into_keys().collect())
+            .unwrap_or_default()
+    } else {
+        Vec::new()
+    };
+
+    let mut applied = 0;
+    let mut rotations: Vec<(String, Option<String>)> = Vec::new();
+
+    for (file, mut findings) in by_file {
+        findings.sort_by(|a, b| a.line.cmp(&b.line).then(b.column.cmp(&a.column)));
+        let path = PathBuf::from(file);
+        let language = Language::from_path(&path).expect("filtered above");
+        let content =
+            fs::read_to_string(&path).with_context(|| format!("Failed to read {}", file))?;
+        let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+        let mut changed = false;
+
+        for finding in findings {
+            if finding.line > lines.len() {
+                continue;
+            }
+            let Some(fix) = plan_fix(finding, language, &lines, &env) else {
+                println!(
+                    "\n{} {} is not a plain string literal, fix it by hand",
+                    "⚠️ ".yellow(),
+                    finding.location
+                );
+                continue;
+            };
+
+            print_fix(file, &fix, root);
+            let apply = yes
+                || Confirm::new()
+                    .with_prompt("Apply this fix?")
+                    .default(false)
+                    .interact()?;
+            if !apply {
+                continue;
+            }
+
+            if fix.add_to_env {
+                append_line(&env_path, &env_line(&fix.key, &fix.value))?;
+                env.insert(fix.key.clone(), fix.value.clone());
+            }
+            if !example_keys.contains(&fix.key) {
+                append_line(
+                    &example_path,
+                    &format!("{}={}", fix.key, placeholder(&fix.key)),
+                )?;
+                example_keys.push(fix.key.clone());
+            }
+            lines[finding.line - 1] = fix.new_line;
+            changed = true;
+            applied += 1;
+            if !rotations.iter().any(|(p, _)| p == &finding.pattern) {
+                rotations.push((finding.pattern.clone(), finding.action_url.clone()));
+            }
+        }
+
+        if changed {
+            ensure_import(language, &mut lines);
+            let mut out = lines.join("\n");
+            if content.ends_with('\n') {
+                out.push('\n');
+            }
+            fs::write(&path, out).with_context(|| format!("Failed to write {}", file))?;
+        }
+    }
+
+    println!(
+        "\n{} Applied {} fix(es){}",
+        "✓".green(),
+        applied,
+        if skipped > 0 {
+            format!(", {} finding(s) outside supported source files", skipped)
+        } else {
+            String::new()
+        }
+    );
+
+    if !rotations.is_empty() {
+        println!("\n{}", "Rotate these credentials:".bold());
+        println!(
+            "  {}",
+            "The old values remain in version control history; moving them is not enough.".dimmed()
+        );
+        for (pattern, url) in rotations {
+            match url {
+                Some(url) => println!("  • {} → {}", pattern, url),
+                None => println!("  • {} → revoke it with the issuing service", pattern),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn finding(line: usize, column: usize, variable: Option<&str>) -> Finding {
+        Finding {
+            pattern: "Stripe Secret Key".to_string(),
+            confidence: "high".to_string(),
+            value_preview: String::new(),
+            location: String::new(),
+            file: "app.py".to_string(),
+            line,
+            column,
+            variable: variable.map(str::to_string),
+            action_url: None,
+        }
+    }
+
+    #[test]
+    fn test_plan_fix_per_language() {
+        let env = HashMap::new();
+        let cases = [
+            (
+                Language::Python,
+                "stripe_key = \"sk_live_abc\"",
+                "stripe_key = os.environ[\"STRIPE_KEY\"]",
+            ),
+            (
+                Language::JavaScript,
+                "const stripeKey = 'sk_live_abc';",
+                "const stripeKey = process.env.STRIPE_KEY;",
+            ),
+            (
+                Language::Rust,
+                "let stripe_key: &str = \"sk_live_abc\";",
+                "let stripe_key: &str = &std::env::var(\"STRIPE_KEY\").expect(\"STRIPE_KEY must be set\");",
+            ),
+            (
+                Language::Go,
+                "\tstripeKey := \"sk_live_abc\"",
+                "\tstripeKey := os.Getenv(\"STRIPE_KEY\")",
+            ),
+        ];
+
+        for (language, line, expected) in cases {
+            let column = line.find("sk_").unwrap() + 1;
+            let fix = plan_fix(&finding(1, column, None), language, &[line], &env).unwrap();
+            assert_eq!(fix.new_line, expected);
+            assert_eq!(fix.value, "sk_live_abc");
+        }
+
+        // Go constants must be compile-time values, in a block or not.
+        let go = [
+            "package config",
+            "const stripeKey = \"sk_live_abc\"",
+            "const (",
+            "\tport = 8080",
+            "\tapiKey = \"sk_live_abc\"",
+            ")",
+            "var secret = \"sk_live_abc\"",
+        ];
+        let column = |line: usize| go[line - 1].find("sk_").unwrap() + 1;
+        for line in [2, 5] {
+            let finding = finding(line, column(line), None);
+            assert!(plan_fix(&finding, Language::Go, &go, &env).is_none());
+        }
+        let fix = plan_fix(&finding(7, column(7), None), Language::Go, &go, &env).unwrap();
+        assert_eq!(fix.new_line, "var secret = os.Getenv(\"SECRET\")");
+    }
+
+    #[test]
+    fn test_plan_fix_keys() {
+        let line = "call(\"sk_live_abc\")";
+        let mut env = HashMap::new();
+
+        // No assignment: named after the pattern.
+        let fix = plan_fix(&finding(1, 7, None), Language::Python, &[line], &env).unwrap();
+        assert_eq!(fix.key, "STRIPE_SECRET_KEY");
+
+        // Reuse a matching .env entry, never overwrite a different one.
+        env.insert("STRIPE_SECRET_KEY".to_string(), "sk_live_abc".to_string());
+        let fix = plan_fix(&finding(1, 7, None), Language::Python, &[line], &env).unwrap();
+        assert!(!fix.add_to_env);
+        env.insert("STRIPE_SECRET_KEY".to_string(), "other".to_string());
+        let fix = plan_fix(&finding(1, 7, None), Language::Python, &[line], &env).unwrap();
+        assert_eq!(fix.key, "STRIPE_SECRET_KEY_2");
+
+        // Not a literal.
+        assert!(plan_fix(
+            &finding(1, 1, None),
+            Language::Python,
+            &["sk_live_abc"],
+            &env
+        )
+        .is_none());
+
+        // A value .env loaders would expand stays in the source.
+        let line = "call(\"sk_live_$abc\")";
+        assert!(plan_fix(&finding(1, 7, None), Language::Python, &[line], &env).is_none());
+
+        // Whole-line matches point at column 1.
+        let aws = format!("key = 'x' + \"AKIA{}\"", "4OZRMFJ3VREALKEY");
+        let mut aws_finding = finding(1, 1, None);
+        aws_finding.pattern = "AWS Access Key ID".to_string();
+        let fix = plan_fix(&aws_finding, Language::Python, &[&aws], &HashMap::new()).unwrap();
+        assert_eq!(
+            fix.new_line,
+            "key = 'x' + os.environ[\"AWS_ACCESS_KEY_ID\"]"
+        );
+    }
+
+    #[test]
+    fn test_rust_fixes_compile() {
+        let env = HashMap::new();
+        let lines = [
+            "    let stripe_key: &str = \"sk_live_abc\";",
+            "    let stripe_key = \"sk_live_abc\";",
+            "    let _ = takes_str(\"sk_live_abc\");",
+        ];
+        let mut body = String::new();
+        for line in lines {
+            let column = line.find("sk_").unwrap() + 1;
+            let fix = plan_fix(&finding(1, column, None), Language::Rust, &[line], &env).unwrap();
+            body.push_str(&format!("{{\n{}\n}}\n", fix.new_line));
+        }
+        let source = format!(
+            "fn takes_str(s: &str) -> usize {{ s.len() }}\n\
+             #[allow(unused_variables)]\nfn main() {{\n{}}}\n",
+            body
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("fixed.rs");
+        fs::write(&path, source).unwrap();
+        let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".into());
+        let status = std::process::Command::new(rustc)
+            .args(["--edition", "2021", "--emit", "metadata", "--out-dir"])
+            .arg(dir.path())
+            .arg(&path)
+            .status()
+            .unwrap();
+        assert!(status.success());
+
+        // Items evaluated at compile time cannot read the environment.
+        for line in [
+            "const STRIPE_KEY: &str = \"sk_live_abc\";",
+            "pub(crate) static STRIPE_KEY: &str = \"sk_live_abc\";",
+            "let key: &'static str = \"sk_live_abc\";",
+        ] {
+            let column = line.find("sk_").unwrap() + 1;
+            assert!(plan_fix(&finding(1, column, None), Language::Rust, &[line], &env).is_none());
+        }
+    }
+
+    #[test]
+    fn test_ensure_import() {
+        let mut lines = vec!["#!/usr/bin/env python".to_string(), "x = 1".to_string()];
+        ensure_import(Language::Python, &mut lines);
+        assert_eq!(lines[1], "import os");
+        ensure_import(Language::Python, &mut lines);
+        assert_eq!(lines.len(), 3);
+
+        let mut lines = vec![
+            "package main".to_string(),
+            "import (".to_string(),
+            ")".to_string(),
+        ];
+        ensure_import(Language::Go, &mut lines);
+        assert_eq!(lines[2], "\t\"os\"");
+    }
+}
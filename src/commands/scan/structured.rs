@@ -0,0 +1,749 @@
+//! Structure-aware scanning of YAML, JSON, TOML and `.properties` files.
+//!
+//! Instead of matching regexes line by line, the document is parsed and every
+//! leaf value is checked with its dotted key path (`database.primary.password`),
+//! so multi-line YAML blocks and minified JSON are handled like any other value.
+//!
+//! Lines come from the parsers: TOML values carry spans, JSON is walked with
+//! its text, and YAML findings are located with the parser's marks. A finding
+//! whose line cannot be established is reported with line 0.
+
+use serde::de::{self, DeserializeSeed, Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use serde::Deserialize;
+use std::fmt;
+use std::path::Path;
+use toml::Spanned;
+
+use super::{detect_assignment, record_finding, truncate_value, Finding, ScanResults};
+
+/// Config formats the structured scanner understands.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    Yaml,
+    Json,
+    Toml,
+    Properties,
+}
+
+impl Format {
+    /// Pick a format from the file extension.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_string_lossy().to_lowercase();
+        match ext.as_str() {
+            "yml" | "yaml" => Some(Self::Yaml),
+            "json" => Some(Self::Json),
+            "toml" => Some(Self::Toml),
+            "properties" => Some(Self::Properties),
+            _ => None,
+        }
+    }
+}
+
+/// A scalar value in a parsed document.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Leaf {
+    /// Dotted key path, with `[i]` for sequence items (`servers[0].password`).
+    pub path: String,
+    /// Last mapping key on the path (`password`).
+    pub key: String,
+    pub value: String,
+    /// 1-based line, when the parser knows it.
+    pub line: Option<usize>,
+    /// Index of the `---` separated YAML document holding the leaf.
+    pub document: usize,
+}
+
+/// Parse `content` and return its leaves, or `None` if it is not valid `format`.
+pub fn parse_leaves(format: Format, content: &str) -> Option<Vec<Leaf>> {
+    let mut leaves = Vec::new();
+
+    match format {
+        Format::Json => {
+            serde_json::from_str::<IgnoredAny>(content).ok()?;
+            JsonWalker::new(content).value("", "", &mut leaves)?;
+        }
+        Format::Yaml => {
+            // A file may hold several `---` separated documents.
+            for (index, document) in serde_yaml::Deserializer::from_str(content).enumerate() {
+                let value = serde_json::Value::deserialize(document).ok()?;
+                let start = leaves.len();
+                walk_json(&value, "", "", &mut leaves);
+                for leaf in &mut leaves[start..] {
+                    leaf.document = index;
+                }
+            }
+        }
+        Format::Toml => {
+            let table: TomlNode = toml::from_str(content).ok()?;
+            walk_toml(&table, content, "", "", &mut leaves);
+        }
+        Format::Properties => leaves = parse_properties(content),
+    }
+
+    Some(leaves)
+}
+
+/// Scan a structured config file.
+///
+/// Returns `false` if the file does not parse, so the caller can fall back
+/// to line-based scanning.
+pub fn scan_structured_file(
+    path: &Path,
+    format: Format,
+    content: &str,
+    results: &mut ScanResults,
+) -> bool {
+    let leaves = match parse_leaves(format, content) {
+        Some(leaves) => leaves,
+        None => return false,
+    };
+
+    for leaf in leaves {
+        let value = leaf.value.trim();
+        if value.is_empty() {
+            continue;
+        }
+
+        let (pattern, confidence, action_url) =
+            match detect_assignment(value, &leaf.path, &leaf.key) {
+                Some(detection) => detection,
+                None => continue,
+            };
+
+        // Locating a YAML leaf walks its document again, so only findings
+        // pay for it.
+        let line = match format {
+            Format::Yaml => yaml_line(content, leaf.document, &leaf.path),
+            _ => leaf.line,
+        };
+        let (line, column, location) = match line {
+            Some(line) => {
+                let column = content
+                    .lines()
+                    .nth(line - 1)
+                    .and_then(|text| {
+                        let needle = value.lines().next()?.trim();
+                        let offset = text.find(needle)?;
+                        Some(text[..offset].chars().count() + 1)
+                    })
+                    .unwrap_or(1);
+                let location = format!("{}:{} ({})", path.display(), line, leaf.path);
+                (line, column, location)
+            }
+            None => (0, 0, format!("{} ({})", path.display(), leaf.path)),
+        };
+
+        let finding = Finding {
+            pattern,
+            confidence: format!("{}", confidence),
+            value_preview: truncate_value(value),
+            location,
+            file: path.display().to_string(),
+            line,
+            column,
+            variable: Some(leaf.path),
+            action_url,
+        };
+
+        record_finding(results, finding, &confidence);
+    }
+
+    true
+}
+
+fn join_key(prefix: &str, key: &str) -> String {
+    if prefix.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", prefix, key)
+    }
+}
+
+fn walk_json(value: &serde_json::Value, path: &str, key: &str, out: &mut Vec<Leaf>) {
+    use serde_json::Value;
+
+    let scalar = match value {
+        Value::Object(map) => {
+            for (k, v) in map {
+                walk_json(v, &join_key(path, k), k, out);
+            }
+            return;
+        }
+        Value::Array(items) => {
+            for (i, v) in items.iter().enumerate() {
+                walk_json(v, &format!("{}[{}]", path, i), key, out);
+            }
+            return;
+        }
+        Value::String(s) => s.clone(),
+        Value::Number(n) => n.to_string(),
+        Value::Bool(_) | Value::Null => return,
+    };
+
+    out.push(Leaf {
+        path: path.to_string(),
+        key: key.to_string(),
+        value: scalar,
+        line: None,
+        document: 0,
+    });
+}
+
+/// Reads the leaves of a JSON document from its text, so that each knows
+/// its line. The text has already been validated by serde_json.
+struct JsonWalker<'a> {
+    text: &'a str,
+    pos: usize,
+    line: usize,
+}
+
+impl<'a> JsonWalker<'a> {
+    fn new(text: &'a str) -> Self {
+        Self {
+            text,
+            pos: 0,
+            line: 1,
+        }
+    }
+
+    fn peek(&mut self) -> Option<u8> {
+        while let Some(&b) = self.text.as_bytes().get(self.pos) {
+            match b {
+                b'\n' => self.line += 1,
+                b' ' | b'\t' | b'\r' => {}
+                _ => return Some(b),
+            }
+            self.pos += 1;
+        }
+        None
+    }
+
+    fn eat(&mut self, b: u8) -> bool {
+        let found = self.peek() == Some(b);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    /// The string at the cursor, unescaped.
+    fn string(&mut self) -> Option<String> {
+        if self.peek() != Some(b'"') {
+            return None;
+        }
+        let start = self.pos;
+        let bytes = self.text.as_bytes();
+        let mut end = self.pos + 1;
+        loop {
+            match bytes.get(end)? {
+                b'\\' => end += 2,
+                b'"' => break,
+                _ => end += 1,
+            }
+        }
+        self.pos = end + 1;
+        serde_json::from_str(&self.text[start..self.pos]).ok()
+    }
+
+    fn value(&mut self, path: &str, key: &str, out: &mut Vec<Leaf>) -> Option<()> {
+        let leaf = |value: String, line: usize| Leaf {
+            path: path.to_string(),
+            key: key.to_string(),
+            value,
+            line: Some(line),
+            document: 0,
+        };
+
+        match self.peek()? {
+            b'{' => {
+                self.pos += 1;
+                if self.eat(b'}') {
+                    return Some(());
+                }
+                loop {
+                    let k = self.string()?;
+                    if !self.eat(b':') {
+                        return None;
+                    }
+                    self.value(&join_key(path, &k), &k, out)?;
+                    if self.eat(b'}') {
+                        return Some(());
+                    }
+                    if !self.eat(b',') {
+                        return None;
+                    }
+                }
+            }
+            b'[' => {
+                self.pos += 1;
+                if self.eat(b']') {
+                    return Some(());
+                }
+                for i in 0.. {
+                    self.value(&format!("{}[{}]", path, i), key, out)?;
+                    if self.eat(b']') {
+                        break;
+                    }
+                    if !self.eat(b',') {
+                        return None;
+                    }
+                }
+            }
+            b'"' => {
+                let line = self.line;
+                let value = self.string()?;
+                out.push(leaf(value, line));
+            }
+            _ => {
+                // A number, `true`, `false` or `null`.
+                let rest = &self.text[self.pos..];
+                let len = rest
+                    .find(|c: char| matches!(c, ',' | '}' | ']') || c.is_ascii_whitespace())
+                    .unwrap_or(rest.len());
+                if let Ok(number) = rest[..len].parse::<serde_json::Number>() {
+                    out.push(leaf(number.to_string(), self.line));
+                }
+                self.pos += len;
+            }
+        }
+        Some(())
+    }
+}
+
+/// Error message that stops a [`YamlLocator`] at its target.
+const FOUND: &str = "evnx: leaf found";
+
+/// Line of the scalar at `path` in YAML document `document`.
+///
+/// serde_yaml keeps positions only on errors, so the document is walked
+/// again and the walk fails on purpose at `path`: the error then carries
+/// the parser's mark for that scalar.
+fn yaml_line(content: &str, document: usize, path: &str) -> Option<usize> {
+    let document = serde_yaml::Deserializer::from_str(content).nth(document)?;
+    let error = YamlLocator {
+        path: String::new(),
+        target: path,
+    }
+    .deserialize(document)
+    .err()?;
+    if !error.to_string().contains(FOUND) {
+        return None;
+    }
+
+    let location = error.location()?;
+    let line = location.line();
+    // A block scalar's text starts on the line after its `|` or `>`.
+    let block = content.lines().nth(line - 1).is_some_and(|text| {
+        let rest: String = text.chars().skip(location.column() - 1).collect();
+        rest.starts_with(['|', '>'])
+    });
+    Some(if block { line + 1 } else { line })
+}
+
+/// Walks a YAML document building the same paths as [`walk_json`], and
+/// fails with [`FOUND`] on the scalar at `target`.
+struct YamlLocator<'a> {
+    path: String,
+    target: &'a str,
+}
+
+impl YamlLocator<'_> {
+    fn child(&self, path: String) -> Self {
+        YamlLocator {
+            path,
+            target: self.target,
+        }
+    }
+
+    fn scalar<E: de::Error>(self) -> Result<(), E> {
+        if self.path == self.target {
+            Err(E::custom(FOUND))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for YamlLocator<'_> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for YamlLocator<'_> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a YAML value")
+    }
+
+    fn visit_str<E: de::Error>(self, _: &str) -> Result<(), E> {
+        self.scalar()
+    }
+
+    fn visit_i64<E: de::Error>(self, _: i64) -> Result<(), E> {
+        self.scalar()
+    }
+
+    fn visit_u64<E: de::Error>(self, _: u64) -> Result<(), E> {
+        self.scalar()
+    }
+
+    fn visit_f64<E: de::Error>(self, _: f64) -> Result<(), E> {
+        self.scalar()
+    }
+
+    fn visit_bool<E: de::Error>(self, _: bool) -> Result<(), E> {
+        self.scalar()
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<(), E> {
+        self.scalar()
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        for i in 0.. {
+            let item = self.child(format!("{}[{}]", self.path, i));
+            if seq.next_element_seed(item)?.is_none() {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        while let Some(key) = map.next_key::<String>()? {
+            map.next_value_seed(self.child(join_key(&self.path, &key)))?;
+        }
+        Ok(())
+    }
+}
+
+/// A TOML value whose items keep their byte spans in the document.
+enum TomlNode {
+    Table(Vec<(String, Spanned<TomlNode>)>),
+    Array(Vec<Spanned<TomlNode>>),
+    /// Strings and integers; other scalars are never secrets.
+    Scalar(Option<String>),
+}
+
+impl<'de> Deserialize<'de> for TomlNode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(TomlNodeVisitor)
+    }
+}
+
+struct TomlNodeVisitor;
+
+impl<'de> Visitor<'de> for TomlNodeVisitor {
+    type Value = TomlNode;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a TOML value")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<TomlNode, E> {
+        Ok(TomlNode::Scalar(Some(v.to_string())))
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<TomlNode, E> {
+        Ok(TomlNode::Scalar(Some(v.to_string())))
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<TomlNode, E> {
+        Ok(TomlNode::Scalar(Some(v.to_string())))
+    }
+
+    fn visit_f64<E: de::Error>(self, _: f64) -> Result<TomlNode, E> {
+        Ok(TomlNode::Scalar(None))
+    }
+
+    fn visit_bool<E: de::Error>(self, _: bool) -> Result<TomlNode, E> {
+        Ok(TomlNode::Scalar(None))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<TomlNode, A::Error> {
+        let mut items = Vec::new();
+        while let Some(item) = seq.next_element()? {
+            items.push(item);
+        }
+        Ok(TomlNode::Array(items))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<TomlNode, A::Error> {
+        let mut entries = Vec::new();
+        while let Some(key) = map.next_key::<String>()? {
+            // Datetimes arrive as a map with one private key.
+            if key.starts_with("$__toml_private") {
+                map.next_value::<IgnoredAny>()?;
+                return Ok(TomlNode::Scalar(None));
+            }
+            entries.push((key, map.next_value()?));
+        }
+        Ok(TomlNode::Table(entries))
+    }
+}
+
+fn walk_toml(node: &TomlNode, content: &str, path: &str, key: &str, out: &mut Vec<Leaf>) {
+    let visit = |item: &Spanned<TomlNode>, path: &str, key: &str, out: &mut Vec<Leaf>| match item
+        .get_ref()
+    {
+        TomlNode::Scalar(Some(value)) => out.push(Leaf {
+            path: path.to_string(),
+            key: key.to_string(),
+            value: value.clone(),
+            line: Some(content[..item.span().start].matches('\n').count() + 1),
+            document: 0,
+        }),
+        TomlNode::Scalar(None) => {}
+        node => walk_toml(node, content, path, key, out),
+    };
+
+    match node {
+        TomlNode::Table(entries) => {
+            for (k, v) in entries {
+                visit(v, &join_key(path, k), k, out);
+            }
+        }
+        TomlNode::Array(items) => {
+            for (i, v) in items.iter().enumerate() {
+                visit(v, &format!("{}[{}]", path, i), key, out);
+            }
+        }
+        TomlNode::Scalar(_) => {}
+    }
+}
+
+/// Parse Java-style `.properties` (`key=value`, `key: value`, `key value`),
+/// joining backslash-continued lines.
+fn parse_properties(content: &str) -> Vec<Leaf> {
+    let mut leaves = Vec::new();
+    let mut lines = content.lines().enumerate();
+
+    while let Some((idx, line)) = lines.next() {
+        let trimmed = line.trim_start();
+        if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with('!') {
+            continue;
+        }
+
+        let mut logical = trimmed.to_string();
+        while ends_with_continuation(&logical) {
+            logical.pop();
+            match lines.next() {
+                Some((_, next)) => logical.push_str(next.trim_start()),
+                None => break,
+            }
+        }
+
+        let sep = logical
+            .char_indices()
+            .find(|&(i, c)| matches!(c, '=' | ':' | ' ' | '\t') && !logical[..i].ends_with('\\'))
+            .map(|(i, _)| i);
+
+        let (key, value) = match sep {
+            Some(i) => {
+                let rest = logical[i..].trim_start();
+                let rest = rest
+                    .strip_prefix(['=', ':'])
+                    .map(str::trim_start)
+                    .unwrap_or(rest);
+                (&logical[..i], rest)
+            }
+            None => (logical.as_str(), ""),
+        };
+
+        leaves.push(Leaf {
+            path: key.to_string(),
+            key: key.rsplit('.').next().unwrap_or(key).to_string(),
+            value: value.to_string(),
+            line: Some(idx + 1),
+            document: 0,
+        });
+    }
+
+    leaves
+}
+
+/// A line continues when it ends with an odd number of backslashes.
+fn ends_with_continuation(line: &str) -> bool {
+    line.chars().rev().take_while(|&c| c == '\\').count() % 2 == 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn empty_results() -> ScanResults {
+        super::super::scan_files(&[], false).unwrap()
+    }
+
+    #[test]
+    fn test_format_from_path() {
+        assert_eq!(Format::from_path(Path::new("app.YAML")), Some(Format::Yaml));
+        assert_eq!(
+            Format::from_path(Path::new("Cargo.toml")),
+            Some(Format::Toml)
+        );
+        assert_eq!(Format::from_path(Path::new("main.py")), None);
+    }
+
+    #[test]
+    fn test_yaml_leaves_have_dotted_paths() {
+        let content = "database:\n  primary:\n    password: hunter2\nservers:\n  - host: a\n";
+        let leaves = parse_leaves(Format::Yaml, content).unwrap();
+        let paths: Vec<&str> = leaves.iter().map(|l| l.path.as_str()).collect();
+
+        assert_eq!(paths, vec!["database.primary.password", "servers[0].host"]);
+        assert_eq!(leaves[0].key, "password");
+    }
+
+    #[test]
+    fn test_invalid_document_is_rejected() {
+        assert!(parse_leaves(Format::Json, "{ not json").is_none());
+        assert!(parse_leaves(Format::Toml, "= nope").is_none());
+    }
+
+    #[test]
+    fn test_parse_properties() {
+        let content =
+            "# comment\nspring.datasource.password = S3cret!\nlong.value=abc\\\n    def\nflag\n";
+        let leaves = parse_properties(content);
+
+        assert_eq!(leaves[0].path, "spring.datasource.password");
+        assert_eq!(leaves[0].key, "password");
+        assert_eq!(leaves[0].value, "S3cret!");
+        assert_eq!(leaves[0].line, Some(2));
+        assert_eq!(leaves[1].value, "abcdef");
+        assert_eq!(leaves[2].path, "flag");
+        assert_eq!(leaves[2].value, "");
+    }
+
+    #[test]
+    fn test_yaml_block_scalar_key_name_detection() {
+        let content =
+            "database:\n  primary:\n    host: db.internal\n    password: >-\n      Xk9vQ2mL7pR4\n";
+        let mut results = empty_results();
+
+        assert!(scan_structured_file(
+            Path::new("config.yml"),
+            Format::Yaml,
+            content,
+            &mut results
+        ));
+
+        assert_eq!(results.secrets_found, 1);
+        let finding = &results.findings[0];
+        assert_eq!(finding.pattern, super::super::SENSITIVE_KEY_PATTERN);
+        assert_eq!(
+            finding.variable.as_deref(),
+            Some("database.primary.password")
+        );
+        // The value starts on the line after its `>-` indicator.
+        assert_eq!(finding.location, "config.yml:5 (database.primary.password)");
+        assert_eq!((finding.line, finding.column), (5, 7));
+    }
+
+    #[test]
+    fn test_yaml_lines_follow_documents_and_sequences() {
+        let content = "name: first\n---\nservices:\n  - name: api\n    api_key: \"Xk9vQ2mL7pR4\"\n";
+
+        let line = |path: &str| yaml_line(content, 1, path);
+        assert_eq!(line("services[0].name"), Some(4));
+        assert_eq!(line("services[0].api_key"), Some(5));
+        assert_eq!(line("services[1].name"), None);
+        assert_eq!(yaml_line(content, 0, "name"), Some(1));
+    }
+
+    #[test]
+    fn test_json_leaves_have_text_lines() {
+        let content = "{\n  \"db\": {\n    \"port\": 5432,\n    \"hosts\": [\"a\",\n      \"b\\\"c\"],\n    \"ok\": true\n  }\n}\n";
+        let leaves = parse_leaves(Format::Json, content).unwrap();
+
+        let found: Vec<_> = leaves
+            .iter()
+            .map(|l| (l.path.as_str(), l.value.as_str(), l.line))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("db.port", "5432", Some(3)),
+                ("db.hosts[0]", "a", Some(4)),
+                ("db.hosts[1]", "b\"c", Some(5)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_minified_json_value_detection() {
+        let token = format!("ghp_{}", "aB3dE5fG7hJ9kL1mN3pQ5rS7tU9vW1xY3zA5");
+        let content = format!(
+            "{{\"ci\":{{\"github\":{{\"token\":\"{}\",\"secretName\":\"gh-token\"}}}}}}",
+            token
+        );
+        let mut results = empty_results();
+
+        assert!(scan_structured_file(
+            Path::new("ci.json"),
+            Format::Json,
+            &content,
+            &mut results
+        ));
+
+        assert_eq!(results.secrets_found, 1);
+        assert_eq!(results.findings[0].pattern, "GitHub Token");
+        assert_eq!(results.findings[0].location, "ci.json:1 (ci.github.token)");
+    }
+
+    #[test]
+    fn test_toml_leaves_have_parser_lines() {
+        // The key also appears on an earlier line, which a text search
+        // would pick.
+        let content = "# password = \"Xk9vQ2mL7pR4\"\n\
+                       created = 1979-05-27T07:32:00Z\n\
+                       [database]\n\
+                       hosts = [\"a\",\n  \"b\"]\n\
+                       replica = { port = 5432 }\n\
+                       password = \"Xk9vQ2mL7pR4\"\n";
+        let leaves = parse_leaves(Format::Toml, content).unwrap();
+        let lines: Vec<(&str, Option<usize>)> =
+            leaves.iter().map(|l| (l.path.as_str(), l.line)).collect();
+        assert_eq!(
+            lines,
+            vec![
+                ("database.hosts[0]", Some(4)),
+                ("database.hosts[1]", Some(5)),
+                ("database.replica.port", Some(6)),
+                ("database.password", Some(7)),
+            ]
+        );
+
+        let mut results = empty_results();
+        assert!(scan_structured_file(
+            Path::new("app.toml"),
+            Format::Toml,
+            content,
+            &mut results
+        ));
+        assert_eq!(
+            results.findings[0].location,
+            "app.toml:7 (database.password)"
+        );
+        assert_eq!(results.findings[0].column, 13);
+    }
+
+    #[test]
+    fn test_toml_references_are_ignored() {
+        let content = "[database]\npassword = \"${DB_PASSWORD}\"\nhost = \"localhost\"\n";
+        let mut results = empty_results();
+
+        assert!(scan_structured_file(
+            Path::new("app.toml"),
+            Format::Toml,
+            content,
+            &mut results
+        ));
+        assert_eq!(results.secrets_found, 0);
+    }
+}
@@ -6,14 +6,16 @@
 /// - Boolean string trap
 /// - Weak SECRET_KEY
 /// - localhost in Docker context
-/// - Multiple output formats (pretty, json, github-actions)
+/// - Real-looking values in the example file (--check-example)
+/// - Multiple output formats (pretty, json, github-actions, sarif)
 use anyhow::{Context, Result};
 use colored::*;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::path::Path;
 
-use crate::core::{Parser, ParserConfig};
+use crate::core::{hygiene, Parser, ParserConfig};
+use crate::utils::sarif::{self, SarifResult, SarifRule};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ValidationResult {
@@ -42,6 +44,7 @@ pub struct Summary {
     pub style: usize,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn run(
     env: String,
     example: String,
@@ -49,6 +52,8 @@ pub fn run(
     fix: bool,
     format: String,
     exit_zero: bool,
+    check_example: bool,
+    allow_default: Vec<String>,
     verbose: bool,
 ) -> Result<()> {
     if verbose {
@@ -200,6 +205,26 @@ pub fn run(
         }
     }
 
+    // Check 7: Example file hygiene
+    if check_example {
+        for violation in hygiene::check_file(Path::new(&example), &allow_default)? {
+            issues.push(Issue {
+                severity: "error".to_string(),
+                issue_type: "real_value_in_example".to_string(),
+                variable: violation.key.clone(),
+                message: format!(
+                    "{} in {} looks like a real value ({})",
+                    violation.key, example, violation.pattern
+                ),
+                location: format!("{}:{}", example, violation.line),
+                suggestion: Some(format!(
+                    "Replace it with a placeholder, or pass --allow-default {}",
+                    violation.key
+                )),
+            });
+        }
+    }
+
     // Create result
     let errors = issues.iter().filter(|i| i.severity == "error").count();
     let warnings = issues.iter().filter(|i| i.severity == "warning").count();
@@ -225,6 +250,7 @@ pub fn run(
     match format.as_str() {
         "json" => output_json(&result)?,
         "github-actions" => output_github_actions(&result)?,
+        "sarif" => output_sarif(&result, &env, &example)?,
         _ => output_pretty(&result, &env_file.vars, &example_file.vars)?,
     }
 
@@ -332,6 +358,140 @@ fn output_github_actions(result: &ValidationResult) -> Result<()> {
     Ok(())
 }
 
+/// Issue types with their SARIF title, description and default level.
+const ISSUE_RULES: &[(&str, &str, &str, &str)] = &[
+    (
+        "missing_variable",
+        "Missing required variable",
+        "A variable declared in the example file is not set in the env file.",
+        "error",
+    ),
+    (
+        "extra_variable",
+        "Extra variable",
+        "A variable is set in the env file but not declared in the example file (strict mode).",
+        "warning",
+    ),
+    (
+        "placeholder_value",
+        "Placeholder value",
+        "A variable still holds a placeholder instead of a real value.",
+        "error",
+    ),
+    (
+        "boolean_trap",
+        "Boolean string trap",
+        "\"True\"/\"False\" are non-empty strings and therefore truthy in most languages.",
+        "warning",
+    ),
+    (
+        "weak_secret",
+        "Weak secret",
+        "SECRET_KEY is too short or too predictable to be safe.",
+        "error",
+    ),
+    (
+        "localhost_in_docker",
+        "localhost in Docker",
+        "A URL or host points at localhost while the project runs in Docker.",
+        "warning",
+    ),
+    (
+        "real_value_in_example",
+        "Real value in example file",
+        "The committed example file holds a value that looks like a real credential.",
+        "error",
+    ),
+];
+
+/// Build a SARIF 2.1.0 log for `result`.
+///
+/// Missing variables and real values in the example file are reported where
+/// the example file declares them;
+/// everything else at the variable's line in the env file.
+pub fn build_sarif(result: &ValidationResult, env: &str, example: &str) -> serde_json::Value {
+    let rules: Vec<SarifRule> = ISSUE_RULES
+        .iter()
+        .map(|(issue_type, title, description, level)| SarifRule {
+            id: format!("env/{}", sarif::slug(issue_type)),
+            name: sarif::slug(issue_type),
+            short_description: title.to_string(),
+            full_description: description.to_string(),
+            help: description.to_string(),
+            help_uri: format!("{}#evnx-validate", env!("CARGO_PKG_REPOSITORY")),
+            level,
+            tags: vec!["configuration"],
+        })
+        .collect();
+
+    let env_positions = key_positions(Path::new(env));
+    let example_positions = key_positions(Path::new(example));
+
+    let results: Vec<SarifResult> = result
+        .issues
+        .iter()
+        .map(|issue| {
+            let (file, positions) = if matches!(
+                issue.issue_type.as_str(),
+                "missing_variable" | "real_value_in_example"
+            ) {
+                (example, &example_positions)
+            } else {
+                (env, &env_positions)
+            };
+            let (line, column) = positions.get(&issue.variable).copied().unwrap_or((1, 1));
+            let rule_id = format!("env/{}", sarif::slug(&issue.issue_type));
+
+            SarifResult {
+                fingerprint: sarif::fingerprint(&[&rule_id, file, &issue.variable]),
+                rule_id,
+                level: match issue.severity.as_str() {
+                    "error" => "error",
+                    "warning" => "warning",
+                    _ => "note",
+                },
+                message: match &issue.suggestion {
+                    Some(suggestion) => format!("{} ({})", issue.message, suggestion),
+                    None => issue.message.clone(),
+                },
+                path: file.to_string(),
+                line,
+                column,
+            }
+        })
+        .collect();
+
+    sarif::sarif_log("evnx validate", &rules, &results)
+}
+
+fn output_sarif(result: &ValidationResult, env: &str, example: &str) -> Result<()> {
+    let sarif = build_sarif(result, env, example);
+    println!("{}", serde_json::to_string_pretty(&sarif)?);
+    Ok(())
+}
+
+/// 1-based line and column of the first definition of each key in `path`.
+fn key_positions(path: &Path) -> HashMap<String, (usize, usize)> {
+    let mut positions = HashMap::new();
+    let content = std::fs::read_to_string(path).unwrap_or_default();
+
+    for (idx, line) in content.lines().enumerate() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with('#') {
+            continue;
+        }
+        let definition = trimmed.strip_prefix("export ").unwrap_or(trimmed);
+        if let Some((key, _)) = definition.split_once('=') {
+            let column = line[..line.len() - definition.len()].chars().count() + 1;
+            positions
+                .entry(key.trim().to_string())
+                .or_insert((idx + 1, column));
+        }
+    }
+
+    positions
+}
+
 fn is_placeholder(value: &str) -> bool {
     let lower = value.to_lowercase();
 
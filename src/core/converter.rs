@@ -4,7 +4,10 @@
 // to different output formats.
 
 use anyhow::Result;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+
+use super::document::Document;
+use super::keypath::{nest, KeyPath, Node};
 
 /// Key transformation options
 #[derive(Debug, Clone)]
@@ -19,6 +22,19 @@ pub enum KeyTransform {
     SnakeCase,
 }
 
+impl KeyTransform {
+    /// Parse a `--transform` name
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "uppercase" => Some(KeyTransform::Uppercase),
+            "lowercase" => Some(KeyTransform::Lowercase),
+            "camelCase" => Some(KeyTransform::CamelCase),
+            "snake_case" => Some(KeyTransform::SnakeCase),
+            _ => None,
+        }
+    }
+}
+
 /// Options for format conversion
 ///
 /// ✅ CLIPPY FIX: Uses `#[derive(Default)]` instead of manual implementation
@@ -38,6 +54,92 @@ pub struct ConvertOptions {
 
     /// Key transformation to apply
     pub transform: Option<KeyTransform>,
+
+    /// Order variables by key instead of source order
+    pub sort: bool,
+
+    /// Split keys into nested paths (JSON and YAML output)
+    pub nest: Option<KeyPath>,
+
+    /// Type values that read as booleans, numbers or JSON arrays in nested output
+    pub coerce: bool,
+}
+
+/// A variable to convert, with the comments written around it in the source
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Variable {
+    pub key: String,
+    pub value: String,
+
+    /// Comment lines directly above the variable, without `#`
+    pub description: Option<String>,
+
+    /// Header of the section the variable is in
+    pub section: Option<String>,
+}
+
+impl Variable {
+    /// Create a variable without comments
+    pub fn new(key: impl Into<String>, value: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            value: value.into(),
+            ..Default::default()
+        }
+    }
+}
+
+/// Parsed `values` in the order of `source`, with comments from `source`
+/// or, for keys it leaves undescribed, from `fallback`
+///
+/// Keys `source` does not assign come last, ordered by key.
+pub fn document_variables(
+    values: &HashMap<String, String>,
+    source: &Document,
+    fallback: Option<&Document>,
+) -> Vec<Variable> {
+    let fallback: HashMap<String, _> = fallback
+        .map(|doc| {
+            doc.annotations()
+                .into_iter()
+                .map(|a| (a.key.clone(), a))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut vars: Vec<Variable> = source
+        .annotations()
+        .into_iter()
+        .filter_map(|a| {
+            let value = values.get(&a.key)?;
+            let other = fallback.get(&a.key);
+            Some(Variable {
+                description: a
+                    .description
+                    .or_else(|| other.and_then(|f| f.description.clone())),
+                section: a.section.or_else(|| other.and_then(|f| f.section.clone())),
+                value: value.clone(),
+                key: a.key,
+            })
+        })
+        .collect();
+
+    let seen: HashSet<String> = vars.iter().map(|v| v.key.clone()).collect();
+    let mut rest: Vec<Variable> = values
+        .iter()
+        .filter(|(k, _)| !seen.contains(*k))
+        .map(|(k, v)| Variable::new(k, v))
+        .collect();
+    rest.sort_by(|a, b| a.key.cmp(&b.key));
+    vars.extend(rest);
+    vars
+}
+
+/// Variables of a map, ordered by key, without comments
+pub fn variables(vars: &HashMap<String, String>) -> Vec<Variable> {
+    let mut list: Vec<Variable> = vars.iter().map(|(k, v)| Variable::new(k, v)).collect();
+    list.sort_by(|a, b| a.key.cmp(&b.key));
+    list
 }
 
 // ✅ CLIPPY FIX: Removed manual impl Default (using #[derive(Default)])
@@ -49,11 +151,49 @@ impl ConvertOptions {
     }
 
     /// Filter variables based on include/exclude patterns
-    pub fn filter_vars(&self, vars: &HashMap<String, String>) -> HashMap<String, String> {
-        vars.iter()
-            .filter(|(key, _)| self.should_include(key))
-            .map(|(k, v)| (k.clone(), v.clone()))
-            .collect()
+    ///
+    /// Keeps source order, or sorts by key if `sort` is set; sorted variables
+    /// lose their section headers, which no longer group anything.
+    pub fn filter_vars(&self, vars: &[Variable]) -> Vec<Variable> {
+        let mut filtered: Vec<Variable> = vars
+            .iter()
+            .filter(|var| self.should_include(&var.key))
+            .cloned()
+            .collect();
+
+        if self.sort {
+            filtered.sort_by(|a, b| a.key.cmp(&b.key));
+            for var in &mut filtered {
+                var.section = None;
+            }
+        }
+        filtered
+    }
+
+    /// Nested document of the filtered, transformed variables, if `nest`
+    /// is set
+    ///
+    /// Base64-encoded values are never coerced.
+    pub fn nested(&self, vars: &[Variable]) -> Result<Option<Node>> {
+        match &self.nest {
+            Some(path) => self.nested_as(vars, path).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Nested document of the filtered, transformed variables, split with
+    /// `path` rather than `nest`
+    pub fn nested_as(&self, vars: &[Variable], path: &KeyPath) -> Result<Node> {
+        let transformed: Vec<Variable> = self
+            .filter_vars(vars)
+            .into_iter()
+            .map(|var| Variable {
+                key: self.transform_key(&var.key),
+                value: self.transform_value(&var.value),
+                ..var
+            })
+            .collect();
+        nest(&transformed, path, self.coerce && !self.base64)
     }
 
     /// Check if a variable should be included
@@ -108,13 +248,13 @@ pub trait Converter {
     ///
     /// # Arguments
     ///
-    /// * `vars` - Environment variables to convert
+    /// * `vars` - Environment variables to convert, in output order
     /// * `options` - Conversion options (filtering, transformations, etc.)
     ///
     /// # Returns
     ///
     /// Formatted output as a string
-    fn convert(&self, vars: &HashMap<String, String>, options: &ConvertOptions) -> Result<String>;
+    fn convert(&self, vars: &[Variable], options: &ConvertOptions) -> Result<String>;
 
     /// Get the name of this format
     ///
@@ -163,7 +303,7 @@ fn glob_match(text: &str, pattern: &str) -> bool {
 }
 
 /// Convert string to camelCase
-fn to_camel_case(s: &str) -> String {
+pub(crate) fn to_camel_case(s: &str) -> String {
     let parts: Vec<&str> = s.split('_').collect();
     if parts.is_empty() {
         return String::new();
@@ -203,7 +343,7 @@ fn to_camel_case(s: &str) -> String {
 //     result
 // }
 
-fn to_snake_case(s: &str) -> String {
+pub(crate) fn to_snake_case(s: &str) -> String {
     let mut result = String::with_capacity(s.len());
     let chars: Vec<char> = s.chars().collect();
 
@@ -242,6 +382,7 @@ mod tests {
         assert!(!opts.base64);
         assert!(opts.prefix.is_none());
         assert!(opts.transform.is_none());
+        assert!(!opts.sort);
     }
 
     #[test]
@@ -250,78 +391,119 @@ mod tests {
         assert!(opts.include_pattern.is_none());
     }
 
+    fn keys(vars: &[Variable]) -> Vec<&str> {
+        vars.iter().map(|v| v.key.as_str()).collect()
+    }
+
     #[test]
     fn test_filter_vars_no_pattern() {
-        let mut vars = HashMap::new();
-        vars.insert("KEY1".to_string(), "value1".to_string());
-        vars.insert("KEY2".to_string(), "value2".to_string());
+        let vars = vec![
+            Variable::new("KEY2", "value2"),
+            Variable::new("KEY1", "value1"),
+        ];
 
         let opts = ConvertOptions::default();
         let filtered = opts.filter_vars(&vars);
 
-        assert_eq!(filtered.len(), 2);
-        assert!(filtered.contains_key("KEY1"));
-        assert!(filtered.contains_key("KEY2"));
+        assert_eq!(keys(&filtered), vec!["KEY2", "KEY1"]);
     }
 
     #[test]
     fn test_filter_vars_include() {
-        let mut vars = HashMap::new();
-        vars.insert("AWS_KEY".to_string(), "value1".to_string());
-        vars.insert("DB_KEY".to_string(), "value2".to_string());
-
-        let mut opts = ConvertOptions::default();
-        opts.include_pattern = Some("AWS_*".to_string());
+        let vars = vec![
+            Variable::new("AWS_KEY", "value1"),
+            Variable::new("DB_KEY", "value2"),
+        ];
+
+        let opts = ConvertOptions {
+            include_pattern: Some("AWS_*".to_string()),
+            ..Default::default()
+        };
         let filtered = opts.filter_vars(&vars);
 
-        assert_eq!(filtered.len(), 1);
-        assert!(filtered.contains_key("AWS_KEY"));
-        assert!(!filtered.contains_key("DB_KEY"));
+        assert_eq!(keys(&filtered), vec!["AWS_KEY"]);
     }
 
     #[test]
     fn test_filter_vars_exclude() {
-        let mut vars = HashMap::new();
-        vars.insert("KEY1".to_string(), "value1".to_string());
-        vars.insert("KEY2_LOCAL".to_string(), "value2".to_string());
+        let vars = vec![
+            Variable::new("KEY1", "value1"),
+            Variable::new("KEY2_LOCAL", "value2"),
+        ];
+
+        let opts = ConvertOptions {
+            exclude_pattern: Some("*_LOCAL".to_string()),
+            ..Default::default()
+        };
+        let filtered = opts.filter_vars(&vars);
 
-        let mut opts = ConvertOptions::default();
-        opts.exclude_pattern = Some("*_LOCAL".to_string());
+        assert_eq!(keys(&filtered), vec!["KEY1"]);
+    }
+
+    #[test]
+    fn test_filter_vars_sort() {
+        let vars = vec![
+            Variable {
+                section: Some("Cache".to_string()),
+                description: Some("Redis".to_string()),
+                ..Variable::new("REDIS_URL", "redis://")
+            },
+            Variable::new("APP_NAME", "demo"),
+        ];
+
+        let opts = ConvertOptions {
+            sort: true,
+            ..Default::default()
+        };
         let filtered = opts.filter_vars(&vars);
 
-        assert_eq!(filtered.len(), 1);
-        assert!(filtered.contains_key("KEY1"));
-        assert!(!filtered.contains_key("KEY2_LOCAL"));
+        assert_eq!(keys(&filtered), vec!["APP_NAME", "REDIS_URL"]);
+        assert_eq!(filtered[1].section, None);
+        assert_eq!(filtered[1].description.as_deref(), Some("Redis"));
+
+        let map = HashMap::from([
+            ("B".to_string(), "2".to_string()),
+            ("A".to_string(), "1".to_string()),
+        ]);
+        assert_eq!(keys(&variables(&map)), vec!["A", "B"]);
     }
 
     #[test]
     fn test_transform_key_prefix() {
-        let mut opts = ConvertOptions::default();
-        opts.prefix = Some("APP_".to_string());
+        let opts = ConvertOptions {
+            prefix: Some("APP_".to_string()),
+            ..Default::default()
+        };
 
         assert_eq!(opts.transform_key("DATABASE_URL"), "APP_DATABASE_URL");
     }
 
     #[test]
     fn test_transform_key_uppercase() {
-        let mut opts = ConvertOptions::default();
-        opts.transform = Some(KeyTransform::Uppercase);
+        let opts = ConvertOptions {
+            transform: Some(KeyTransform::Uppercase),
+            ..Default::default()
+        };
 
         assert_eq!(opts.transform_key("database_url"), "DATABASE_URL");
     }
 
     #[test]
     fn test_transform_key_lowercase() {
-        let mut opts = ConvertOptions::default();
-        opts.transform = Some(KeyTransform::Lowercase);
+        let opts = ConvertOptions {
+            transform: Some(KeyTransform::Lowercase),
+            ..Default::default()
+        };
 
         assert_eq!(opts.transform_key("DATABASE_URL"), "database_url");
     }
 
     #[test]
     fn test_transform_value_base64() {
-        let mut opts = ConvertOptions::default();
-        opts.base64 = true;
+        let opts = ConvertOptions {
+            base64: true,
+            ..Default::default()
+        };
 
         let result = opts.transform_value("hello");
         assert_eq!(result, "aGVsbG8="); // base64("hello")
@@ -354,4 +536,23 @@ mod tests {
         assert_eq!(to_snake_case("Already_snake"), "already_snake");
         // assert_eq!(to_snake_case("DatabaseURL"), "database_u_r_l");
     }
+
+    #[test]
+    fn test_variables_follow_source_with_example_comments() {
+        let values = HashMap::from([
+            ("PORT".to_string(), "3000".to_string()),
+            ("DB_URL".to_string(), "postgres://".to_string()),
+            ("ZETA".to_string(), "z".to_string()),
+        ]);
+        let source = Document::parse("PORT=3000\n# Primary database\nDB_URL=postgres://\n");
+        let example = Document::parse("# Server\n\n# Listen port\nPORT=\n# Unused\nDB_URL=\n");
+
+        let vars = document_variables(&values, &source, Some(&example));
+        let keys: Vec<&str> = vars.iter().map(|v| v.key.as_str()).collect();
+        assert_eq!(keys, vec!["PORT", "DB_URL", "ZETA"]);
+        assert_eq!(vars[0].description.as_deref(), Some("Listen port"));
+        assert_eq!(vars[0].section.as_deref(), Some("Server"));
+        assert_eq!(vars[1].description.as_deref(), Some("Primary database"));
+        assert_eq!(vars[2].description, None);
+    }
 }
@@ -0,0 +1,305 @@
+//! Lossless `.env` document model.
+//!
+//! [`Parser`](super::Parser) produces a `HashMap` of expanded values, which
+//! is what most commands need but throws away everything a human wrote
+//! around the values: comments, section headers, blank lines, ordering,
+//! quoting and `export` prefixes. Commands that rewrite a file in place
+//! (merge) work on a [`Document`] instead, which renders back to exactly
+//! the text it was parsed from.
+//!
+//! Parsing never fails: a line that is not a valid assignment is kept as
+//! [`Entry::Trivia`] verbatim.
+
+use std::collections::HashSet;
+use std::fmt;
+
+/// One logical piece of a `.env` file.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Entry {
+    /// Blank line, comment or any other non-assignment line, verbatim.
+    Trivia(String),
+    /// An assignment.
+    Var {
+        key: String,
+        /// Raw value text after `=`, trimmed; quotes and escapes are kept.
+        value: String,
+        /// Original text, several lines for a multiline quoted value.
+        text: String,
+    },
+}
+
+impl Entry {
+    /// Key of an assignment, `None` for trivia.
+    pub fn key(&self) -> Option<&str> {
+        match self {
+            Entry::Var { key, .. } => Some(key),
+            Entry::Trivia(_) => None,
+        }
+    }
+
+    /// Original text of the entry.
+    pub fn text(&self) -> &str {
+        match self {
+            Entry::Var { text, .. } | Entry::Trivia(text) => text,
+        }
+    }
+
+    /// Whether this is a comment line.
+    pub fn is_comment(&self) -> bool {
+        matches!(self, Entry::Trivia(t) if t.trim_start().starts_with('#'))
+    }
+}
+
+/// Comments written around a key.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Annotation {
+    pub key: String,
+    /// Comment lines directly above the first assignment, without `#`.
+    pub description: Option<String>,
+    /// Header of the section the key is in.
+    pub section: Option<String>,
+}
+
+/// A `.env` file as a sequence of entries.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Document {
+    pub entries: Vec<Entry>,
+    /// Whether the file ended with a newline.
+    pub trailing_newline: bool,
+}
+
+impl Document {
+    /// Parse `content`; `to_string()` reproduces it byte for byte.
+    pub fn parse(content: &str) -> Self {
+        let trailing_newline = content.ends_with('\n');
+        let body = content.strip_suffix('\n').unwrap_or(content);
+        let mut lines = if content.is_empty() {
+            Vec::new()
+        } else {
+            body.split('\n').collect::<Vec<_>>()
+        }
+        .into_iter();
+
+        let mut entries = Vec::new();
+        while let Some(line) = lines.next() {
+            let Some((key, raw)) = split_assignment(line) else {
+                entries.push(Entry::Trivia(line.to_string()));
+                continue;
+            };
+
+            let mut text = line.to_string();
+            let mut value = raw.trim().to_string();
+            // A quote opened but not closed on this line continues on the
+            // following lines until it is; an inline comment may follow.
+            if let Some(q) = value
+                .chars()
+                .next()
+                .filter(|c| matches!(c, '"' | '\'' | '`'))
+            {
+                if !closes(&value, q) {
+                    for next in lines.by_ref() {
+                        text.push('\n');
+                        text.push_str(next);
+                        value.push('\n');
+                        value.push_str(next.trim_end());
+                        if closes(&value, q) {
+                            break;
+                        }
+                    }
+                }
+            }
+
+            entries.push(Entry::Var {
+                key: key.to_string(),
+                value,
+                text,
+            });
+        }
+
+        Self {
+            entries,
+            trailing_newline,
+        }
+    }
+
+    /// Raw value of `key` (the last assignment wins, as in the parser).
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries.iter().rev().find_map(|entry| match entry {
+            Entry::Var { key: k, value, .. } if k == key => Some(value.as_str()),
+            _ => None,
+        })
+    }
+
+    /// Keys in file order, without duplicates, with their comments.
+    ///
+    /// Comment lines directly above an assignment describe it. A comment
+    /// block followed by a blank line is a section header, which applies to
+    /// the keys after it until the next one. Decoration (`# ======`) is
+    /// dropped from both.
+    pub fn annotations(&self) -> Vec<Annotation> {
+        let mut seen = HashSet::new();
+        let mut annotations = Vec::new();
+        let mut pending: Vec<&str> = Vec::new();
+        let mut section = None;
+
+        for entry in &self.entries {
+            match entry {
+                Entry::Var { key, .. } => {
+                    if seen.insert(key.as_str()) {
+                        annotations.push(Annotation {
+                            key: key.clone(),
+                            description: (!pending.is_empty()).then(|| pending.join("\n")),
+                            section: section.clone(),
+                        });
+                    }
+                    pending.clear();
+                }
+                Entry::Trivia(text) if entry.is_comment() => {
+                    let line = text.trim_start().trim_start_matches('#').trim();
+                    if !line.trim_matches(DECORATION).is_empty() {
+                        pending.push(line);
+                    }
+                }
+                Entry::Trivia(text) if text.trim().is_empty() => {
+                    if let Some(first) = pending.first() {
+                        section = Some(first.trim_matches(DECORATION).to_string());
+                    }
+                    pending.clear();
+                }
+                Entry::Trivia(_) => pending.clear(),
+            }
+        }
+        annotations
+    }
+
+    /// Keys in file order, without duplicates.
+    pub fn keys(&self) -> Vec<&str> {
+        let mut seen = HashSet::new();
+        self.entries
+            .iter()
+            .filter_map(Entry::key)
+            .filter(|key| seen.insert(*key))
+            .collect()
+    }
+}
+
+/// Whether `value`, which opens with quote `q`, closes it. Double quotes
+/// honour backslash escapes; single quotes and backticks are literal.
+fn closes(value: &str, q: char) -> bool {
+    let mut chars = value.chars().skip(1);
+    while let Some(c) = chars.next() {
+        if c == '\\' && q == '"' {
+            chars.next();
+        } else if c == q {
+            return true;
+        }
+    }
+    false
+}
+
+impl fmt::Display for Document {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, entry) in self.entries.iter().enumerate() {
+            if i > 0 {
+                f.write_str("\n")?;
+            }
+            f.write_str(entry.text())?;
+        }
+        if self.trailing_newline && !self.entries.is_empty() {
+            f.write_str("\n")?;
+        }
+        Ok(())
+    }
+}
+
+/// Characters that only decorate a comment (`# ==== Database ====`).
+const DECORATION: &[char] = &['#', '=', '-', '*', '_', '~', '─', '━', '═', ' '];
+
+/// `(key, raw value)` of an assignment line, `None` for anything else.
+fn split_assignment(line: &str) -> Option<(&str, &str)> {
+    let trimmed = line.trim_start();
+    if trimmed.starts_with('#') {
+        return None;
+    }
+    let rest = trimmed
+        .strip_prefix("export ")
+        .map(str::trim_start)
+        .unwrap_or(trimmed);
+    let (key, raw) = rest.split_once('=')?;
+    let key = key.trim();
+
+    let mut chars = key.chars();
+    let valid = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+    valid.then_some((key, raw))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip_is_lossless() {
+        let content = "# Database\r\nexport DB_URL = \"postgres://localhost/app\"  \n\n\
+                       KEY='multi\nline\nvalue'\nnot an assignment\nEMPTY=\nDB_URL=override";
+        let doc = Document::parse(content);
+        assert_eq!(doc.to_string(), content);
+        assert_eq!(doc.entries.len(), 7);
+        assert!(!doc.trailing_newline);
+
+        assert_eq!(doc.get("DB_URL"), Some("override"));
+        assert_eq!(doc.get("KEY"), Some("'multi\nline\nvalue'"));
+        assert_eq!(doc.keys(), vec!["DB_URL", "KEY", "EMPTY"]);
+
+        let content = "DB_URL=\"postgres://x\" # primary db\nPORT=3000\n\
+                       NAME=\"say \\\"hi\\\"\" # quoted\nTOKEN='a # b' # note\n\
+                       PEM=\"line1\nline2\" # key\nLAST=1\n";
+        let doc = Document::parse(content);
+        assert_eq!(doc.to_string(), content);
+        assert_eq!(
+            doc.keys(),
+            vec!["DB_URL", "PORT", "NAME", "TOKEN", "PEM", "LAST"]
+        );
+        assert_eq!(doc.get("DB_URL"), Some("\"postgres://x\" # primary db"));
+        assert_eq!(doc.get("PEM"), Some("\"line1\nline2\" # key"));
+
+        assert_eq!(Document::parse("").to_string(), "");
+        assert_eq!(Document::parse("\n\n").to_string(), "\n\n");
+    }
+
+    #[test]
+    fn test_annotations() {
+        let doc = Document::parse(
+            "# Generated for demo\n\n# ==== Database ====\n\n# Primary connection\n\
+             # (read-write)\nDB_URL=postgres://\nDB_POOL=5\n\n# --- Cache ---\n\n\
+             REDIS_URL=\n#\n# Key prefix\nREDIS_PREFIX=app\nDB_URL=override\n",
+        );
+        let annotations = doc.annotations();
+
+        let described: Vec<_> = annotations
+            .iter()
+            .map(|a| {
+                (
+                    a.key.as_str(),
+                    a.description.as_deref(),
+                    a.section.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            described,
+            vec![
+                (
+                    "DB_URL",
+                    Some("Primary connection\n(read-write)"),
+                    Some("Database")
+                ),
+                ("DB_POOL", None, Some("Database")),
+                ("REDIS_URL", None, Some("Cache")),
+                ("REDIS_PREFIX", Some("Key prefix"), Some("Cache")),
+            ]
+        );
+    }
+}
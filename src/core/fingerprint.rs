@@ -0,0 +1,274 @@
+//! Secret-safe value comparison.
+//!
+//! A [`Fingerprint`] records, per key, an HMAC-SHA256 of the value keyed by a
+//! shared secret, and optionally its shape (length and charset). Two
+//! fingerprints built with the same key tell whether a key holds the same
+//! value on both sides, and how its shape changed, without revealing either
+//! value. A fingerprint can be exported (`evnx diff --fingerprint`) and
+//! compared on another machine, so staging and production can be checked
+//! without exchanging `.env` files.
+//!
+//! The key is never written to the fingerprint; it travels separately. Without
+//! it a fingerprint cannot be used to confirm guesses, even of short values
+//! such as `true` or `3000`. Exported shapes do reveal lengths and charsets,
+//! so they are left out unless asked for.
+
+use anyhow::{bail, Result};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::utils::scoring::Charset;
+
+/// Value of the `format` field, which also identifies fingerprint files.
+pub const FORMAT: &str = "evnx-fingerprint/2";
+
+/// Length and charset of a value.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Shape {
+    pub length: usize,
+    /// `empty`, `hex`, `alphanumeric`, `base64url`, `base64` or `mixed`.
+    pub charset: String,
+}
+
+impl Shape {
+    pub fn of(value: &str) -> Self {
+        Self {
+            length: value.chars().count(),
+            charset: if value.is_empty() {
+                "empty".to_string()
+            } else {
+                Charset::of(value).to_string()
+            },
+        }
+    }
+}
+
+/// Digest and, if recorded, shape of one key's value.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyPrint {
+    pub digest: String,
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub shape: Option<Shape>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Fingerprint {
+    pub format: String,
+    /// Identifies the key without revealing it, so that fingerprints made
+    /// with different keys are not reported as all different.
+    pub key_check: String,
+    pub keys: BTreeMap<String, KeyPrint>,
+}
+
+impl Fingerprint {
+    /// Fingerprint `vars` with the secret `key`, recording shapes if
+    /// `shapes` is set.
+    pub fn new(vars: &HashMap<String, String>, key: &str, shapes: bool) -> Self {
+        let keys = vars
+            .iter()
+            .map(|(name, value)| {
+                (
+                    name.clone(),
+                    KeyPrint {
+                        digest: digest(key, name, value),
+                        shape: shapes.then(|| Shape::of(value)),
+                    },
+                )
+            })
+            .collect();
+
+        Self {
+            format: FORMAT.to_string(),
+            key_check: hex(&hmac(key.as_bytes(), &[FORMAT.as_bytes()])),
+            keys,
+        }
+    }
+
+    /// Parse `content` if it is a fingerprint file, `None` otherwise.
+    pub fn parse(content: &str) -> Option<Self> {
+        if !content.trim_start().starts_with('{') {
+            return None;
+        }
+        serde_json::from_str::<Self>(content)
+            .ok()
+            .filter(|fp| fp.format == FORMAT)
+    }
+}
+
+/// A fresh random key (256 bits, hex).
+pub fn random_key() -> String {
+    hex(&rand::random::<[u8; 32]>())
+}
+
+/// Keyed digest of `name=value`; the name is included so equal values under
+/// different names do not show up as equal.
+pub fn digest(key: &str, name: &str, value: &str) -> String {
+    hex(&hmac(
+        key.as_bytes(),
+        &[name.as_bytes(), &[0], value.as_bytes()],
+    ))
+}
+
+/// HMAC-SHA256 (RFC 2104) of the concatenated `message` parts.
+fn hmac(key: &[u8], message: &[&[u8]]) -> [u8; 32] {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC takes keys of any length");
+    for part in message {
+        mac.update(part);
+    }
+    mac.finalize().into_bytes().into()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// A key present on both sides with different values. Shapes are missing
+/// for a side whose fingerprint was exported without them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Changed {
+    pub key: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub left: Option<Shape>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub right: Option<Shape>,
+}
+
+/// Comparison of two fingerprints; keys are sorted.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HashedDiff {
+    /// Only in the right-hand side.
+    pub missing: Vec<String>,
+    /// Only in the left-hand side.
+    pub extra: Vec<String>,
+    pub same: Vec<String>,
+    pub different: Vec<Changed>,
+}
+
+/// Compare two fingerprints built with the same key.
+pub fn compare(left: &Fingerprint, right: &Fingerprint) -> Result<HashedDiff> {
+    if left.key_check != right.key_check {
+        bail!("The fingerprints were made with different keys; pass the key used for the export with --key");
+    }
+
+    let mut diff = HashedDiff::default();
+    for (key, l) in &left.keys {
+        match right.keys.get(key) {
+            None => diff.extra.push(key.clone()),
+            Some(r) if r.digest == l.digest => diff.same.push(key.clone()),
+            Some(r) => diff.different.push(Changed {
+                key: key.clone(),
+                left: l.shape.clone(),
+                right: r.shape.clone(),
+            }),
+        }
+    }
+    diff.missing = right
+        .keys
+        .keys()
+        .filter(|key| !left.keys.contains_key(*key))
+        .cloned()
+        .collect();
+
+    Ok(diff)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_hmac_matches_rfc_4231() {
+        assert_eq!(
+            hex(&hmac(
+                b"Jefe",
+                &[&b"what do ya want "[..], &b"for nothing?"[..]]
+            )),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+        // Keys longer than a block are hashed first (test case 6).
+        assert_eq!(
+            hex(&hmac(
+                &[0xaa; 131],
+                &[b"Test Using Larger Than Block-Size Key - Hash Key First"]
+            )),
+            "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"
+        );
+    }
+
+    #[test]
+    fn test_compare_fingerprints() {
+        let key = random_key();
+        assert_eq!(key.len(), 64);
+
+        let staging = Fingerprint::new(
+            &vars(&[
+                ("JWT_SECRET", "9f86d081884c7d65"),
+                ("PORT", "3000"),
+                ("DEBUG", "1"),
+            ]),
+            &key,
+            true,
+        );
+        let production = Fingerprint::new(
+            &vars(&[
+                ("JWT_SECRET", "Zq8w-N3vB6yT1mK4xR7c"),
+                ("PORT", "3000"),
+                ("SENTRY_DSN", ""),
+            ]),
+            &key,
+            true,
+        );
+
+        let diff = compare(&staging, &production).unwrap();
+        assert_eq!(diff.same, vec!["PORT"]);
+        assert_eq!(diff.extra, vec!["DEBUG"]);
+        assert_eq!(diff.missing, vec!["SENTRY_DSN"]);
+        assert_eq!(
+            diff.different,
+            vec![Changed {
+                key: "JWT_SECRET".into(),
+                left: Some(Shape {
+                    length: 16,
+                    charset: "hex".into()
+                }),
+                right: Some(Shape {
+                    length: 20,
+                    charset: "base64url".into()
+                }),
+            }]
+        );
+
+        // Neither values nor the key appear in the export, and it parses back.
+        let json = serde_json::to_string(&staging).unwrap();
+        assert!(!json.contains("9f86d081884c7d65"));
+        assert!(!json.contains(&key));
+        assert_eq!(Fingerprint::parse(&json), Some(staging.clone()));
+        assert_eq!(Fingerprint::parse("PORT=3000"), None);
+
+        let other = Fingerprint::new(&vars(&[("PORT", "3000")]), &random_key(), true);
+        assert!(compare(&staging, &other).is_err());
+    }
+
+    #[test]
+    fn test_shapes_are_optional() {
+        let key = random_key();
+        let print = Fingerprint::new(&vars(&[("JWT_SECRET", "9f86d081884c7d65")]), &key, false);
+        let json = serde_json::to_string(&print).unwrap();
+        assert!(!json.contains("length"));
+        assert!(!json.contains("charset"));
+        assert_eq!(Fingerprint::parse(&json), Some(print.clone()));
+
+        let local = Fingerprint::new(&vars(&[("JWT_SECRET", "other")]), &key, true);
+        let diff = compare(&local, &print).unwrap();
+        assert_eq!(diff.different[0].right, None);
+    }
+}
@@ -0,0 +1,315 @@
+//! Mapping between flat env keys and nested configuration paths.
+//!
+//! .NET reads `Logging__LogLevel__Default` as `Logging:LogLevel:Default`,
+//! and Spring reads `SPRING_DATASOURCE_URL` as `spring.datasource.url`. A
+//! [`KeyPath`] describes such a mapping: the separator between segments and
+//! the casing of segments in the nested document. [`nest`] builds the nested
+//! document from flat variables; [`KeyPath::join`] goes back, for
+//! `evnx import`.
+//!
+//! Numeric segments (`HOSTS__0`, `HOSTS__1`) become arrays when they number
+//! the items of a node from zero without gaps.
+
+use anyhow::{bail, Result};
+use serde::{Serialize, Serializer};
+
+use super::converter::{to_camel_case, to_snake_case, Variable};
+
+/// Casing of path segments in the nested document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SegmentCase {
+    /// Segments as written in the key (`Logging__LogLevel`)
+    #[default]
+    Preserve,
+    /// `DATASOURCE` → `datasource`
+    Lower,
+    /// `MAX_POOL_SIZE` → `maxPoolSize`
+    Camel,
+    /// `MAX_POOL_SIZE` → `max-pool-size`
+    Kebab,
+}
+
+impl SegmentCase {
+    /// Parse a `--key-case` name
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "preserve" => Some(SegmentCase::Preserve),
+            "lower" | "lowercase" => Some(SegmentCase::Lower),
+            "camel" | "camelCase" => Some(SegmentCase::Camel),
+            "kebab" | "kebab-case" => Some(SegmentCase::Kebab),
+            _ => None,
+        }
+    }
+}
+
+/// How flat keys map to nested paths.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyPath {
+    /// Between segments in the flat key, e.g. `__` or `_`
+    pub separator: String,
+    pub case: SegmentCase,
+}
+
+impl KeyPath {
+    pub fn new(separator: impl Into<String>, case: SegmentCase) -> Self {
+        Self {
+            separator: separator.into(),
+            case,
+        }
+    }
+
+    /// Path of `key` in the nested document.
+    pub fn split(&self, key: &str) -> Vec<String> {
+        key.split(self.separator.as_str())
+            .filter(|segment| !segment.is_empty())
+            .map(|segment| match self.case {
+                SegmentCase::Preserve => segment.to_string(),
+                SegmentCase::Lower => segment.to_lowercase(),
+                SegmentCase::Camel => to_camel_case(segment),
+                SegmentCase::Kebab => segment.to_lowercase().replace('_', "-"),
+            })
+            .collect()
+    }
+
+    /// Flat key of `path`; the inverse of [`split`](Self::split). Segments
+    /// that were re-cased go back to `UPPER_SNAKE_CASE`.
+    pub fn join(&self, path: &[String]) -> String {
+        path.iter()
+            .map(|segment| match self.case {
+                SegmentCase::Preserve => segment.clone(),
+                _ => to_snake_case(segment)
+                    .replace(['-', '.', ' '], "_")
+                    .to_uppercase(),
+            })
+            .collect::<Vec<_>>()
+            .join(&self.separator)
+    }
+}
+
+/// A node of a nested document; maps keep the order of the variables.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Node {
+    Leaf {
+        value: serde_json::Value,
+        /// Description of the variable the value came from
+        description: Option<String>,
+    },
+    Map(Vec<(String, Node)>),
+    List(Vec<Node>),
+}
+
+impl Serialize for Node {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Node::Leaf { value, .. } => value.serialize(serializer),
+            Node::Map(entries) => serializer.collect_map(entries.iter().map(|(k, v)| (k, v))),
+            Node::List(items) => serializer.collect_seq(items),
+        }
+    }
+}
+
+/// Nested document of `vars`, whose keys are split with `path`.
+///
+/// With `coerce`, values that read as booleans, numbers or JSON arrays and
+/// objects are typed; otherwise every value is a string.
+pub fn nest(vars: &[Variable], path: &KeyPath, coerce: bool) -> Result<Node> {
+    let mut root = Vec::new();
+
+    for Variable {
+        key,
+        value,
+        description,
+        ..
+    } in vars
+    {
+        let segments = path.split(key);
+        if segments.is_empty() {
+            bail!("{} has no path segments", key);
+        }
+
+        let mut entries = &mut root;
+        for (depth, segment) in segments.iter().enumerate() {
+            let last = depth + 1 == segments.len();
+            let at = match entries.iter().position(|(k, _)| k == segment) {
+                Some(at) => at,
+                None => {
+                    let node = if last {
+                        Node::Leaf {
+                            value: typed(value, coerce),
+                            description: description.clone(),
+                        }
+                    } else {
+                        Node::Map(Vec::new())
+                    };
+                    entries.push((segment.clone(), node));
+                    entries.len() - 1
+                }
+            };
+
+            match &mut entries[at].1 {
+                Node::Map(children) if !last => entries = children,
+                Node::Leaf { .. } if last => break,
+                _ => bail!(
+                    "{} and another variable both set `{}`; one is a value, the other a parent",
+                    key,
+                    segments[..=depth].join(".")
+                ),
+            }
+        }
+    }
+
+    Ok(lists(Node::Map(root)))
+}
+
+/// `node` with maps keyed `0`, `1`, … turned into lists.
+fn lists(node: Node) -> Node {
+    let Node::Map(entries) = node else {
+        return node;
+    };
+    let entries: Vec<(String, Node)> = entries.into_iter().map(|(k, v)| (k, lists(v))).collect();
+
+    let mut indices: Vec<usize> = entries
+        .iter()
+        .filter(|(k, _)| !k.starts_with('0') || k == "0")
+        .filter_map(|(k, _)| k.parse().ok())
+        .collect();
+    indices.sort_unstable();
+    let numbered = !entries.is_empty()
+        && indices.len() == entries.len()
+        && indices.iter().enumerate().all(|(i, &n)| i == n);
+
+    if numbered {
+        let mut entries = entries;
+        entries.sort_by_key(|(k, _)| k.parse::<usize>().unwrap_or_default());
+        Node::List(entries.into_iter().map(|(_, v)| v).collect())
+    } else {
+        Node::Map(entries)
+    }
+}
+
+/// `value` as JSON: typed when `coerce` is set and it reads as a boolean,
+/// number, array or object, a string otherwise.
+pub(crate) fn typed(value: &str, coerce: bool) -> serde_json::Value {
+    use serde_json::Value;
+
+    if !coerce {
+        return Value::String(value.to_string());
+    }
+    match value {
+        "true" => return Value::Bool(true),
+        "false" => return Value::Bool(false),
+        _ => {}
+    }
+
+    // Numbers as JSON writes them; `007` and `1e3` stay strings.
+    let digits = value.strip_prefix('-').unwrap_or(value);
+    let (int, frac) = digits.split_once('.').unwrap_or((digits, "0"));
+    let numeric = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+    if numeric(int) && numeric(frac) && (int == "0" || !int.starts_with('0')) {
+        // Only if JSON writes it back the same, so nothing is rounded.
+        if let Ok(number) = value.parse::<serde_json::Number>() {
+            if number.to_string() == value {
+                return Value::Number(number);
+            }
+        }
+    }
+
+    if (value.starts_with('[') && value.ends_with(']'))
+        || (value.starts_with('{') && value.ends_with('}'))
+    {
+        if let Ok(parsed) = serde_json::from_str(value) {
+            return parsed;
+        }
+    }
+    Value::String(value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(pairs: &[(&str, &str)]) -> Vec<Variable> {
+        pairs.iter().map(|(k, v)| Variable::new(*k, *v)).collect()
+    }
+
+    #[test]
+    fn test_dotnet_style_paths() {
+        let path = KeyPath::new("__", SegmentCase::Preserve);
+        let doc = nest(
+            &input(&[
+                ("Logging__LogLevel__Default", "Information"),
+                ("Logging__LogLevel__Microsoft", "Warning"),
+                ("Hosts__1", "b"),
+                ("Hosts__0", "a"),
+                ("Port", "8080"),
+            ]),
+            &path,
+            true,
+        )
+        .unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&doc).unwrap(),
+            serde_json::json!({
+                "Logging": {"LogLevel": {"Default": "Information", "Microsoft": "Warning"}},
+                "Hosts": ["a", "b"],
+                "Port": 8080
+            })
+        );
+        assert_eq!(
+            path.join(&["Logging".into(), "LogLevel".into(), "Default".into()]),
+            "Logging__LogLevel__Default"
+        );
+    }
+
+    #[test]
+    fn test_spring_style_paths() {
+        let path = KeyPath::new("_", SegmentCase::Lower);
+        assert_eq!(
+            path.split("SPRING_DATASOURCE_URL"),
+            vec!["spring", "datasource", "url"]
+        );
+        assert_eq!(
+            path.join(&["spring".into(), "datasource".into(), "url".into()]),
+            "SPRING_DATASOURCE_URL"
+        );
+
+        let camel = KeyPath::new("__", SegmentCase::Camel);
+        assert_eq!(
+            camel.split("SPRING__DATASOURCE__MAX_POOL_SIZE"),
+            vec!["spring", "datasource", "maxPoolSize"]
+        );
+        assert_eq!(
+            camel.join(&["spring".into(), "maxPoolSize".into()]),
+            "SPRING__MAX_POOL_SIZE"
+        );
+        let kebab = KeyPath::new("__", SegmentCase::Kebab);
+        assert_eq!(kebab.split("SERVER__MAX_HTTP"), vec!["server", "max-http"]);
+        assert_eq!(kebab.join(&["max-http".into()]), "MAX_HTTP");
+    }
+
+    #[test]
+    fn test_coercion_and_conflicts() {
+        let path = KeyPath::new("__", SegmentCase::Preserve);
+        let values = input(&[
+            ("A", "true"),
+            ("B", "-1.5"),
+            ("C", "007"),
+            ("F", "123456789012345678901234567890"),
+            ("D", "[1, 2]"),
+            ("E", "[not json"),
+        ]);
+        assert_eq!(
+            serde_json::to_value(nest(&values, &path, true).unwrap()).unwrap(),
+            serde_json::json!({"A": true, "B": -1.5, "C": "007", "D": [1, 2], "E": "[not json",
+                "F": "123456789012345678901234567890"})
+        );
+        assert_eq!(
+            serde_json::to_value(nest(&values, &path, false).unwrap()).unwrap()["A"],
+            "true"
+        );
+
+        assert!(nest(&input(&[("A", "1"), ("A__B", "2")]), &path, false).is_err());
+        assert!(nest(&input(&[("A__B", "2"), ("A", "1")]), &path, false).is_err());
+    }
+}
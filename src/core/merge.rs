@@ -0,0 +1,333 @@
+//! Three-way merge of `.env` documents by key.
+//!
+//! Line-based merges conflict whenever two branches append different
+//! variables at the end of the same file. Merging by key avoids that: a
+//! variable changed on one side only takes that side's value, additions from
+//! both sides are kept, and only the same key changed to different values
+//! (or changed on one side and deleted on the other) is a conflict.
+//!
+//! The layout follows `ours`: its comments, sections and order are kept.
+//! Variables added in `theirs` are placed after the variable that precedes
+//! them in `theirs` (after any of our own additions there), together with
+//! comments they introduced. Conflicts are written with git-style markers.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use super::document::{Document, Entry};
+
+/// Why a key could not be merged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictKind {
+    /// Both sides changed the value differently.
+    BothModified,
+    /// Both sides added the key with different values.
+    BothAdded,
+    /// We changed the value, they deleted the key.
+    ModifiedDeleted,
+    /// We deleted the key, they changed the value.
+    DeletedModified,
+}
+
+impl fmt::Display for ConflictKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ConflictKind::BothModified => "changed on both sides",
+            ConflictKind::BothAdded => "added on both sides with different values",
+            ConflictKind::ModifiedDeleted => "changed in ours, deleted in theirs",
+            ConflictKind::DeletedModified => "deleted in ours, changed in theirs",
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Conflict {
+    pub key: String,
+    pub kind: ConflictKind,
+}
+
+/// Merged document and the keys left with conflict markers.
+#[derive(Debug, Clone)]
+pub struct Merge {
+    pub document: Document,
+    pub conflicts: Vec<Conflict>,
+}
+
+/// An entry of the output; `added` marks entries that are not in `base`
+/// (and comments belonging to them), which theirs' additions are placed after.
+struct Slot {
+    key: Option<String>,
+    added: bool,
+    entry: Entry,
+}
+
+/// Merge `ours` and `theirs`, both derived from `base`.
+pub fn merge(base: &Document, ours: &Document, theirs: &Document) -> Merge {
+    let mut slots = Vec::new();
+    let mut conflicts = Vec::new();
+
+    let ours_last = last_positions(ours);
+    let theirs_last = last_positions(theirs);
+    let theirs_entries: HashMap<&str, &Entry> = theirs_last
+        .iter()
+        .map(|(key, &i)| (*key, &theirs.entries[i]))
+        .collect();
+
+    // Comments belong to the variable below them.
+    let mut added_flags = vec![false; ours.entries.len()];
+    let mut next_added = false;
+    for (i, entry) in ours.entries.iter().enumerate().rev() {
+        if let Some(key) = entry.key() {
+            next_added = base.get(key).is_none();
+        }
+        added_flags[i] = next_added;
+    }
+
+    for (i, entry) in ours.entries.iter().enumerate() {
+        let Entry::Var { key, value, .. } = entry else {
+            slots.push(Slot {
+                key: None,
+                added: added_flags[i],
+                entry: entry.clone(),
+            });
+            continue;
+        };
+        if ours_last[key.as_str()] != i {
+            slots.push(Slot {
+                key: None,
+                added: false,
+                entry: entry.clone(),
+            });
+            continue;
+        }
+
+        let b = base.get(key);
+        let t = theirs_entries.get(key.as_str()).copied();
+
+        let merged = match (b, t.map(value_of)) {
+            (Some(b), Some(t_value)) if t_value == b || t_value == value => Ok(entry.clone()),
+            (Some(b), Some(_)) if value == b => Ok(t.cloned().unwrap_or_else(|| entry.clone())),
+            (Some(_), Some(_)) => Err(ConflictKind::BothModified),
+            (Some(b), None) if value == b => continue,
+            (Some(_), None) => Err(ConflictKind::ModifiedDeleted),
+            (None, Some(t_value)) if t_value == value => Ok(entry.clone()),
+            (None, Some(_)) => Err(ConflictKind::BothAdded),
+            (None, None) => Ok(entry.clone()),
+        };
+        let merged = merged.unwrap_or_else(|kind| {
+            conflicts.push(Conflict {
+                key: key.clone(),
+                kind,
+            });
+            conflict_block(Some(entry), t)
+        });
+
+        slots.push(Slot {
+            key: Some(key.clone()),
+            added: b.is_none(),
+            entry: merged,
+        });
+    }
+
+    // Keys only theirs has: their additions, or keys we deleted.
+    let known_comments: HashSet<&str> = base
+        .entries
+        .iter()
+        .chain(&ours.entries)
+        .filter(|e| e.is_comment())
+        .map(Entry::text)
+        .collect();
+
+    for (i, entry) in theirs.entries.iter().enumerate() {
+        let Some(key) = entry.key() else {
+            continue;
+        };
+        if ours_last.contains_key(key) || theirs_last[key] != i {
+            continue;
+        }
+
+        let block = match base.get(key) {
+            Some(b) if b == value_of(entry) => continue,
+            Some(_) => {
+                conflicts.push(Conflict {
+                    key: key.to_string(),
+                    kind: ConflictKind::DeletedModified,
+                });
+                conflict_block(None, Some(entry))
+            }
+            None => entry.clone(),
+        };
+
+        // Comment lines directly above the key travel with it if they are new.
+        let gap_start = theirs.entries[..i]
+            .iter()
+            .rposition(|e| e.key().is_some())
+            .map_or(0, |p| p + 1);
+        let gap = &theirs.entries[gap_start..i];
+        let carry = gap
+            .iter()
+            .any(|e| e.is_comment() && !known_comments.contains(e.text()));
+
+        let at = insert_position(&slots, &theirs.entries[..gap_start]);
+        let mut new_slots: Vec<Slot> = if carry {
+            gap.iter()
+                .map(|e| Slot {
+                    key: None,
+                    added: true,
+                    entry: e.clone(),
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+        new_slots.push(Slot {
+            key: Some(key.to_string()),
+            added: true,
+            entry: block,
+        });
+        slots.splice(at..at, new_slots);
+    }
+
+    Merge {
+        document: Document {
+            entries: slots.into_iter().map(|s| s.entry).collect(),
+            trailing_newline: ours.trailing_newline
+                || (ours.entries.is_empty() && theirs.trailing_newline),
+        },
+        conflicts,
+    }
+}
+
+/// Index of the last assignment of each key.
+fn last_positions(doc: &Document) -> HashMap<&str, usize> {
+    doc.entries
+        .iter()
+        .enumerate()
+        .filter_map(|(i, e)| e.key().map(|k| (k, i)))
+        .collect()
+}
+
+fn value_of(entry: &Entry) -> &str {
+    match entry {
+        Entry::Var { value, .. } => value,
+        Entry::Trivia(_) => "",
+    }
+}
+
+/// Where to insert an entry of theirs whose preceding entries are `before`:
+/// after the nearest preceding key we also have, and after any additions
+/// that follow it. Without such a key, before our first variable.
+fn insert_position(slots: &[Slot], before: &[Entry]) -> usize {
+    let anchor = before
+        .iter()
+        .rev()
+        .filter_map(Entry::key)
+        .find_map(|key| slots.iter().rposition(|s| s.key.as_deref() == Some(key)));
+
+    match anchor {
+        Some(i) => {
+            let mut at = i + 1;
+            while slots.get(at).is_some_and(|s| s.added) {
+                at += 1;
+            }
+            at
+        }
+        None => slots
+            .iter()
+            .position(|s| s.key.is_some())
+            .unwrap_or(slots.len()),
+    }
+}
+
+fn conflict_block(ours: Option<&Entry>, theirs: Option<&Entry>) -> Entry {
+    let mut lines = vec!["<<<<<<< ours"];
+    lines.extend(ours.map(Entry::text));
+    lines.push("=======");
+    lines.extend(theirs.map(Entry::text));
+    lines.push(">>>>>>> theirs");
+    Entry::Trivia(lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(base: &str, ours: &str, theirs: &str) -> (String, Vec<Conflict>) {
+        let merged = merge(
+            &Document::parse(base),
+            &Document::parse(ours),
+            &Document::parse(theirs),
+        );
+        (merged.document.to_string(), merged.conflicts)
+    }
+
+    #[test]
+    fn test_both_sides_append() {
+        let base = "# App\nPORT=3000\n";
+        let ours = "# App\nPORT=3000\nREDIS_URL=\n";
+        let theirs = "# App\nPORT=3000\n\n# Payments\nSTRIPE_KEY=\n";
+
+        let (merged, conflicts) = run(base, ours, theirs);
+        assert!(conflicts.is_empty());
+        assert_eq!(
+            merged,
+            "# App\nPORT=3000\nREDIS_URL=\n\n# Payments\nSTRIPE_KEY=\n"
+        );
+    }
+
+    #[test]
+    fn test_one_sided_changes_and_deletes() {
+        let base = "A=1\nB=2\nC=3\n";
+        let ours = "# ours keeps its comment\nA=1\nB=20\nC=3\n";
+        let theirs = "A=10\nB=2\n";
+
+        let (merged, conflicts) = run(base, ours, theirs);
+        assert!(conflicts.is_empty());
+        assert_eq!(merged, "# ours keeps its comment\nA=10\nB=20\n");
+    }
+
+    #[test]
+    fn test_conflicts() {
+        let base = "A=1\nB=2\n";
+        let ours = "A=ours\nB=2\nC=x\n";
+        let theirs = "A=theirs\nC=y\n";
+
+        let (merged, conflicts) = run(base, ours, theirs);
+        assert_eq!(
+            conflicts,
+            vec![
+                Conflict {
+                    key: "A".into(),
+                    kind: ConflictKind::BothModified
+                },
+                Conflict {
+                    key: "C".into(),
+                    kind: ConflictKind::BothAdded
+                },
+            ]
+        );
+        assert_eq!(
+            merged,
+            "<<<<<<< ours\nA=ours\n=======\nA=theirs\n>>>>>>> theirs\n\
+             <<<<<<< ours\nC=x\n=======\nC=y\n>>>>>>> theirs\n"
+        );
+
+        let (merged, conflicts) = run("A=1\n", "", "A=2\n");
+        assert_eq!(conflicts[0].kind, ConflictKind::DeletedModified);
+        assert_eq!(merged, "<<<<<<< ours\n=======\nA=2\n>>>>>>> theirs\n");
+    }
+
+    #[test]
+    fn test_inline_comment_after_quoted_value() {
+        // The comment must not make DB_URL swallow the keys after it.
+        let base = "DB_URL=\"postgres://x\" # primary db\nPORT=3000\nNAME=app\n";
+        let ours = "DB_URL=\"postgres://x\" # primary db\nPORT=4000\nNAME=app\n";
+        let theirs = "DB_URL=\"postgres://x\" # primary db\nPORT=3000\nNAME=web\n";
+        let (merged, conflicts) = run(base, ours, theirs);
+        assert!(conflicts.is_empty());
+        assert_eq!(
+            merged,
+            "DB_URL=\"postgres://x\" # primary db\nPORT=4000\nNAME=web\n"
+        );
+    }
+}
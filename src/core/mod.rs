@@ -1,8 +1,15 @@
 pub mod config;
 pub mod converter;
+pub mod document;
+pub mod fingerprint;
+pub mod hygiene;
+pub mod keypath;
+pub mod merge;
 pub mod parser;
 
 // Re-export commonly used types
 pub use config::Config;
-pub use converter::{ConvertOptions, Converter, KeyTransform};
+pub use converter::{ConvertOptions, Converter, KeyTransform, Variable};
+pub use document::{Document, Entry};
+pub use keypath::{KeyPath, SegmentCase};
 pub use parser::{EnvFile, ParseError, ParseResult, Parser, ParserConfig};
@@ -20,6 +20,7 @@
 //! | Multiline values         | ✓ (opt-in via [`ParserConfig::allow_multiline`]) |
 //! | `${VAR}` expansion       | ✓         |
 //! | `$VAR` expansion         | ✓         |
+//! | `\$` escape (double quotes) | ✓       |
 //! | Circular expansion guard | ✓         |
 //! | Strict uppercase keys    | ✓ (opt-in via [`ParserConfig::strict`]) |
 //!
@@ -67,7 +68,7 @@
 //! | `core/converter.rs`    | `EnvFile.vars`     | None — field name preserved |
 //! | Tests                  | `parse_content`    | None — method name preserved |
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::Path;
 use thiserror::Error;
@@ -295,6 +296,8 @@ impl Parser {
     /// ```
     pub fn parse_content(&self, content: &str) -> ParseResult<HashMap<String, String>> {
         let mut vars: HashMap<String, String> = HashMap::new();
+        // Keys whose double-quoted value is unescaped during expansion.
+        let mut escaped: HashSet<String> = HashSet::new();
 
         // Multiline accumulation state.
         let mut ml_key: Option<String> = None;
@@ -313,6 +316,7 @@ impl Parser {
                     // Closing quote found — finalise the value.
                     ml_value.push('\n');
                     ml_value.push_str(before_close);
+                    escaped.remove(key);
                     vars.insert(key.clone(), ml_value.clone());
                     ml_key = None;
                     ml_value.clear();
@@ -349,6 +353,11 @@ impl Parser {
                 }
                 _ => {
                     let value = self.parse_value(&raw_value, line_num)?;
+                    if self.config.allow_expansion && raw_value.trim_start().starts_with('"') {
+                        escaped.insert(key.clone());
+                    } else {
+                        escaped.remove(&key);
+                    }
                     vars.insert(key, value);
                 }
             }
@@ -364,7 +373,7 @@ impl Parser {
 
         // ── Variable expansion ────────────────────────────────────────────────
         if self.config.allow_expansion {
-            self.expand_all(&mut vars)?;
+            self.expand_all(&mut vars, &escaped)?;
         }
 
         Ok(vars)
@@ -460,7 +469,8 @@ impl Parser {
     ///
     /// Dispatch order:
     /// 1. Empty → empty string.
-    /// 2. Double-quoted → unescape escape sequences.
+    /// 2. Double-quoted → unescape escape sequences (during expansion when
+    ///    it is enabled, so that `\$` keeps a `$` from being expanded).
     /// 3. Single-quoted / backtick → literal (no unescaping).
     /// 4. Unquoted → strip inline comment, optionally trim.
     fn parse_value(&self, raw: &str, line_num: usize) -> ParseResult<String> {
@@ -478,7 +488,11 @@ impl Parser {
                     return Err(ParseError::UnterminatedString { line: line_num });
                 }
                 let inner = &raw[1..raw.len() - 1];
-                Ok(self.unescape_double(inner))
+                if self.config.allow_expansion {
+                    Ok(inner.to_string())
+                } else {
+                    Ok(self.unescape_double(inner))
+                }
             }
 
             '\'' | '`' => {
@@ -513,7 +527,7 @@ impl Parser {
 
     /// Process backslash escape sequences inside a double-quoted value.
     ///
-    /// Recognised sequences: `\n`, `\r`, `\t`, `\\`, `\"`, `\'`.
+    /// Recognised sequences: `\n`, `\r`, `\t`, `\\`, `\"`, `\'`, `\$`.
     /// Unknown sequences are kept literally (backslash + character).
     fn unescape_double(&self, s: &str) -> String {
         let mut result = String::with_capacity(s.len());
@@ -524,19 +538,7 @@ impl Parser {
                 result.push(ch);
                 continue;
             }
-            match chars.next() {
-                Some('n') => result.push('\n'),
-                Some('r') => result.push('\r'),
-                Some('t') => result.push('\t'),
-                Some('\\') => result.push('\\'),
-                Some('"') => result.push('"'),
-                Some('\'') => result.push('\''),
-                Some(c) => {
-                    result.push('\\');
-                    result.push(c);
-                }
-                None => result.push('\\'),
-            }
+            push_escape(&mut result, chars.next());
         }
         result
     }
@@ -547,19 +549,28 @@ impl Parser {
     ///
     /// Each value is expanded independently. Circular references and undefined
     /// variables produce structured errors.
-    fn expand_all(&self, vars: &mut HashMap<String, String>) -> ParseResult<()> {
-        // Snapshot keys to avoid borrow conflicts while mutating the map.
-        let keys: Vec<String> = vars.keys().cloned().collect();
-        let mut expanded: HashMap<String, String> = HashMap::with_capacity(vars.len());
-
-        for key in &keys {
-            let value = vars[key].clone();
+    ///
+    /// Values of the `escaped` keys are still double-quoted text, unescaped
+    /// here so that `\$` yields a literal `$`.
+    fn expand_all(
+        &self,
+        vars: &mut HashMap<String, String>,
+        escaped: &HashSet<String>,
+    ) -> ParseResult<()> {
+        let sources: HashMap<String, (String, bool)> = vars
+            .drain()
+            .map(|(key, value)| {
+                let escapes = escaped.contains(&key);
+                (key, (value, escapes))
+            })
+            .collect();
+
+        for (key, (value, escapes)) in &sources {
             let mut stack: Vec<String> = Vec::new();
-            let result = self.expand_value(&value, vars, &mut stack, 0, 0)?;
-            expanded.insert(key.clone(), result);
+            let result = self.expand_value(value, *escapes, &sources, &mut stack, 0, 0)?;
+            vars.insert(key.clone(), result);
         }
 
-        *vars = expanded;
         Ok(())
     }
 
@@ -568,14 +579,17 @@ impl Parser {
     /// # Arguments
     ///
     /// * `value`     — The string to expand.
-    /// * `vars`      — The full variable map (snapshot at expansion start).
+    /// * `escapes`   — Whether `value` is double-quoted text still to unescape.
+    /// * `vars`      — The full variable map (snapshot at expansion start),
+    ///   with each value's `escapes`.
     /// * `stack`     — Variables currently being expanded (cycle detection).
     /// * `depth`     — Current recursion depth.
     /// * `line_hint` — Line number for error reporting (0 when unknown).
     fn expand_value(
         &self,
         value: &str,
-        vars: &HashMap<String, String>,
+        escapes: bool,
+        vars: &HashMap<String, (String, bool)>,
         stack: &mut Vec<String>,
         depth: usize,
         line_hint: usize,
@@ -591,6 +605,10 @@ impl Parser {
         let mut chars = value.chars().peekable();
 
         while let Some(ch) = chars.next() {
+            if ch == '\\' && escapes {
+                push_escape(&mut result, chars.next());
+                continue;
+            }
             if ch != '$' {
                 result.push(ch);
                 continue;
@@ -610,10 +628,16 @@ impl Parser {
                     }
 
                     match vars.get(&var_name) {
-                        Some(val) => {
+                        Some((val, escapes)) => {
                             stack.push(var_name.clone());
-                            let expanded =
-                                self.expand_value(val, vars, stack, depth + 1, line_hint)?;
+                            let expanded = self.expand_value(
+                                val,
+                                *escapes,
+                                vars,
+                                stack,
+                                depth + 1,
+                                line_hint,
+                            )?;
                             stack.pop();
                             result.push_str(&expanded);
                         }
@@ -641,10 +665,16 @@ impl Parser {
                     }
 
                     match vars.get(&var_name) {
-                        Some(val) => {
+                        Some((val, escapes)) => {
                             stack.push(var_name.clone());
-                            let expanded =
-                                self.expand_value(val, vars, stack, depth + 1, line_hint)?;
+                            let expanded = self.expand_value(
+                                val,
+                                *escapes,
+                                vars,
+                                stack,
+                                depth + 1,
+                                line_hint,
+                            )?;
                             stack.pop();
                             result.push_str(&expanded);
                         }
@@ -672,6 +702,22 @@ impl Parser {
     }
 }
 
+/// Append the character a backslash escape in a double-quoted value stands
+/// for; `next` is the character after the backslash.
+fn push_escape(result: &mut String, next: Option<char>) {
+    match next {
+        Some('n') => result.push('\n'),
+        Some('r') => result.push('\r'),
+        Some('t') => result.push('\t'),
+        Some(c @ ('\\' | '"' | '\'' | '$')) => result.push(c),
+        Some(c) => {
+            result.push('\\');
+            result.push(c);
+        }
+        None => result.push('\\'),
+    }
+}
+
 // ── Tests ─────────────────────────────────────────────────────────────────────
 
 #[cfg(test)]
@@ -938,6 +984,25 @@ mod tests {
         assert_eq!(vars["KEY"], "${OTHER}");
     }
 
+    #[test]
+    fn test_escaped_dollar_in_double_quotes() {
+        let p = Parser::default();
+        let content = "HASH=\"\\$2b\\$12\\${X}\"\nRAW='\\$HOME'\nREF=${HASH}\nPATH_=\"a\\\\\\$b\"";
+        let vars = p.parse_content(content).unwrap();
+        assert_eq!(vars["HASH"], "$2b$12${X}");
+        assert_eq!(vars["RAW"], "\\$HOME");
+        assert_eq!(vars["REF"], "$2b$12${X}");
+        assert_eq!(vars["PATH_"], "a\\$b");
+
+        let literal = Parser::new(ParserConfig {
+            allow_expansion: false,
+            ..Default::default()
+        });
+        let vars = literal.parse_content(content).unwrap();
+        assert_eq!(vars["HASH"], "$2b$12${X}");
+        assert_eq!(vars["REF"], "${HASH}");
+    }
+
     #[test]
     fn test_undefined_brace_var_errors() {
         let p = Parser::default();
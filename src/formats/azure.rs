@@ -2,9 +2,9 @@
 // Azure Key Vault
 // ============================================================================
 
-use crate::core::converter::{ConvertOptions, Converter};
+use super::escape::{hash_comments, shell_quote};
+use crate::core::converter::{ConvertOptions, Converter, Variable};
 use anyhow::Result;
-use std::collections::HashMap;
 
 pub struct AzureKeyVaultConverter {
     pub vault_name: String,
@@ -19,24 +19,30 @@ impl Default for AzureKeyVaultConverter {
 }
 
 impl Converter for AzureKeyVaultConverter {
-    fn convert(&self, vars: &HashMap<String, String>, options: &ConvertOptions) -> Result<String> {
+    fn convert(&self, vars: &[Variable], options: &ConvertOptions) -> Result<String> {
         let filtered = options.filter_vars(vars);
 
         let mut output = String::new();
         output.push_str("#!/bin/bash\n");
         output.push_str("# Upload secrets to Azure Key Vault\n");
-        output.push_str(&format!("# Vault: {}\n\n", self.vault_name));
+        output.push_str(&format!(
+            "# Vault: {}\n\n",
+            self.vault_name.replace('\n', " ")
+        ));
 
-        for (k, v) in filtered.iter() {
-            let key = options.transform_key(k);
-            let value = options.transform_value(v);
+        for (i, var) in filtered.iter().enumerate() {
+            let key = options.transform_key(&var.key);
+            let value = options.transform_value(&var.value);
+            output.push_str(&hash_comments(&filtered, i, ""));
 
             // Azure Key Vault naming: alphanumeric and hyphens only
             let secret_name = key.replace('_', "-");
 
             output.push_str(&format!(
-                "az keyvault secret set --vault-name {} --name {} --value '{}'\n",
-                self.vault_name, secret_name, value
+                "az keyvault secret set --vault-name {} --name {} --value {}\n",
+                shell_quote(&self.vault_name),
+                shell_quote(&secret_name),
+                shell_quote(&value)
             ));
         }
 
@@ -58,8 +64,7 @@ mod tests {
 
     #[test]
     fn test_azure_converter() {
-        let mut vars = HashMap::new();
-        vars.insert("API_KEY".to_string(), "secret123".to_string());
+        let vars = vec![Variable::new("API_KEY", "secret123")];
 
         let converter = AzureKeyVaultConverter::default();
         let result = converter
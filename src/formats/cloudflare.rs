@@ -0,0 +1,117 @@
+// ============================================================================
+// formats/cloudflare.rs
+// ============================================================================
+
+//! Cloudflare Workers: plain values go in the `[vars]` table of
+//! `wrangler.toml`, secret values (see [`is_secret`]) in a JSON object for
+//! `wrangler secret bulk`.
+
+use super::escape::{hash_comments, json_object, toml_key, toml_string};
+use super::kubernetes::is_secret;
+use crate::core::converter::{ConvertOptions, Converter, Variable};
+use anyhow::Result;
+
+/// `[vars]` table for `wrangler.toml`, without secret values.
+pub struct WranglerVarsConverter;
+
+impl Converter for WranglerVarsConverter {
+    fn convert(&self, vars: &[Variable], options: &ConvertOptions) -> Result<String> {
+        let filtered = options.filter_vars(vars);
+        let (secrets, config): (Vec<Variable>, Vec<Variable>) =
+            filtered.into_iter().partition(is_secret);
+
+        let mut output = String::from("# Generated by evnx\n");
+        if !secrets.is_empty() {
+            let keys: Vec<String> = secrets
+                .iter()
+                .map(|var| options.transform_key(&var.key))
+                .collect();
+            output.push_str(&format!(
+                "# Secrets, for `evnx convert --to wrangler-secrets | wrangler secret bulk`: {}\n",
+                keys.join(", ")
+            ));
+        }
+        output.push_str("\n[vars]\n");
+        for (i, var) in config.iter().enumerate() {
+            let key = options.transform_key(&var.key);
+            let value = options.transform_value(&var.value);
+            output.push_str(&hash_comments(&config, i, ""));
+            output.push_str(&format!("{} = {}\n", toml_key(&key), toml_string(&value)));
+        }
+
+        Ok(output)
+    }
+
+    fn name(&self) -> &str {
+        "wrangler"
+    }
+
+    fn description(&self) -> &str {
+        "wrangler.toml [vars] (secrets left out)"
+    }
+}
+
+/// JSON object of the secret values, for `wrangler secret bulk`.
+pub struct WranglerSecretsConverter;
+
+impl Converter for WranglerSecretsConverter {
+    fn convert(&self, vars: &[Variable], options: &ConvertOptions) -> Result<String> {
+        let secrets: Vec<(String, String)> = options
+            .filter_vars(vars)
+            .iter()
+            .filter(|var| is_secret(var))
+            .map(|var| {
+                (
+                    options.transform_key(&var.key),
+                    options.transform_value(&var.value),
+                )
+            })
+            .collect();
+
+        Ok(json_object(&secrets)?)
+    }
+
+    fn name(&self) -> &str {
+        "wrangler-secrets"
+    }
+
+    fn description(&self) -> &str {
+        "Cloudflare Workers secrets JSON (wrangler secret bulk)"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wrangler_split() {
+        let mut salt = Variable::new("SESSION_SECRET", "public-salt");
+        salt.description = Some("Not sensitive @config".into());
+        let vars = vec![
+            Variable::new("API_BASE", "https://api.example.com"),
+            Variable::new("API_TOKEN", "abc123"),
+            salt,
+        ];
+
+        let output = WranglerVarsConverter
+            .convert(&vars, &ConvertOptions::default())
+            .unwrap();
+        let parsed: toml::Table = toml::from_str(&output).unwrap();
+        assert_eq!(
+            parsed["vars"]["API_BASE"].as_str(),
+            Some("https://api.example.com")
+        );
+        assert_eq!(
+            parsed["vars"]["SESSION_SECRET"].as_str(),
+            Some("public-salt")
+        );
+        assert!(parsed["vars"].get("API_TOKEN").is_none());
+
+        let output = WranglerSecretsConverter
+            .convert(&vars, &ConvertOptions::default())
+            .unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(parsed, serde_json::json!({"API_TOKEN": "abc123"}));
+    }
+}
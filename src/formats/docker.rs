@@ -2,23 +2,26 @@
 // formats/docker.rs
 // ============================================================================
 
-use crate::core::converter::{ConvertOptions, Converter};
+use super::escape::{hash_comments, yaml_scalar};
+use crate::core::converter::{ConvertOptions, Converter, Variable};
 use anyhow::Result;
-use std::collections::HashMap;
 
 pub struct DockerComposeConverter;
 
 impl Converter for DockerComposeConverter {
-    fn convert(&self, vars: &HashMap<String, String>, options: &ConvertOptions) -> Result<String> {
+    fn convert(&self, vars: &[Variable], options: &ConvertOptions) -> Result<String> {
         let filtered = options.filter_vars(vars);
 
         let mut output = String::new();
         output.push_str("environment:\n");
 
-        for (k, v) in filtered.iter() {
-            let key = options.transform_key(k);
-            let value = options.transform_value(v);
-            output.push_str(&format!("  - {}={}\n", key, value));
+        for (i, var) in filtered.iter().enumerate() {
+            let key = options.transform_key(&var.key);
+            let value = options.transform_value(&var.value);
+            output.push_str(&hash_comments(&filtered, i, "  "));
+            // Compose interpolates `$` in the file itself; `$$` is a literal `$`.
+            let item = format!("{}={}", key, value.replace('$', "$$"));
+            output.push_str(&format!("  - {}\n", yaml_scalar(&item, 4)));
         }
 
         Ok(output)
@@ -2,21 +2,21 @@
 // Doppler Format
 // ============================================================================
 
-use crate::core::converter::{ConvertOptions, Converter};
+use super::escape::json_object;
+use crate::core::converter::{ConvertOptions, Converter, Variable};
 use anyhow::Result;
-use std::collections::HashMap;
 
 pub struct DopplerConverter;
 
 impl Converter for DopplerConverter {
-    fn convert(&self, vars: &HashMap<String, String>, options: &ConvertOptions) -> Result<String> {
+    fn convert(&self, vars: &[Variable], options: &ConvertOptions) -> Result<String> {
         let filtered = options.filter_vars(vars);
 
-        let transformed: HashMap<String, serde_json::Value> = filtered
+        let transformed: Vec<(String, serde_json::Value)> = filtered
             .iter()
-            .map(|(k, v)| {
-                let key = options.transform_key(k);
-                let value = options.transform_value(v);
+            .map(|var| {
+                let key = options.transform_key(&var.key);
+                let value = options.transform_value(&var.value);
                 (
                     key,
                     serde_json::json!({
@@ -27,7 +27,7 @@ impl Converter for DopplerConverter {
             })
             .collect();
 
-        let json = serde_json::to_string_pretty(&transformed)?;
+        let json = json_object(&transformed)?;
         Ok(json)
     }
 
@@ -0,0 +1,57 @@
+// ============================================================================
+// formats/dotnet.rs
+// ============================================================================
+
+use crate::core::converter::{ConvertOptions, Converter, Variable};
+use crate::core::{KeyPath, SegmentCase};
+use anyhow::Result;
+
+/// .NET `appsettings.json`. Keys are split on `__`, the separator .NET
+/// configuration reads from environment variables, so
+/// `Logging__LogLevel__Default` becomes `Logging:LogLevel:Default`.
+/// `--nest` and `--key-case` override the mapping.
+pub struct AppSettingsConverter;
+
+impl Converter for AppSettingsConverter {
+    fn convert(&self, vars: &[Variable], options: &ConvertOptions) -> Result<String> {
+        let path = options
+            .nest
+            .clone()
+            .unwrap_or_else(|| KeyPath::new("__", SegmentCase::Preserve));
+        let doc = options.nested_as(vars, &path)?;
+        Ok(serde_json::to_string_pretty(&doc)?)
+    }
+
+    fn name(&self) -> &str {
+        "appsettings"
+    }
+
+    fn description(&self) -> &str {
+        ".NET appsettings.json (__ hierarchy)"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_appsettings_converter() {
+        let vars = vec![
+            Variable::new("Logging__LogLevel__Default", "Warning"),
+            Variable::new("AllowedHosts", "*"),
+            Variable::new("Kestrel__Endpoints__Http__Url", "http://0.0.0.0:5000"),
+        ];
+        let output = AppSettingsConverter
+            .convert(&vars, &ConvertOptions::default())
+            .unwrap();
+
+        let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(json["Logging"]["LogLevel"]["Default"], "Warning");
+        assert_eq!(json["AllowedHosts"], "*");
+        assert_eq!(
+            json["Kestrel"]["Endpoints"]["Http"]["Url"],
+            "http://0.0.0.0:5000"
+        );
+    }
+}
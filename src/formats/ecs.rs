@@ -0,0 +1,88 @@
+// ============================================================================
+// formats/ecs.rs
+// ============================================================================
+
+use super::kubernetes::is_secret;
+use crate::core::converter::{ConvertOptions, Converter, Variable};
+use anyhow::Result;
+use serde_json::json;
+
+/// `environment` and `secrets` arrays for an ECS container definition.
+///
+/// Plain values go in `environment`. Secret values (see [`is_secret`]) are
+/// not written: each `secrets` entry points at its key in the Secrets
+/// Manager secret `secret_name`, as stored by `evnx convert --to
+/// aws-secrets`. The ARNs keep `${AWS_REGION}` and `${AWS_ACCOUNT_ID}` for
+/// `envsubst` to fill in.
+pub struct EcsTaskConverter {
+    pub secret_name: String,
+}
+
+impl Default for EcsTaskConverter {
+    fn default() -> Self {
+        Self {
+            secret_name: "app-secrets".to_string(),
+        }
+    }
+}
+
+impl Converter for EcsTaskConverter {
+    fn convert(&self, vars: &[Variable], options: &ConvertOptions) -> Result<String> {
+        let filtered = options.filter_vars(vars);
+
+        let mut environment = Vec::new();
+        let mut secrets = Vec::new();
+        for var in &filtered {
+            let key = options.transform_key(&var.key);
+            if is_secret(var) {
+                let arn = format!(
+                    "arn:aws:secretsmanager:${{AWS_REGION}}:${{AWS_ACCOUNT_ID}}:secret:{}:{}::",
+                    self.secret_name, key
+                );
+                secrets.push(json!({"name": key, "valueFrom": arn}));
+            } else {
+                let value = options.transform_value(&var.value);
+                environment.push(json!({"name": key, "value": value}));
+            }
+        }
+
+        let definition = json!({"environment": environment, "secrets": secrets});
+        Ok(serde_json::to_string_pretty(&definition)?)
+    }
+
+    fn name(&self) -> &str {
+        "ecs"
+    }
+
+    fn description(&self) -> &str {
+        "AWS ECS container definition environment/secrets"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ecs_environment_and_secrets() {
+        let vars = vec![
+            Variable::new("LOG_LEVEL", "info"),
+            Variable::new("DB_PASSWORD", "hunter2"),
+        ];
+        let output = EcsTaskConverter::default()
+            .convert(&vars, &ConvertOptions::default())
+            .unwrap();
+        assert!(!output.contains("hunter2"));
+
+        let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(
+            parsed["environment"],
+            json!([{"name": "LOG_LEVEL", "value": "info"}])
+        );
+        assert_eq!(parsed["secrets"][0]["name"], "DB_PASSWORD");
+        assert_eq!(
+            parsed["secrets"][0]["valueFrom"],
+            "arn:aws:secretsmanager:${AWS_REGION}:${AWS_ACCOUNT_ID}:secret:app-secrets:DB_PASSWORD::"
+        );
+    }
+}
@@ -0,0 +1,86 @@
+// ============================================================================
+// formats/fly.rs
+// ============================================================================
+
+use crate::core::converter::{ConvertOptions, Converter, Variable};
+use anyhow::{bail, Result};
+
+/// Input for `fly secrets import`: `NAME=VALUE` lines, read literally, with
+/// multiline values wrapped in `"""`.
+///
+/// ```bash
+/// evnx convert --to fly | fly secrets import
+/// ```
+pub struct FlySecretsConverter;
+
+impl Converter for FlySecretsConverter {
+    fn convert(&self, vars: &[Variable], options: &ConvertOptions) -> Result<String> {
+        let filtered = options.filter_vars(vars);
+
+        let mut output = String::new();
+        for var in &filtered {
+            let key = options.transform_key(&var.key);
+            let value = options.transform_value(&var.value);
+
+            // flyctl reads lines, so carriage returns are lost, and a line
+            // ending in `"""` would end a multiline value early.
+            if value.contains('\r') {
+                bail!(
+                    "{} contains a carriage return, which fly cannot import",
+                    key
+                );
+            }
+            if !value.contains('\n') {
+                if value.starts_with("\"\"\"") {
+                    bail!(
+                        "{} starts with \"\"\", which fly reads as a multiline value",
+                        key
+                    );
+                }
+                output.push_str(&format!("{}={}\n", key, value));
+            } else if value.lines().any(|line| line.ends_with("\"\"\"")) {
+                bail!(
+                    "{} has a line ending in \"\"\", which fly cannot import",
+                    key
+                );
+            } else {
+                output.push_str(&format!("{}=\"\"\"{}\"\"\"\n", key, value));
+            }
+        }
+
+        Ok(output)
+    }
+
+    fn name(&self) -> &str {
+        "fly"
+    }
+
+    fn description(&self) -> &str {
+        "Fly.io secrets (fly secrets import)"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fly_secrets() {
+        let vars = vec![
+            Variable::new("API_KEY", "it's \"raw\""),
+            Variable::new("PEM", "-----BEGIN-----\nabc\n-----END-----\n"),
+        ];
+        let output = FlySecretsConverter
+            .convert(&vars, &ConvertOptions::default())
+            .unwrap();
+        assert_eq!(
+            output,
+            "API_KEY=it's \"raw\"\nPEM=\"\"\"-----BEGIN-----\nabc\n-----END-----\n\"\"\"\n"
+        );
+
+        let vars = vec![Variable::new("BAD", "a\"\"\"\nb")];
+        assert!(FlySecretsConverter
+            .convert(&vars, &ConvertOptions::default())
+            .is_err());
+    }
+}
@@ -2,9 +2,9 @@
 // GCP Secret Manager
 // ============================================================================
 
-use crate::core::converter::{ConvertOptions, Converter};
+use super::escape::{hash_comments, shell_quote};
+use crate::core::converter::{ConvertOptions, Converter, Variable};
 use anyhow::Result;
-use std::collections::HashMap;
 
 pub struct GcpSecretConverter {
     pub project_id: String,
@@ -19,24 +19,32 @@ impl Default for GcpSecretConverter {
 }
 
 impl Converter for GcpSecretConverter {
-    fn convert(&self, vars: &HashMap<String, String>, options: &ConvertOptions) -> Result<String> {
+    fn convert(&self, vars: &[Variable], options: &ConvertOptions) -> Result<String> {
         let filtered = options.filter_vars(vars);
 
         let mut output = String::new();
         output.push_str("#!/bin/bash\n");
         output.push_str("# Upload secrets to GCP Secret Manager\n");
-        output.push_str(&format!("# Project: {}\n\n", self.project_id));
+        output.push_str(&format!(
+            "# Project: {}\n\n",
+            self.project_id.replace('\n', " ")
+        ));
 
-        for (k, v) in filtered.iter() {
-            let key = options.transform_key(k);
-            let value = options.transform_value(v);
+        for (i, var) in filtered.iter().enumerate() {
+            let key = options.transform_key(&var.key);
+            let value = options.transform_value(&var.value);
+            output.push_str(&hash_comments(&filtered, i, ""));
 
             // Convert to lowercase with hyphens (GCP naming)
             let secret_name = key.to_lowercase().replace('_', "-");
 
+            // `printf '%s'` rather than `echo`: no trailing newline added to
+            // the secret, and no backslash interpretation.
             output.push_str(&format!(
-                "echo '{}' | gcloud secrets create {} --data-file=- --project={}\n",
-                value, secret_name, self.project_id
+                "printf '%s' {} | gcloud secrets create {} --data-file=- --project={}\n",
+                shell_quote(&value),
+                shell_quote(&secret_name),
+                shell_quote(&self.project_id)
             ));
         }
 
@@ -58,11 +66,7 @@ mod tests {
 
     #[test]
     fn test_gcp_converter() {
-        let mut vars = HashMap::new();
-        vars.insert(
-            "DATABASE_URL".to_string(),
-            "postgresql://localhost".to_string(),
-        );
+        let vars = vec![Variable::new("DATABASE_URL", "postgresql://localhost")];
 
         let converter = GcpSecretConverter::default();
         let result = converter
@@ -2,9 +2,8 @@
 // formats/github.rs
 // ============================================================================
 
-use crate::core::converter::{ConvertOptions, Converter};
+use crate::core::converter::{ConvertOptions, Converter, Variable};
 use anyhow::Result;
-use std::collections::HashMap;
 
 pub struct GitHubActionsConverter {
     pub separator: String,
@@ -19,16 +18,16 @@ impl Default for GitHubActionsConverter {
 }
 
 impl Converter for GitHubActionsConverter {
-    fn convert(&self, vars: &HashMap<String, String>, options: &ConvertOptions) -> Result<String> {
+    fn convert(&self, vars: &[Variable], options: &ConvertOptions) -> Result<String> {
         let filtered = options.filter_vars(vars);
 
         let mut output = String::new();
         output.push_str("Paste these into Settings → Secrets and variables → Actions:\n\n");
 
         let count = filtered.len();
-        for (i, (k, v)) in filtered.iter().enumerate() {
-            let key = options.transform_key(k);
-            let value = options.transform_value(v);
+        for (i, var) in filtered.iter().enumerate() {
+            let key = options.transform_key(&var.key);
+            let value = options.transform_value(&var.value);
 
             output.push_str(&format!("Name: {}\n", key));
             output.push_str(&format!("Value: {}\n", value));
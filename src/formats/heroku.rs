@@ -2,9 +2,9 @@
 //!
 //! Converts environment variables to Heroku CLI commands for setting config vars.
 
-use crate::core::converter::{ConvertOptions, Converter};
+use super::escape::{hash_comments, shell_quote};
+use crate::core::converter::{ConvertOptions, Converter, Variable};
 use anyhow::Result;
-use std::collections::HashMap;
 
 /// Heroku Config Vars converter
 ///
@@ -32,7 +32,7 @@ impl HerokuConfigConverter {
 }
 
 impl Converter for HerokuConfigConverter {
-    fn convert(&self, vars: &HashMap<String, String>, options: &ConvertOptions) -> Result<String> {
+    fn convert(&self, vars: &[Variable], options: &ConvertOptions) -> Result<String> {
         let filtered = options.filter_vars(vars);
         let mut output = String::new();
 
@@ -55,7 +55,7 @@ impl Converter for HerokuConfigConverter {
 
         // App name flag
         let app_flag = if let Some(ref app) = self.app_name {
-            format!(" --app {}", app)
+            format!(" --app {}", shell_quote(app))
         } else {
             " --app ${HEROKU_APP:-YOUR_APP_NAME}".to_string()
         };
@@ -65,24 +65,21 @@ impl Converter for HerokuConfigConverter {
         // Generate config:set commands
         output.push_str("echo \"Setting Heroku config vars...\"\n\n");
 
-        for (key, value) in filtered.iter() {
-            let transformed_key = options.transform_key(key);
-            let transformed_value = options.transform_value(value);
+        for (i, var) in filtered.iter().enumerate() {
+            let transformed_key = options.transform_key(&var.key);
+            let transformed_value = options.transform_value(&var.value);
+            output.push_str(&hash_comments(&filtered, i, ""));
 
-            // Escape value for shell
-            let escaped_value = transformed_value
-                .replace('\\', "\\\\")
-                .replace('"', "\\\"")
-                .replace('$', "\\$")
-                .replace('`', "\\`");
+            let assignment = format!("{}={}", transformed_key, transformed_value);
 
             output.push_str(&format!(
-                "heroku config:set {}=\"{}\"{}",
-                transformed_key, escaped_value, app_flag
+                "heroku config:set {}{}",
+                shell_quote(&assignment),
+                app_flag
             ));
-            output.push_str(" || echo \"Failed to set ");
-            output.push_str(&transformed_key);
-            output.push_str("\"\n");
+            output.push_str(" || echo ");
+            output.push_str(&shell_quote(&format!("Failed to set {}", transformed_key)));
+            output.push('\n');
         }
 
         output.push_str("\necho \"✓ Config vars set successfully\"\n");
@@ -127,9 +124,10 @@ mod tests {
 
     #[test]
     fn test_convert_basic() {
-        let mut vars = HashMap::new();
-        vars.insert("DATABASE_URL".to_string(), "postgresql://...".to_string());
-        vars.insert("SECRET_KEY".to_string(), "secret123".to_string());
+        let vars = vec![
+            Variable::new("DATABASE_URL", "postgresql://..."),
+            Variable::new("SECRET_KEY", "secret123"),
+        ];
 
         let converter = HerokuConfigConverter::new("test-app".to_string());
         let options = ConvertOptions::default();
@@ -143,8 +141,7 @@ mod tests {
 
     #[test]
     fn test_convert_without_app() {
-        let mut vars = HashMap::new();
-        vars.insert("KEY".to_string(), "value".to_string());
+        let vars = vec![Variable::new("KEY", "value")];
 
         let converter = HerokuConfigConverter::without_app();
         let options = ConvertOptions::default();
@@ -155,19 +152,14 @@ mod tests {
 
     #[test]
     fn test_convert_with_special_chars() {
-        let mut vars = HashMap::new();
-        vars.insert(
-            "KEY".to_string(),
-            "value with \"quotes\" and $vars".to_string(),
-        );
+        let vars = vec![Variable::new("KEY", "value with \"quotes\" and $vars")];
 
         let converter = HerokuConfigConverter::default();
         let options = ConvertOptions::default();
         let result = converter.convert(&vars, &options).unwrap();
 
-        // Should escape special characters
-        assert!(result.contains("\\\""));
-        assert!(result.contains("\\$"));
+        // Single-quoted, so nothing is expanded
+        assert!(result.contains(r#"heroku config:set 'KEY=value with "quotes" and $vars'"#));
     }
 
     #[test]
@@ -0,0 +1,1067 @@
+//! Reading other formats back into variables (`evnx import`).
+//!
+//! The reverse of the converters, for the formats they emit and a few that
+//! other tools produce: JSON objects (Heroku `config --json`, AWS Secrets
+//! Manager, Railway, and the per-key objects of Doppler and Vercel), YAML
+//! (flat maps, Kubernetes Secrets and ConfigMaps, Compose `environment:` and
+//! `env_file`), Terraform `.tfvars`, shell `export` scripts and dotenv files
+//! such as the ones `vercel env pull` writes.
+//!
+//! Order is kept, and so are comments where the format has them (dotenv,
+//! shell, tfvars), following the same description and section rules as
+//! [`Document::annotations`].
+
+use anyhow::{anyhow, bail, Context, Result};
+use base64::{engine::general_purpose, Engine as _};
+use serde::de::{Deserialize, Deserializer, MapAccess, Visitor};
+use serde_yaml::{Mapping, Value};
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::marker::PhantomData;
+use std::path::Path;
+
+use crate::core::converter::{document_variables, Variable};
+use crate::core::{Document, Entry, KeyPath, Parser, ParserConfig};
+
+/// Source format of an import.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportFormat {
+    Dotenv,
+    Json,
+    Yaml,
+    Tfvars,
+    Shell,
+}
+
+impl ImportFormat {
+    /// Parse a `--from` name. The names `convert --to` accepts map to the
+    /// format that reads their output back.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "dotenv" | "env" | "vercel" => Some(Self::Dotenv),
+            "json" | "heroku" | "aws" | "aws-secrets" | "railway" | "doppler" => Some(Self::Json),
+            "yaml" | "yml" | "kubernetes" | "k8s" | "configmap" | "docker-compose" | "compose"
+            | "docker" => Some(Self::Yaml),
+            "terraform" | "tfvars" | "tf" => Some(Self::Tfvars),
+            "shell" | "bash" | "sh" | "export" => Some(Self::Shell),
+            _ => None,
+        }
+    }
+
+    /// Guess the format from the file name, then from the content.
+    pub fn detect(path: &str, content: &str) -> Self {
+        let name = Path::new(path)
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or_default()
+            .to_lowercase();
+
+        if name.ends_with(".json") {
+            return Self::Json;
+        }
+        if name.ends_with(".yaml") || name.ends_with(".yml") {
+            return Self::Yaml;
+        }
+        if name.ends_with(".tfvars") || name.ends_with(".tf") {
+            return Self::Tfvars;
+        }
+        if name.ends_with(".sh") || name.ends_with(".bash") {
+            return Self::Shell;
+        }
+        if name.starts_with(".env") || name.ends_with(".env") {
+            return Self::Dotenv;
+        }
+
+        let lines = || content.lines().map(str::trim_start);
+        if content.trim_start().starts_with('{') {
+            Self::Json
+        } else if content.starts_with("#!") || lines().any(|l| l.starts_with("export ")) {
+            Self::Shell
+        } else if lines().any(|l| {
+            ["apiVersion:", "kind:", "services:", "environment:"]
+                .iter()
+                .any(|p| l.starts_with(p))
+        }) {
+            Self::Yaml
+        } else {
+            Self::Dotenv
+        }
+    }
+}
+
+/// Where an import reads from and how it maps keys.
+#[derive(Debug, Clone, Copy)]
+pub struct ImportOptions<'a> {
+    /// Directory Compose `env_file` paths are relative to
+    pub base_dir: &'a Path,
+    /// Compose service to read when several set variables
+    pub service: Option<&'a str>,
+    /// Flatten nested JSON/YAML with this mapping instead of rejecting it
+    pub nest: Option<&'a KeyPath>,
+}
+
+impl Default for ImportOptions<'_> {
+    fn default() -> Self {
+        Self {
+            base_dir: Path::new("."),
+            service: None,
+            nest: None,
+        }
+    }
+}
+
+/// Variables in `content`, in source order.
+pub fn import(
+    format: ImportFormat,
+    content: &str,
+    options: &ImportOptions,
+) -> Result<Vec<Variable>> {
+    let content = without_banner(content);
+    match format {
+        ImportFormat::Dotenv => dotenv(&content),
+        ImportFormat::Json => json(&content, options.nest),
+        ImportFormat::Yaml => yaml(&content, options),
+        ImportFormat::Tfvars => tfvars(&content),
+        ImportFormat::Shell => shell(&content),
+    }
+}
+
+/// `content` without a shebang and the banner lines generators put at the
+/// top, which would otherwise become the first variable's description.
+fn without_banner(content: &str) -> String {
+    const BANNERS: &[&str] = &["# Generated by evnx", "# Created by Vercel CLI"];
+
+    let mut lines = content.split_inclusive('\n').peekable();
+    while let Some(line) = lines.peek() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("#!") || BANNERS.contains(&trimmed) {
+            lines.next();
+        } else {
+            break;
+        }
+    }
+    lines.collect()
+}
+
+/// Add `var`, or replace the value of the variable already holding its key.
+fn upsert(vars: &mut Vec<Variable>, var: Variable) {
+    match vars.iter_mut().find(|v| v.key == var.key) {
+        Some(existing) => existing.value = var.value,
+        None => vars.push(var),
+    }
+}
+
+// ─────────────────────────────────────────────────────────────
+// dotenv
+// ─────────────────────────────────────────────────────────────
+
+fn dotenv(content: &str) -> Result<Vec<Variable>> {
+    // Imported values are kept literally; `.env` readers expand them later.
+    let parser = Parser::new(ParserConfig {
+        allow_expansion: false,
+        ..Default::default()
+    });
+    let values = parser.parse_content(content)?;
+    Ok(document_variables(&values, &Document::parse(content), None))
+}
+
+// ─────────────────────────────────────────────────────────────
+// JSON
+// ─────────────────────────────────────────────────────────────
+
+/// A map deserialized into a list, keeping the source order
+/// (`serde_json::Map` sorts its keys).
+struct Ordered<V>(Vec<(String, V)>);
+
+impl<'de, V: Deserialize<'de>> Deserialize<'de> for Ordered<V> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct OrderedVisitor<V>(PhantomData<V>);
+
+        impl<'de, V: Deserialize<'de>> Visitor<'de> for OrderedVisitor<V> {
+            type Value = Ordered<V>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("an object")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut entries = Vec::new();
+                while let Some(entry) = map.next_entry()? {
+                    entries.push(entry);
+                }
+                Ok(Ordered(entries))
+            }
+        }
+
+        deserializer.deserialize_map(OrderedVisitor(PhantomData))
+    }
+}
+
+fn json(content: &str, nest: Option<&KeyPath>) -> Result<Vec<Variable>> {
+    // Read as YAML values, whose mappings keep their order at every level.
+    let Ordered(entries) = serde_json::from_str::<Ordered<Value>>(content)
+        .context("Expected a JSON object of variables")?;
+
+    let mut vars = Vec::new();
+    for (key, value) in entries {
+        match (&value, nest) {
+            (Value::Mapping(_) | Value::Sequence(_), Some(path)) => {
+                flatten(path, &mut vec![key], &value, &mut vars)?
+            }
+            // Doppler and Vercel wrap each value in an object.
+            (Value::Mapping(object), None) => match object.get("value") {
+                Some(Value::String(s)) => vars.push(Variable::new(key, s.clone())),
+                _ => bail!(
+                    "{} holds a nested object; flatten it with --nest or import a flat object",
+                    key
+                ),
+            },
+            (Value::Sequence(_), None) => bail!(
+                "{} holds an array; flatten it with --nest or import a flat object",
+                key
+            ),
+            _ => vars.push(Variable::new(key, scalar(&value)?)),
+        }
+    }
+    Ok(vars)
+}
+
+/// Variables of the leaves of `value`, at `prefix`, keyed with `path`.
+/// Arrays contribute their indices as segments.
+fn flatten(
+    path: &KeyPath,
+    prefix: &mut Vec<String>,
+    value: &Value,
+    vars: &mut Vec<Variable>,
+) -> Result<()> {
+    match value {
+        Value::Mapping(map) => {
+            for (key, child) in map {
+                prefix.push(scalar(key)?);
+                flatten(path, prefix, child, vars)?;
+                prefix.pop();
+            }
+        }
+        Value::Sequence(items) => {
+            for (i, child) in items.iter().enumerate() {
+                prefix.push(i.to_string());
+                flatten(path, prefix, child, vars)?;
+                prefix.pop();
+            }
+        }
+        Value::Tagged(_) => bail!("{} holds a tagged value", prefix.join(".")),
+        _ => upsert(vars, Variable::new(path.join(prefix), scalar(value)?)),
+    }
+    Ok(())
+}
+
+// ─────────────────────────────────────────────────────────────
+// YAML: flat maps, Kubernetes, Compose
+// ─────────────────────────────────────────────────────────────
+
+fn yaml(content: &str, options: &ImportOptions) -> Result<Vec<Variable>> {
+    let mut vars = Vec::new();
+
+    // Manifests often hold several documents; they are read in order.
+    for document in serde_yaml::Deserializer::from_str(content) {
+        let value = Value::deserialize(document).context("Invalid YAML")?;
+        let found = match &value {
+            Value::Null => continue,
+            Value::Mapping(_) => match options.nest {
+                Some(path) => {
+                    let mut found = Vec::new();
+                    flatten(path, &mut Vec::new(), &value, &mut found)?;
+                    found
+                }
+                None => yaml_document(&value, options)?,
+            },
+            _ => bail!("Expected a YAML mapping of variables"),
+        };
+        for var in found {
+            upsert(&mut vars, var);
+        }
+    }
+    Ok(vars)
+}
+
+fn yaml_document(value: &Value, options: &ImportOptions) -> Result<Vec<Variable>> {
+    let Some(map) = value.as_mapping() else {
+        bail!("Expected a YAML mapping of variables");
+    };
+    if let Some(kind) = map.get("kind") {
+        return match kind.as_str() {
+            Some("Secret") => kubernetes(map, true),
+            Some("ConfigMap") => kubernetes(map, false),
+            _ => bail!(
+                "Unsupported Kubernetes kind {}; expected a Secret or ConfigMap",
+                scalar(kind).unwrap_or_default()
+            ),
+        };
+    }
+    if let Some(services) = map.get("services") {
+        return compose(services, options.base_dir, options.service);
+    }
+    // A bare service definition, as `convert --to docker-compose` writes.
+    let is_service = map
+        .get("environment")
+        .is_some_and(|e| e.is_sequence() || e.is_mapping())
+        || map.contains_key("env_file");
+    if is_service {
+        return compose_service(map, options.base_dir);
+    }
+
+    map.iter()
+        .map(|(key, value)| {
+            let key = scalar(key)?;
+            let value = scalar(value)
+                .with_context(|| format!("Cannot import {}; flatten it with --nest", key))?;
+            Ok(Variable::new(key, value))
+        })
+        .collect()
+}
+
+/// Text of a YAML scalar; `null` is empty.
+fn scalar(value: &Value) -> Result<String> {
+    match value {
+        Value::String(s) => Ok(s.clone()),
+        Value::Number(n) => Ok(n.to_string()),
+        Value::Bool(b) => Ok(b.to_string()),
+        Value::Null => Ok(String::new()),
+        _ => bail!("expected a string, number or boolean, found a list or mapping"),
+    }
+}
+
+fn kubernetes(map: &Mapping, secret: bool) -> Result<Vec<Variable>> {
+    let mut vars = Vec::new();
+
+    let entries = |field: &str| -> Result<Vec<(String, String)>> {
+        match map.get(field) {
+            None | Some(Value::Null) => Ok(Vec::new()),
+            Some(Value::Mapping(m)) => m
+                .iter()
+                .map(|(k, v)| Ok((scalar(k)?, scalar(v)?)))
+                .collect(),
+            Some(_) => bail!("`{}` must be a mapping", field),
+        }
+    };
+
+    for (key, value) in entries("data")? {
+        let value = if secret {
+            let compact: String = value.split_whitespace().collect();
+            let bytes = general_purpose::STANDARD
+                .decode(compact)
+                .with_context(|| format!("{} in `data` is not valid base64", key))?;
+            String::from_utf8(bytes)
+                .map_err(|_| anyhow!("{} in `data` is binary, not text", key))?
+        } else {
+            value
+        };
+        upsert(&mut vars, Variable::new(key, value));
+    }
+    // `stringData` wins over `data`, as in the API server.
+    if secret {
+        for (key, value) in entries("stringData")? {
+            upsert(&mut vars, Variable::new(key, value));
+        }
+    }
+    Ok(vars)
+}
+
+fn compose(services: &Value, base_dir: &Path, service: Option<&str>) -> Result<Vec<Variable>> {
+    let services = services
+        .as_mapping()
+        .ok_or_else(|| anyhow!("`services` must be a mapping"))?;
+
+    let definition = match service {
+        Some(name) => services
+            .get(name)
+            .ok_or_else(|| anyhow!("No service `{}` in the Compose file", name))?,
+        None => {
+            let with_env: Vec<(&Value, &Value)> = services
+                .iter()
+                .filter(|(_, def)| {
+                    def.get("environment").is_some() || def.get("env_file").is_some()
+                })
+                .collect();
+            match with_env.as_slice() {
+                [(_, def)] => *def,
+                [] => bail!("No service in the Compose file sets environment variables"),
+                several => bail!(
+                    "Several services set environment variables ({}); choose one with --service",
+                    several
+                        .iter()
+                        .filter_map(|(name, _)| name.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            }
+        }
+    };
+
+    let definition = definition
+        .as_mapping()
+        .ok_or_else(|| anyhow!("The service definition must be a mapping"))?;
+    compose_service(definition, base_dir)
+}
+
+fn compose_service(definition: &Mapping, base_dir: &Path) -> Result<Vec<Variable>> {
+    let mut vars = Vec::new();
+
+    // `env_file` first: `environment` takes precedence, as in Compose.
+    if let Some(files) = definition.get("env_file") {
+        for (path, required) in env_files(files)? {
+            let full = base_dir.join(&path);
+            if !required && !full.exists() {
+                continue;
+            }
+            let content = fs::read_to_string(&full)
+                .with_context(|| format!("Failed to read env_file {}", full.display()))?;
+            for var in dotenv(&content)? {
+                upsert(&mut vars, var);
+            }
+        }
+    }
+
+    // Compose interpolates `$` in the file itself; `$$` is a literal `$`.
+    let literal = |value: &str| value.replace("$$", "$");
+    match definition.get("environment") {
+        None | Some(Value::Null) => {}
+        Some(Value::Sequence(items)) => {
+            for item in items {
+                let item = scalar(item)?;
+                let (key, value) = item.split_once('=').unwrap_or((&item, ""));
+                upsert(&mut vars, Variable::new(key, literal(value)));
+            }
+        }
+        Some(Value::Mapping(map)) => {
+            for (key, value) in map {
+                upsert(
+                    &mut vars,
+                    Variable::new(scalar(key)?, literal(&scalar(value)?)),
+                );
+            }
+        }
+        Some(_) => bail!("`environment` must be a list or a mapping"),
+    }
+    Ok(vars)
+}
+
+/// `(path, required)` of each `env_file` entry.
+fn env_files(value: &Value) -> Result<Vec<(String, bool)>> {
+    let entry = |item: &Value| -> Result<(String, bool)> {
+        match item {
+            Value::String(path) => Ok((path.clone(), true)),
+            Value::Mapping(m) => {
+                let path = m
+                    .get("path")
+                    .and_then(Value::as_str)
+                    .ok_or_else(|| anyhow!("env_file entries need a `path`"))?;
+                let required = m.get("required").and_then(Value::as_bool).unwrap_or(true);
+                Ok((path.to_string(), required))
+            }
+            _ => bail!("env_file entries must be paths"),
+        }
+    };
+
+    match value {
+        Value::Sequence(items) => items.iter().map(entry).collect(),
+        other => Ok(vec![entry(other)?]),
+    }
+}
+
+// ─────────────────────────────────────────────────────────────
+// Terraform .tfvars
+// ─────────────────────────────────────────────────────────────
+
+/// Attributes of a `.tfvars` file. Strings (quoted or heredoc), numbers,
+/// booleans and `null` are supported; lists and maps are rejected.
+fn tfvars(content: &str) -> Result<Vec<Variable>> {
+    // Comments and blank lines become trivia of a Document, so the usual
+    // description and section rules apply.
+    let mut entries = Vec::new();
+    let mut values = HashMap::new();
+
+    let mut lines = content.lines().enumerate();
+    while let Some((i, line)) = lines.next() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            entries.push(Entry::Trivia(String::new()));
+            continue;
+        }
+        if let Some(comment) = trimmed
+            .strip_prefix('#')
+            .or_else(|| trimmed.strip_prefix("//"))
+        {
+            entries.push(Entry::Trivia(format!("#{}", comment)));
+            continue;
+        }
+        if trimmed.starts_with("/*") {
+            if !trimmed.contains("*/") {
+                lines.by_ref().find(|(_, l)| l.contains("*/"));
+            }
+            entries.push(Entry::Trivia(trimmed.to_string()));
+            continue;
+        }
+
+        let at = || format!("line {}", i + 1);
+        let (key, expr) = trimmed
+            .split_once('=')
+            .ok_or_else(|| anyhow!("{}: expected `name = value`", at()))?;
+        let key = key.trim().trim_matches('"').to_string();
+        let expr = expr.trim();
+
+        let value = if let Some(rest) = expr.strip_prefix("<<") {
+            let (indented, delimiter) = match rest.strip_prefix('-') {
+                Some(d) => (true, d.trim()),
+                None => (false, rest.trim()),
+            };
+            let mut body = Vec::new();
+            loop {
+                let Some((_, next)) = lines.next() else {
+                    bail!("{}: heredoc {} is not closed", at(), delimiter);
+                };
+                if next.trim() == delimiter {
+                    break;
+                }
+                body.push(next);
+            }
+            heredoc(&body, indented)
+        } else if let Some(quoted) = expr.strip_prefix('"') {
+            hcl_quoted(quoted).with_context(at)?
+        } else if expr.starts_with(['[', '{']) {
+            bail!(
+                "{}: {} is a list or map; only strings, numbers and booleans can be imported",
+                at(),
+                key
+            );
+        } else {
+            let literal = expr.split('#').next().unwrap_or_default();
+            let literal = literal.split("//").next().unwrap_or_default().trim();
+            if literal == "null" {
+                String::new()
+            } else {
+                literal.to_string()
+            }
+        };
+
+        entries.push(Entry::Var {
+            key: key.clone(),
+            value: String::new(),
+            text: line.to_string(),
+        });
+        values.insert(key, value);
+    }
+
+    let doc = Document {
+        entries,
+        trailing_newline: true,
+    };
+    Ok(document_variables(&values, &doc, None))
+}
+
+/// Text of a heredoc body; `<<-` strips the indentation common to all lines.
+fn heredoc(body: &[&str], indented: bool) -> String {
+    let strip = if indented {
+        body.iter()
+            .filter(|l| !l.trim().is_empty())
+            .map(|l| l.len() - l.trim_start().len())
+            .min()
+            .unwrap_or(0)
+    } else {
+        0
+    };
+
+    let mut text = String::new();
+    for line in body {
+        text.push_str(line.get(strip..).unwrap_or_default());
+        text.push('\n');
+    }
+    text.replace("$${", "${").replace("%%{", "%{")
+}
+
+/// Text of a quoted HCL string, given what follows the opening quote.
+fn hcl_quoted(rest: &str) -> Result<String> {
+    let chars: Vec<char> = rest.chars().collect();
+    let mut out = String::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        match chars[i] {
+            '"' => {
+                let after: String = chars[i + 1..].iter().collect();
+                let after = after.trim();
+                if !(after.is_empty() || after.starts_with('#') || after.starts_with("//")) {
+                    bail!("unexpected `{}` after the string", after);
+                }
+                return Ok(out);
+            }
+            '\\' => {
+                let escape = chars.get(i + 1).copied();
+                i += 2;
+                match escape {
+                    Some('n') => out.push('\n'),
+                    Some('r') => out.push('\r'),
+                    Some('t') => out.push('\t'),
+                    Some('"') => out.push('"'),
+                    Some('\\') => out.push('\\'),
+                    Some(u @ ('u' | 'U')) => {
+                        let len = if u == 'u' { 4 } else { 8 };
+                        let hex: String = chars.iter().skip(i).take(len).collect();
+                        let c = u32::from_str_radix(&hex, 16)
+                            .ok()
+                            .and_then(char::from_u32)
+                            .ok_or_else(|| anyhow!("invalid escape \\{}{}", u, hex))?;
+                        out.push(c);
+                        i += len;
+                    }
+                    Some(other) => bail!("invalid escape \\{}", other),
+                    None => bail!("unterminated string"),
+                }
+            }
+            c @ ('$' | '%') if chars.get(i + 1) == Some(&c) && chars.get(i + 2) == Some(&'{') => {
+                out.push(c);
+                out.push('{');
+                i += 3;
+            }
+            '$' | '%' if chars.get(i + 1) == Some(&'{') => {
+                bail!("template expressions (`${{...}}`, `%{{...}}`) cannot be imported")
+            }
+            c => {
+                out.push(c);
+                i += 1;
+            }
+        }
+    }
+    bail!("unterminated string")
+}
+
+// ─────────────────────────────────────────────────────────────
+// Shell scripts
+// ─────────────────────────────────────────────────────────────
+
+/// `KEY=value` and `export KEY=value` lines of a shell script; other
+/// commands are ignored.
+fn shell(content: &str) -> Result<Vec<Variable>> {
+    let mut entries = Vec::new();
+    let mut values = HashMap::new();
+    let mut rest = content;
+    let mut line_number = 1;
+
+    while !rest.is_empty() {
+        let (line, after) = rest.split_once('\n').unwrap_or((rest, ""));
+        let trimmed = line.trim_start();
+        let assignment = trimmed
+            .strip_prefix("export ")
+            .map(str::trim_start)
+            .unwrap_or(trimmed);
+
+        let key = assignment
+            .split_once('=')
+            .map(|(key, _)| key)
+            .filter(|key| !trimmed.starts_with('#') && is_identifier(key));
+
+        let Some(key) = key else {
+            entries.push(Entry::Trivia(line.to_string()));
+            rest = after;
+            line_number += 1;
+            continue;
+        };
+
+        let start = line.find('=').unwrap_or_default() + 1;
+        let (value, consumed) = shell_word(&rest[start..])
+            .with_context(|| format!("line {}: cannot read the value of {}", line_number, key))?;
+        let text = &rest[..start + consumed];
+        line_number += text.matches('\n').count();
+
+        entries.push(Entry::Var {
+            key: key.to_string(),
+            value: String::new(),
+            text: text.trim_end_matches('\n').to_string(),
+        });
+        values.insert(key.to_string(), value);
+        rest = &rest[start + consumed..];
+    }
+
+    let doc = Document {
+        entries,
+        trailing_newline: true,
+    };
+    Ok(document_variables(&values, &doc, None))
+}
+
+/// Whether `key` is a valid variable name.
+pub fn is_identifier(key: &str) -> bool {
+    let mut chars = key.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Value of the POSIX shell word at the start of `input`, and the number of
+/// bytes read up to and including the end of its line. Quotes are removed
+/// and escapes applied; `$` expansions are kept literally. Only a comment
+/// may follow the word.
+fn shell_word(input: &str) -> Result<(String, usize)> {
+    let mut out = String::new();
+    let mut chars = input.char_indices();
+
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\'' => loop {
+                match chars.next() {
+                    Some((_, '\'')) => break,
+                    Some((_, c)) => out.push(c),
+                    None => bail!("unterminated single quote"),
+                }
+            },
+            '"' => loop {
+                match chars.next() {
+                    Some((_, '"')) => break,
+                    Some((_, '\\')) => match chars.next() {
+                        Some((_, c @ ('$' | '`' | '"' | '\\'))) => out.push(c),
+                        Some((_, '\n')) => {}
+                        Some((_, c)) => {
+                            out.push('\\');
+                            out.push(c);
+                        }
+                        None => bail!("unterminated double quote"),
+                    },
+                    Some((_, c)) => out.push(c),
+                    None => bail!("unterminated double quote"),
+                }
+            },
+            '\\' => match chars.next() {
+                Some((_, '\n')) | None => {}
+                Some((_, c)) => out.push(c),
+            },
+            '\n' => return Ok((out, i + 1)),
+            c if c.is_whitespace() || c == ';' => {
+                let end = input[i..].find('\n').map_or(input.len(), |n| i + n + 1);
+                let trailing = input[i..end].trim_matches(|c: char| c.is_whitespace() || c == ';');
+                if !trailing.is_empty() && !trailing.starts_with('#') {
+                    bail!("unexpected `{}` after the value", trailing);
+                }
+                return Ok((out, end));
+            }
+            c => out.push(c),
+        }
+    }
+    Ok((out, input.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::converter::{ConvertOptions, Converter};
+    use crate::core::SegmentCase;
+    use crate::formats::escape::tests::tricky_values;
+    use crate::formats::*;
+
+    fn read(format: ImportFormat, content: &str) -> Vec<Variable> {
+        import(format, content, &ImportOptions::default()).unwrap()
+    }
+
+    fn pairs(vars: &[Variable]) -> Vec<(String, String)> {
+        vars.iter()
+            .map(|v| (v.key.clone(), v.value.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_converter_output_round_trips() {
+        let vars: Vec<Variable> = tricky_values()
+            .into_iter()
+            .enumerate()
+            .map(|(i, v)| Variable::new(format!("KEY_{}", i), v))
+            .collect();
+        let expected = pairs(&vars);
+        let base64 = ConvertOptions {
+            base64: true,
+            ..Default::default()
+        };
+
+        let defaults = ConvertOptions::default();
+        let cases: Vec<(&dyn Converter, ImportFormat)> = vec![
+            (&JsonConverter, ImportFormat::Json),
+            (&AwsSecretsConverter, ImportFormat::Json),
+            (&DopplerConverter, ImportFormat::Json),
+            (&VercelEnvConverter, ImportFormat::Json),
+            (&YamlConverter, ImportFormat::Yaml),
+            (&DockerComposeConverter, ImportFormat::Yaml),
+            (&ShellExportConverter, ImportFormat::Shell),
+        ];
+        for (converter, format) in cases {
+            let output = converter.convert(&vars, &defaults).unwrap();
+            assert_eq!(
+                pairs(&read(format, &output)),
+                expected,
+                "{}",
+                converter.name()
+            );
+        }
+
+        let secret = KubernetesSecretConverter::default();
+        for options in [&defaults, &base64] {
+            let output = secret.convert(&vars, options).unwrap();
+            assert_eq!(pairs(&read(ImportFormat::Yaml, &output)), expected);
+        }
+
+        let output = TerraformConverter.convert(&vars, &defaults).unwrap();
+        let imported: Vec<(String, String)> = read(ImportFormat::Tfvars, &output)
+            .into_iter()
+            .map(|v| (v.key.to_uppercase(), v.value))
+            .collect();
+        assert_eq!(imported, expected);
+    }
+
+    #[test]
+    fn test_comments_survive_a_round_trip() {
+        let vars = vec![
+            Variable {
+                section: Some("Server".into()),
+                description: Some("Listen port".into()),
+                ..Variable::new("PORT", "3000")
+            },
+            Variable {
+                section: Some("Server".into()),
+                ..Variable::new("HOST", "0.0.0.0")
+            },
+        ];
+        for (converter, format) in [
+            (&ShellExportConverter as &dyn Converter, ImportFormat::Shell),
+            (&TerraformConverter, ImportFormat::Tfvars),
+        ] {
+            let output = converter
+                .convert(&vars, &ConvertOptions::default())
+                .unwrap();
+            let imported = read(format, &output);
+            let expected: Vec<Variable> = vars
+                .iter()
+                .map(|v| Variable {
+                    key: converter_key(converter, &v.key),
+                    ..v.clone()
+                })
+                .collect();
+            assert_eq!(imported, expected, "{}", converter.name());
+        }
+    }
+
+    fn converter_key(converter: &dyn Converter, key: &str) -> String {
+        if converter.name() == "terraform" {
+            key.to_lowercase()
+        } else {
+            key.to_string()
+        }
+    }
+
+    #[test]
+    fn test_kubernetes_configmap_and_multiple_documents() {
+        let content = "\
+apiVersion: v1
+kind: ConfigMap
+metadata:
+  name: app
+data:
+  LOG_LEVEL: info
+  WORKERS: \"4\"
+---
+apiVersion: v1
+kind: Secret
+metadata:
+  name: app
+data:
+  API_KEY: c2VjcmV0
+stringData:
+  LOG_LEVEL: debug
+";
+        assert_eq!(
+            pairs(&read(ImportFormat::Yaml, content)),
+            vec![
+                ("LOG_LEVEL".to_string(), "debug".to_string()),
+                ("WORKERS".to_string(), "4".to_string()),
+                ("API_KEY".to_string(), "secret".to_string()),
+            ]
+        );
+
+        let options = ImportOptions::default();
+        let err = import(ImportFormat::Yaml, "kind: Deployment\n", &options);
+        assert!(err.is_err());
+    }
+
+    #[test]
+    fn test_compose_services_and_env_file() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("web.env"),
+            "# From the env file\nPORT=80\nDEBUG=0\n",
+        )
+        .unwrap();
+        let content = "\
+services:
+  db:
+    image: postgres
+    environment:
+      POSTGRES_PASSWORD: example
+  web:
+    image: app
+    env_file:
+      - web.env
+      - path: missing.env
+        required: false
+    environment:
+      - DEBUG=1
+      - PRICE=$$5
+";
+        let mut options = ImportOptions {
+            base_dir: dir.path(),
+            ..Default::default()
+        };
+        let err = import(ImportFormat::Yaml, content, &options).unwrap_err();
+        assert!(err.to_string().contains("db, web"));
+
+        options.service = Some("web");
+        let vars = import(ImportFormat::Yaml, content, &options).unwrap();
+        assert_eq!(
+            pairs(&vars),
+            vec![
+                ("PORT".to_string(), "80".to_string()),
+                ("DEBUG".to_string(), "1".to_string()),
+                ("PRICE".to_string(), "$5".to_string()),
+            ]
+        );
+        assert_eq!(vars[0].description.as_deref(), Some("From the env file"));
+    }
+
+    #[test]
+    fn test_tfvars_literals() {
+        let content = "\
+# Generated by evnx
+
+// Region to deploy to
+region = \"eu-west-1\" # inline
+replicas = 3
+enabled = true
+unset = null
+cert = <<-EOT
+    line one
+      indented
+    EOT
+";
+        assert_eq!(
+            pairs(&read(ImportFormat::Tfvars, content)),
+            vec![
+                ("region".to_string(), "eu-west-1".to_string()),
+                ("replicas".to_string(), "3".to_string()),
+                ("enabled".to_string(), "true".to_string()),
+                ("unset".to_string(), String::new()),
+                ("cert".to_string(), "line one\n  indented\n".to_string()),
+            ]
+        );
+        assert_eq!(
+            read(ImportFormat::Tfvars, content)[0]
+                .description
+                .as_deref(),
+            Some("Region to deploy to")
+        );
+
+        let options = ImportOptions::default();
+        let err = import(ImportFormat::Tfvars, "tags = [\"a\"]\n", &options);
+        assert!(err.unwrap_err().to_string().contains("list or map"));
+    }
+
+    #[test]
+    fn test_shell_and_vercel_files() {
+        let script = "#!/bin/sh\nset -e\n# Token\nexport TOKEN=\"a\\$b\"'c d' # note\nNAME=plain;\necho done\n";
+        assert_eq!(
+            pairs(&read(ImportFormat::Shell, script)),
+            vec![
+                ("TOKEN".to_string(), "a$bc d".to_string()),
+                ("NAME".to_string(), "plain".to_string()),
+            ]
+        );
+        let options = ImportOptions::default();
+        assert!(import(ImportFormat::Shell, "A=b c\n", &options).is_err());
+
+        let pulled = "# Created by Vercel CLI\nVERCEL=\"1\"\nDB_URL=\"postgres://x\\ny\"\n";
+        let vars = read(ImportFormat::Dotenv, pulled);
+        assert_eq!(
+            pairs(&vars),
+            vec![
+                ("VERCEL".to_string(), "1".to_string()),
+                ("DB_URL".to_string(), "postgres://x\ny".to_string()),
+            ]
+        );
+        assert_eq!(vars[0].description, None);
+    }
+
+    #[test]
+    fn test_nested_documents_flatten() {
+        let dotnet = KeyPath::new("__", SegmentCase::Preserve);
+        let options = ImportOptions {
+            nest: Some(&dotnet),
+            ..Default::default()
+        };
+        let content = r#"{"Logging": {"LogLevel": {"Default": "Warning"}},
+                          "Hosts": ["a", "b"], "Port": 5000}"#;
+        assert_eq!(
+            pairs(&import(ImportFormat::Json, content, &options).unwrap()),
+            vec![
+                (
+                    "Logging__LogLevel__Default".to_string(),
+                    "Warning".to_string()
+                ),
+                ("Hosts__0".to_string(), "a".to_string()),
+                ("Hosts__1".to_string(), "b".to_string()),
+                ("Port".to_string(), "5000".to_string()),
+            ]
+        );
+        assert!(import(ImportFormat::Json, content, &ImportOptions::default()).is_err());
+
+        let spring = KeyPath::new("_", SegmentCase::Kebab);
+        let options = ImportOptions {
+            nest: Some(&spring),
+            ..Default::default()
+        };
+        let content =
+            "spring:\n  datasource:\n    url: jdbc:postgresql://db/app\n    max-pool-size: 10\n";
+        assert_eq!(
+            pairs(&import(ImportFormat::Yaml, content, &options).unwrap()),
+            vec![
+                (
+                    "SPRING_DATASOURCE_URL".to_string(),
+                    "jdbc:postgresql://db/app".to_string()
+                ),
+                (
+                    "SPRING_DATASOURCE_MAX_POOL_SIZE".to_string(),
+                    "10".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(ImportFormat::detect("secret.yaml", ""), ImportFormat::Yaml);
+        assert_eq!(
+            ImportFormat::detect("prod.tfvars", ""),
+            ImportFormat::Tfvars
+        );
+        assert_eq!(ImportFormat::detect(".env.local", ""), ImportFormat::Dotenv);
+        assert_eq!(
+            ImportFormat::detect("-", "{\"A\": \"1\"}"),
+            ImportFormat::Json
+        );
+        assert_eq!(
+            ImportFormat::detect("-", "export A=1\n"),
+            ImportFormat::Shell
+        );
+        assert_eq!(
+            ImportFormat::detect("-", "kind: Secret\n"),
+            ImportFormat::Yaml
+        );
+        assert_eq!(ImportFormat::from_name("heroku"), Some(ImportFormat::Json));
+        assert_eq!(ImportFormat::from_name("nope"), None);
+    }
+}
@@ -2,26 +2,30 @@
 // formats/json.rs
 // ============================================================================
 
-use crate::core::converter::{ConvertOptions, Converter};
+use super::escape::json_object;
+use crate::core::converter::{ConvertOptions, Converter, Variable};
 use anyhow::Result;
-use std::collections::HashMap;
 
 pub struct JsonConverter;
 
 impl Converter for JsonConverter {
-    fn convert(&self, vars: &HashMap<String, String>, options: &ConvertOptions) -> Result<String> {
+    fn convert(&self, vars: &[Variable], options: &ConvertOptions) -> Result<String> {
+        if let Some(doc) = options.nested(vars)? {
+            return Ok(serde_json::to_string_pretty(&doc)?);
+        }
+
         let filtered = options.filter_vars(vars);
 
-        let transformed: HashMap<String, String> = filtered
+        let transformed: Vec<(String, String)> = filtered
             .iter()
-            .map(|(k, v)| {
-                let key = options.transform_key(k);
-                let value = options.transform_value(v);
+            .map(|var| {
+                let key = options.transform_key(&var.key);
+                let value = options.transform_value(&var.value);
                 (key, value)
             })
             .collect();
 
-        let json = serde_json::to_string_pretty(&transformed)?;
+        let json = json_object(&transformed)?;
         Ok(json)
     }
 
@@ -37,12 +41,11 @@ impl Converter for JsonConverter {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::collections::HashMap;
+    use crate::core::{KeyPath, SegmentCase};
 
     #[test]
     fn test_json_converter() {
-        let mut vars = HashMap::new();
-        vars.insert("KEY".to_string(), "value".to_string());
+        let vars = vec![Variable::new("KEY", "value"), Variable::new("APP", "demo")];
 
         let converter = JsonConverter;
         let options = ConvertOptions::default();
@@ -50,5 +53,25 @@ mod tests {
 
         let json: serde_json::Value = serde_json::from_str(&result).unwrap();
         assert_eq!(json["KEY"], "value");
+        // Source order, not sorted
+        assert!(result.find("KEY").unwrap() < result.find("APP").unwrap());
+    }
+
+    #[test]
+    fn test_json_nested() {
+        let vars = vec![
+            Variable::new("Logging__LogLevel__Default", "Warning"),
+            Variable::new("Kestrel__Port", "5000"),
+        ];
+        let options = ConvertOptions {
+            nest: Some(KeyPath::new("__", SegmentCase::Preserve)),
+            coerce: true,
+            ..Default::default()
+        };
+        let result = JsonConverter.convert(&vars, &options).unwrap();
+
+        let json: serde_json::Value = serde_json::from_str(&result).unwrap();
+        assert_eq!(json["Logging"]["LogLevel"]["Default"], "Warning");
+        assert_eq!(json["Kestrel"]["Port"], 5000);
     }
 }
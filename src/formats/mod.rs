@@ -1,12 +1,23 @@
+pub mod escape;
+pub mod import;
+
 pub mod aws;
 pub mod docker;
 pub mod github;
 pub mod json;
 pub mod kubernetes;
+#[cfg(feature = "sealed-secrets")]
+pub mod sealed;
 pub mod shell;
 pub mod terraform;
 pub mod yaml;
 
+// Application config files
+pub mod dotnet;
+pub mod properties;
+pub mod python;
+pub mod spring;
+
 // Cloud providers
 pub mod azure;
 pub mod doppler;
@@ -15,19 +26,333 @@ pub mod heroku;
 pub mod railway;
 pub mod vercel;
 
+// Deployment platforms
+pub mod cloudflare;
+pub mod ecs;
+pub mod fly;
+pub mod netlify;
+pub mod nomad;
+pub mod render;
+pub mod systemd;
+
 // Re-export converters
 pub use aws::AwsSecretsConverter;
 pub use docker::DockerComposeConverter;
 pub use github::GitHubActionsConverter;
 pub use json::JsonConverter;
-pub use kubernetes::KubernetesSecretConverter;
-pub use shell::ShellExportConverter;
+pub use kubernetes::{KubernetesOutput, KubernetesSecretConverter};
+#[cfg(feature = "sealed-secrets")]
+pub use sealed::{SealedSecretConverter, SealingScope};
+pub use shell::{
+    CmdConverter, DirenvConverter, FishConverter, NushellConverter, PowerShellConverter,
+    ShellExportConverter,
+};
 pub use terraform::TerraformConverter;
 pub use yaml::YamlConverter;
 
+pub use dotnet::AppSettingsConverter;
+pub use properties::PropertiesConverter;
+pub use python::PythonSettingsConverter;
+pub use spring::SpringConverter;
+
 pub use azure::AzureKeyVaultConverter;
 pub use doppler::DopplerConverter;
 pub use gcp::GcpSecretConverter;
 pub use heroku::HerokuConfigConverter;
 pub use railway::RailwayConverter;
 pub use vercel::VercelEnvConverter;
+
+pub use cloudflare::{WranglerSecretsConverter, WranglerVarsConverter};
+pub use ecs::EcsTaskConverter;
+pub use fly::FlySecretsConverter;
+pub use netlify::{NetlifyCliConverter, NetlifyTomlConverter};
+pub use nomad::NomadConverter;
+pub use render::RenderConverter;
+pub use systemd::{SystemdDropInConverter, SystemdEnvFileConverter};
+
+#[cfg(test)]
+mod tests {
+    use super::escape::tests::{parse_hcl_string, tricky_values};
+    use super::*;
+    use crate::core::converter::{variables, ConvertOptions, Converter, Variable};
+    use crate::core::{KeyPath, SegmentCase};
+    use serde_json::json;
+    use std::collections::HashMap;
+    use std::process::Command;
+
+    /// `KEY_<i>` for every tricky value.
+    fn vars() -> HashMap<String, String> {
+        tricky_values()
+            .into_iter()
+            .enumerate()
+            .map(|(i, v)| (format!("KEY_{}", i), v))
+            .collect()
+    }
+
+    fn convert(converter: &dyn Converter) -> String {
+        converter
+            .convert(&variables(&vars()), &ConvertOptions::default())
+            .unwrap()
+    }
+
+    /// Run `script` with `sh`, after `prelude`, with stdout discarded; the
+    /// prelude's functions report NUL-separated fields on fd 3.
+    fn run_sh(prelude: &str, script: &str, epilogue: &str) -> Vec<String> {
+        let full = format!(
+            "exec 3>&1 1>/dev/null\n{}\n{}\n{}\n",
+            prelude, script, epilogue
+        );
+        let output = Command::new("sh").arg("-c").arg(&full).output().unwrap();
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        let mut fields: Vec<String> = String::from_utf8(output.stdout)
+            .unwrap()
+            .split('\0')
+            .map(str::to_string)
+            .collect();
+        assert_eq!(fields.pop().as_deref(), Some(""));
+        fields
+    }
+
+    fn pairs(fields: Vec<String>) -> HashMap<String, String> {
+        fields
+            .chunks(2)
+            .map(|c| (c[0].clone(), c[1].clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_json_formats_round_trip() {
+        for converter in [
+            &JsonConverter as &dyn Converter,
+            &AwsSecretsConverter,
+            &RailwayConverter,
+            &AppSettingsConverter,
+        ] {
+            let parsed: HashMap<String, String> =
+                serde_json::from_str(&convert(converter)).unwrap();
+            assert_eq!(parsed, vars(), "{}", converter.name());
+        }
+
+        for converter in [&DopplerConverter as &dyn Converter, &VercelEnvConverter] {
+            let parsed: HashMap<String, serde_json::Value> =
+                serde_json::from_str(&convert(converter)).unwrap();
+            let parsed: HashMap<String, String> = parsed
+                .into_iter()
+                .map(|(k, v)| (k, v["value"].as_str().unwrap().to_string()))
+                .collect();
+            assert_eq!(parsed, vars(), "{}", converter.name());
+        }
+    }
+
+    #[test]
+    fn test_yaml_formats_round_trip() {
+        let parsed: HashMap<String, String> =
+            serde_yaml::from_str(&convert(&YamlConverter)).unwrap();
+        assert_eq!(parsed, vars());
+
+        let secret: serde_yaml::Value =
+            serde_yaml::from_str(&convert(&KubernetesSecretConverter::default())).unwrap();
+        let parsed: HashMap<String, String> =
+            serde_yaml::from_value(secret["stringData"].clone()).unwrap();
+        assert_eq!(parsed, vars());
+        assert_eq!(secret["metadata"]["name"], "app-secrets");
+
+        #[derive(serde::Deserialize)]
+        struct Service {
+            environment: Vec<String>,
+        }
+        let service: Service = serde_yaml::from_str(&convert(&DockerComposeConverter)).unwrap();
+        let parsed: HashMap<String, String> = service
+            .environment
+            .iter()
+            .map(|item| {
+                let (k, v) = item.split_once('=').unwrap();
+                (k.to_string(), v.replace("$$", "$"))
+            })
+            .collect();
+        assert_eq!(parsed, vars());
+    }
+
+    #[test]
+    fn test_shell_formats_round_trip() {
+        let vars = vars();
+        let keys: Vec<&String> = vars.keys().collect();
+
+        let print_all = format!(
+            "printf '%s\\0' {} >&3",
+            keys.iter()
+                .map(|k| format!("{} \"${}\"", k, k))
+                .collect::<Vec<_>>()
+                .join(" ")
+        );
+        let fields = run_sh("", &convert(&ShellExportConverter), &print_all);
+        assert_eq!(pairs(fields), vars);
+
+        // systemd environment files quote the way sh does.
+        let env_file = format!("set -a\n{}", convert(&SystemdEnvFileConverter));
+        let fields = run_sh("", &env_file, &print_all);
+        assert_eq!(pairs(fields), vars);
+
+        let fields = run_sh(
+            "heroku() { printf '%s\\0' \"$2\" >&3; }",
+            &convert(&HerokuConfigConverter::new("my app".to_string())),
+            "",
+        );
+        let parsed: HashMap<String, String> = fields
+            .iter()
+            .map(|f| {
+                let (k, v) = f.split_once('=').unwrap();
+                (k.to_string(), v.to_string())
+            })
+            .collect();
+        assert_eq!(parsed, vars);
+
+        let by_name = |name: &str, sep: char| {
+            vars.iter()
+                .map(|(k, v)| (k.replace('_', &sep.to_string()).to_lowercase(), v.clone()))
+                .filter(|(k, _)| k.starts_with(name))
+                .collect::<HashMap<_, _>>()
+        };
+
+        let fields = run_sh(
+            "az() { printf '%s\\0' \"$7\" \"$9\" >&3; }",
+            &convert(&AzureKeyVaultConverter::default()),
+            "",
+        );
+        let parsed: HashMap<String, String> = pairs(fields)
+            .into_iter()
+            .map(|(k, v)| (k.to_lowercase(), v))
+            .collect();
+        assert_eq!(parsed, by_name("key", '-'));
+
+        let fields = run_sh(
+            "gcloud() { printf '%s\\0' \"$3\" >&3; cat >&3; printf '\\0' >&3; }",
+            &convert(&GcpSecretConverter::default()),
+            "",
+        );
+        assert_eq!(pairs(fields), by_name("key", '-'));
+    }
+
+    #[test]
+    fn test_terraform_round_trip() {
+        let output = convert(&TerraformConverter);
+        let mut parsed = HashMap::new();
+        let mut lines = output.lines();
+        while let Some(line) = lines.next() {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, expr) = line.split_once(" = ").unwrap();
+            let mut expr = expr.to_string();
+            if let Some(delimiter) = expr.strip_prefix("<<").map(str::to_string) {
+                for next in lines.by_ref() {
+                    expr.push('\n');
+                    expr.push_str(next);
+                    if next == delimiter {
+                        break;
+                    }
+                }
+            }
+            parsed.insert(key.to_uppercase(), parse_hcl_string(&expr));
+        }
+        assert_eq!(parsed, vars());
+    }
+
+    #[test]
+    fn test_source_order_and_comments() {
+        let vars = vec![
+            Variable {
+                description: Some("Listen port".to_string()),
+                section: Some("Server".to_string()),
+                ..Variable::new("PORT", "3000")
+            },
+            Variable {
+                section: Some("Server".to_string()),
+                ..Variable::new("HOST", "0.0.0.0")
+            },
+            Variable {
+                description: Some("Primary database\n(read-write)".to_string()),
+                section: Some("Database".to_string()),
+                ..Variable::new("DB_URL", "postgres://db/app")
+            },
+        ];
+        let options = ConvertOptions::default();
+
+        let output = YamlConverter.convert(&vars, &options).unwrap();
+        assert_eq!(
+            output,
+            "# Server\n\n# Listen port\nPORT: \"3000\"\nHOST: \"0.0.0.0\"\n\n\
+             # Database\n\n# Primary database\n# (read-write)\nDB_URL: postgres://db/app\n"
+        );
+
+        let output = KubernetesSecretConverter::default()
+            .convert(&vars, &options)
+            .unwrap();
+        assert!(output.contains("stringData:\n  # Server\n\n  # Listen port\n  PORT: \"3000\"\n"));
+
+        let output = TerraformConverter.convert(&vars, &options).unwrap();
+        assert!(output.contains("\n# Database\n\n# Primary database\n# (read-write)\ndb_url = "));
+
+        // JSON has no comments but keeps the order.
+        let output = JsonConverter.convert(&vars, &options).unwrap();
+        let port = output.find("PORT").unwrap();
+        let host = output.find("HOST").unwrap();
+        let db = output.find("DB_URL").unwrap();
+        assert!(port < host && host < db);
+
+        // Sorted output drops the section headers but keeps descriptions.
+        let sorted = ConvertOptions {
+            sort: true,
+            ..Default::default()
+        };
+        let output = ShellExportConverter.convert(&vars, &sorted).unwrap();
+        assert_eq!(
+            output,
+            "#!/bin/bash\n# Generated by evnx\n\n\
+             # Primary database\n# (read-write)\nexport DB_URL=postgres://db/app\n\
+             export HOST=0.0.0.0\n# Listen port\nexport PORT=3000\n"
+        );
+    }
+
+    #[test]
+    fn test_nested_yaml_matches_json() {
+        let mut vars: Vec<Variable> = tricky_values()
+            .into_iter()
+            .enumerate()
+            .map(|(i, v)| Variable::new(format!("App__Values__{}", i), v))
+            .collect();
+        vars.push(Variable {
+            description: Some("Worker count".into()),
+            ..Variable::new("App__Workers", "4")
+        });
+        vars.push(Variable::new("App__Hosts__0__Name", "a"));
+        vars.push(Variable::new("App__Hosts__1__Name", "b"));
+        vars.push(Variable::new("Flags", "[1, true]"));
+
+        for coerce in [false, true] {
+            let options = ConvertOptions {
+                nest: Some(KeyPath::new("__", SegmentCase::Preserve)),
+                coerce,
+                ..Default::default()
+            };
+            let json: serde_json::Value =
+                serde_json::from_str(&JsonConverter.convert(&vars, &options).unwrap()).unwrap();
+            let yaml = YamlConverter.convert(&vars, &options).unwrap();
+            assert_eq!(
+                serde_yaml::from_str::<serde_json::Value>(&yaml).unwrap(),
+                json
+            );
+
+            assert_eq!(json["App"]["Hosts"][1]["Name"], "b");
+            assert_eq!(
+                json["App"]["Workers"],
+                if coerce { json!(4) } else { json!("4") }
+            );
+            assert!(yaml.contains("  # Worker count\n  Workers:"));
+        }
+    }
+}
@@ -0,0 +1,127 @@
+// ============================================================================
+// formats/netlify.rs
+// ============================================================================
+
+//! Netlify: `netlify env:set` commands for the site's environment, or the
+//! `[build.environment]` table of `netlify.toml` for values that can be
+//! committed. Secret values (see [`is_secret`]) stay out of `netlify.toml`.
+
+use super::escape::{hash_comments, shell_quote, toml_key, toml_string};
+use super::kubernetes::is_secret;
+use crate::core::converter::{ConvertOptions, Converter, Variable};
+use anyhow::Result;
+
+/// Script of `netlify env:set` commands, run in the linked site's directory.
+pub struct NetlifyCliConverter;
+
+impl Converter for NetlifyCliConverter {
+    fn convert(&self, vars: &[Variable], options: &ConvertOptions) -> Result<String> {
+        let filtered = options.filter_vars(vars);
+
+        let mut output = String::new();
+        output.push_str("#!/bin/bash\n");
+        output.push_str("# Netlify environment variables\n");
+        output.push_str("# Generated by evnx\n\n");
+        output.push_str("set -e\n\n");
+
+        for (i, var) in filtered.iter().enumerate() {
+            let key = options.transform_key(&var.key);
+            let value = options.transform_value(&var.value);
+            output.push_str(&hash_comments(&filtered, i, ""));
+            // `--` keeps a value starting with `-` from reading as a flag.
+            let separator = if value.starts_with('-') { " --" } else { "" };
+            output.push_str(&format!(
+                "netlify env:set {}{} {}\n",
+                shell_quote(&key),
+                separator,
+                shell_quote(&value)
+            ));
+        }
+
+        Ok(output)
+    }
+
+    fn name(&self) -> &str {
+        "netlify"
+    }
+
+    fn description(&self) -> &str {
+        "Netlify environment (netlify env:set commands)"
+    }
+}
+
+/// `[build.environment]` table for `netlify.toml`, without secret values.
+pub struct NetlifyTomlConverter;
+
+impl Converter for NetlifyTomlConverter {
+    fn convert(&self, vars: &[Variable], options: &ConvertOptions) -> Result<String> {
+        let filtered = options.filter_vars(vars);
+        let (secrets, config): (Vec<Variable>, Vec<Variable>) =
+            filtered.into_iter().partition(is_secret);
+
+        let mut output = String::from("# Generated by evnx\n");
+        if !secrets.is_empty() {
+            let keys: Vec<String> = secrets
+                .iter()
+                .map(|var| options.transform_key(&var.key))
+                .collect();
+            output.push_str(&format!(
+                "# Not committed; set with `evnx convert --to netlify`: {}\n",
+                keys.join(", ")
+            ));
+        }
+        output.push_str("\n[build.environment]\n");
+        for (i, var) in config.iter().enumerate() {
+            let key = options.transform_key(&var.key);
+            let value = options.transform_value(&var.value);
+            output.push_str(&hash_comments(&config, i, ""));
+            output.push_str(&format!("{} = {}\n", toml_key(&key), toml_string(&value)));
+        }
+
+        Ok(output)
+    }
+
+    fn name(&self) -> &str {
+        "netlify-toml"
+    }
+
+    fn description(&self) -> &str {
+        "netlify.toml [build.environment] (secrets left out)"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars() -> Vec<Variable> {
+        vec![
+            Variable::new("NODE_VERSION", "20"),
+            Variable::new("STRIPE_SECRET_KEY", "sk_live_abc"),
+        ]
+    }
+
+    #[test]
+    fn test_netlify_toml_leaves_out_secrets() {
+        let output = NetlifyTomlConverter
+            .convert(&vars(), &ConvertOptions::default())
+            .unwrap();
+        let parsed: toml::Table = toml::from_str(&output).unwrap();
+        let environment = &parsed["build"]["environment"];
+        assert_eq!(environment["NODE_VERSION"].as_str(), Some("20"));
+        assert!(environment.get("STRIPE_SECRET_KEY").is_none());
+        assert!(!output.contains("sk_live_abc"));
+        assert!(output.contains("--to netlify`: STRIPE_SECRET_KEY"));
+    }
+
+    #[test]
+    fn test_netlify_cli() {
+        let mut vars = vars();
+        vars.push(Variable::new("FLAGS", "-v"));
+        let output = NetlifyCliConverter
+            .convert(&vars, &ConvertOptions::default())
+            .unwrap();
+        assert!(output.contains("netlify env:set STRIPE_SECRET_KEY sk_live_abc\n"));
+        assert!(output.contains("netlify env:set FLAGS -- -v\n"));
+    }
+}
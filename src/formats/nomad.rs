@@ -0,0 +1,102 @@
+// ============================================================================
+// formats/nomad.rs
+// ============================================================================
+
+use super::escape::{hash_comments, hcl_string};
+use super::kubernetes::is_secret;
+use crate::core::converter::{ConvertOptions, Converter, Variable};
+use anyhow::Result;
+
+/// `env` and `template` blocks for a Nomad task.
+///
+/// Plain values go in `env`. Secret values (see [`is_secret`]) are not
+/// written into the job: the `template` reads them from the job's Nomad
+/// Variables (`nomad/jobs/<job>`) into the task's environment, and the
+/// header shows the `nomad var put` that stores them.
+pub struct NomadConverter;
+
+impl Converter for NomadConverter {
+    fn convert(&self, vars: &[Variable], options: &ConvertOptions) -> Result<String> {
+        let filtered = options.filter_vars(vars);
+        let (secrets, config): (Vec<Variable>, Vec<Variable>) =
+            filtered.into_iter().partition(is_secret);
+        let secret_keys: Vec<String> = secrets
+            .iter()
+            .map(|var| options.transform_key(&var.key))
+            .collect();
+
+        let mut output = String::from("# Generated by evnx; goes in a task block\n");
+        if !secret_keys.is_empty() {
+            output.push_str("# Store the secrets with:\n");
+            output.push_str(&format!(
+                "#   nomad var put nomad/jobs/<job> {}\n",
+                secret_keys
+                    .iter()
+                    .map(|key| format!("{}=...", key))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            ));
+        }
+
+        output.push_str("\nenv {\n");
+        for (i, var) in config.iter().enumerate() {
+            let key = options.transform_key(&var.key);
+            let value = options.transform_value(&var.value);
+            output.push_str(&hash_comments(&config, i, "  "));
+            output.push_str(&format!("  {} = {}\n", key, hcl_string(&value)));
+        }
+        output.push_str("}\n");
+
+        if !secret_keys.is_empty() {
+            output.push_str("\ntemplate {\n");
+            output.push_str("  data        = <<EOT\n");
+            output.push_str(
+                "{{ with nomadVar (printf \"nomad/jobs/%s\" (env \"NOMAD_JOB_ID\")) }}\n",
+            );
+            for key in &secret_keys {
+                output.push_str(&format!("{}={{{{ .{} | toJSON }}}}\n", key, key));
+            }
+            output.push_str("{{ end }}\n");
+            output.push_str("EOT\n");
+            output.push_str("  destination = \"secrets/env\"\n");
+            output.push_str("  env         = true\n");
+            output.push_str("}\n");
+        }
+
+        Ok(output)
+    }
+
+    fn name(&self) -> &str {
+        "nomad"
+    }
+
+    fn description(&self) -> &str {
+        "Nomad task env and template blocks (secrets from Nomad Variables)"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nomad_env_and_template() {
+        let vars = vec![
+            Variable::new("LOG_LEVEL", "info"),
+            Variable::new("API_TOKEN", "abc123"),
+        ];
+        let output = NomadConverter
+            .convert(&vars, &ConvertOptions::default())
+            .unwrap();
+        assert!(output.contains("env {\n  LOG_LEVEL = \"info\"\n}\n"));
+        assert!(output.contains("API_TOKEN={{ .API_TOKEN | toJSON }}\n"));
+        assert!(output.contains("nomad var put nomad/jobs/<job> API_TOKEN=...\n"));
+        assert!(!output.contains("abc123"));
+
+        let vars = vec![Variable::new("LOG_LEVEL", "info")];
+        let output = NomadConverter
+            .convert(&vars, &ConvertOptions::default())
+            .unwrap();
+        assert!(!output.contains("template"));
+    }
+}
@@ -0,0 +1,69 @@
+// ============================================================================
+// formats/properties.rs
+// ============================================================================
+
+use super::escape::{hash_comments, properties_key, properties_value};
+use crate::core::converter::{ConvertOptions, Converter, Variable};
+use anyhow::Result;
+
+/// Java `.properties` file. With `--nest`, keys are split into segments
+/// and joined with `.` (`--nest _ --key-case lower` turns `DB_URL` into
+/// `db.url`).
+pub struct PropertiesConverter;
+
+impl Converter for PropertiesConverter {
+    fn convert(&self, vars: &[Variable], options: &ConvertOptions) -> Result<String> {
+        let filtered = options.filter_vars(vars);
+
+        let mut output = String::from("# Generated by evnx\n\n");
+        for (i, var) in filtered.iter().enumerate() {
+            let mut key = options.transform_key(&var.key);
+            if let Some(path) = &options.nest {
+                key = path.split(&key).join(".");
+            }
+            let value = options.transform_value(&var.value);
+            output.push_str(&hash_comments(&filtered, i, ""));
+            output.push_str(&format!(
+                "{}={}\n",
+                properties_key(&key),
+                properties_value(&value)
+            ));
+        }
+
+        Ok(output)
+    }
+
+    fn name(&self) -> &str {
+        "properties"
+    }
+
+    fn description(&self) -> &str {
+        "Java .properties file"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{KeyPath, SegmentCase};
+
+    #[test]
+    fn test_properties_converter() {
+        let vars = vec![
+            Variable::new("DB_URL", "jdbc:postgresql://db:5432/app"),
+            Variable::new("GREETING", " hello"),
+        ];
+        let output = PropertiesConverter
+            .convert(&vars, &ConvertOptions::default())
+            .unwrap();
+        assert!(output.contains("DB_URL=jdbc\\:postgresql\\://db\\:5432/app\n"));
+        assert!(output.contains("GREETING=\\ hello\n"));
+
+        let options = ConvertOptions {
+            nest: Some(KeyPath::new("_", SegmentCase::Lower)),
+            ..Default::default()
+        };
+        let output = PropertiesConverter.convert(&vars, &options).unwrap();
+        assert!(output.contains("db.url="));
+    }
+}
@@ -0,0 +1,105 @@
+// ============================================================================
+// formats/python.rs
+// ============================================================================
+
+use super::escape::{hash_comments, python_string};
+use super::import::is_identifier;
+use crate::core::converter::{ConvertOptions, Converter, Variable};
+use crate::core::keypath::typed;
+use anyhow::{bail, Result};
+use serde_json::Value;
+
+/// Python settings module (Django style): one `NAME = value` per variable.
+/// With `--coerce`, booleans, numbers and JSON arrays and objects become
+/// Python literals; otherwise every value is a string.
+pub struct PythonSettingsConverter;
+
+impl Converter for PythonSettingsConverter {
+    fn convert(&self, vars: &[Variable], options: &ConvertOptions) -> Result<String> {
+        let filtered = options.filter_vars(vars);
+
+        let mut output = String::from("# Generated by evnx\n\n");
+        for (i, var) in filtered.iter().enumerate() {
+            let key = options.transform_key(&var.key);
+            if !is_identifier(&key) {
+                bail!("{} is not a valid Python name", key);
+            }
+            let value = options.transform_value(&var.value);
+            let value = typed(&value, options.coerce && !options.base64);
+            output.push_str(&hash_comments(&filtered, i, ""));
+            output.push_str(&format!("{} = {}\n", key, python_literal(&value)));
+        }
+
+        Ok(output)
+    }
+
+    fn name(&self) -> &str {
+        "python"
+    }
+
+    fn description(&self) -> &str {
+        "Python settings module"
+    }
+}
+
+/// Python literal of a JSON value.
+fn python_literal(value: &Value) -> String {
+    match value {
+        Value::Null => "None".to_string(),
+        Value::Bool(true) => "True".to_string(),
+        Value::Bool(false) => "False".to_string(),
+        Value::Number(number) => number.to_string(),
+        Value::String(s) => python_string(s),
+        Value::Array(items) => format!(
+            "[{}]",
+            items
+                .iter()
+                .map(python_literal)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        Value::Object(entries) => format!(
+            "{{{}}}",
+            entries
+                .iter()
+                .map(|(k, v)| format!("{}: {}", python_string(k), python_literal(v)))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_python_settings_converter() {
+        let vars = vec![
+            Variable::new("DEBUG", "false"),
+            Variable::new("PORT", "8000"),
+            Variable::new("ALLOWED_HOSTS", r#"["a.com", "b.com"]"#),
+            Variable::new("SECRET_KEY", "it's \"x\""),
+        ];
+
+        let output = PythonSettingsConverter
+            .convert(&vars, &ConvertOptions::default())
+            .unwrap();
+        assert!(output.contains("DEBUG = \"false\"\n"));
+
+        let options = ConvertOptions {
+            coerce: true,
+            ..Default::default()
+        };
+        let output = PythonSettingsConverter.convert(&vars, &options).unwrap();
+        assert!(output.contains("DEBUG = False\n"));
+        assert!(output.contains("PORT = 8000\n"));
+        assert!(output.contains("ALLOWED_HOSTS = [\"a.com\", \"b.com\"]\n"));
+        assert!(output.contains("SECRET_KEY = \"it's \\\"x\\\"\"\n"));
+
+        let vars = vec![Variable::new("my-key", "x")];
+        assert!(PythonSettingsConverter
+            .convert(&vars, &ConvertOptions::default())
+            .is_err());
+    }
+}
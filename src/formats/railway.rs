@@ -2,26 +2,26 @@
 // Railway Variables
 // ============================================================================
 
-use crate::core::converter::{ConvertOptions, Converter};
+use super::escape::json_object;
+use crate::core::converter::{ConvertOptions, Converter, Variable};
 use anyhow::Result;
-use std::collections::HashMap;
 
 pub struct RailwayConverter;
 
 impl Converter for RailwayConverter {
-    fn convert(&self, vars: &HashMap<String, String>, options: &ConvertOptions) -> Result<String> {
+    fn convert(&self, vars: &[Variable], options: &ConvertOptions) -> Result<String> {
         let filtered = options.filter_vars(vars);
 
-        let transformed: HashMap<String, String> = filtered
+        let transformed: Vec<(String, String)> = filtered
             .iter()
-            .map(|(k, v)| {
-                let key = options.transform_key(k);
-                let value = options.transform_value(v);
+            .map(|var| {
+                let key = options.transform_key(&var.key);
+                let value = options.transform_value(&var.value);
                 (key, value)
             })
             .collect();
 
-        let json = serde_json::to_string_pretty(&transformed)?;
+        let json = json_object(&transformed)?;
         Ok(json)
     }
 
@@ -0,0 +1,75 @@
+// ============================================================================
+// formats/render.rs
+// ============================================================================
+
+use super::escape::{hash_comments, yaml_scalar};
+use super::kubernetes::is_secret;
+use crate::core::converter::{ConvertOptions, Converter, Variable};
+use anyhow::Result;
+
+/// `envVars` list for a service in a Render blueprint (`render.yaml`).
+/// Secret values (see [`is_secret`]) are not written: they get
+/// `sync: false`, and Render asks for them when the blueprint is applied.
+pub struct RenderConverter;
+
+impl Converter for RenderConverter {
+    fn convert(&self, vars: &[Variable], options: &ConvertOptions) -> Result<String> {
+        let filtered = options.filter_vars(vars);
+
+        let mut output = String::new();
+        output.push_str("# Generated by evnx; goes under a service in render.yaml\n");
+        if filtered.is_empty() {
+            output.push_str("envVars: []\n");
+            return Ok(output);
+        }
+
+        output.push_str("envVars:\n");
+        for (i, var) in filtered.iter().enumerate() {
+            let key = options.transform_key(&var.key);
+            output.push_str(&hash_comments(&filtered, i, "  "));
+            output.push_str(&format!("  - key: {}\n", yaml_scalar(&key, 6)));
+            if is_secret(var) {
+                output.push_str("    sync: false\n");
+            } else {
+                let value = options.transform_value(&var.value);
+                output.push_str(&format!("    value: {}\n", yaml_scalar(&value, 6)));
+            }
+        }
+
+        Ok(output)
+    }
+
+    fn name(&self) -> &str {
+        "render"
+    }
+
+    fn description(&self) -> &str {
+        "Render blueprint envVars (secrets as sync: false)"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_env_vars() {
+        let vars = vec![
+            Variable::new("PORT", "10000"),
+            Variable::new("DATABASE_PASSWORD", "hunter2"),
+            Variable::new("MOTD", "line1\nline2\n"),
+        ];
+        let output = RenderConverter
+            .convert(&vars, &ConvertOptions::default())
+            .unwrap();
+        assert!(!output.contains("hunter2"));
+
+        let parsed: serde_yaml::Value = serde_yaml::from_str(&output).unwrap();
+        let env = &parsed["envVars"];
+        assert_eq!(env[0]["key"], "PORT");
+        assert_eq!(env[0]["value"], "10000");
+        assert_eq!(env[1]["key"], "DATABASE_PASSWORD");
+        assert_eq!(env[1]["sync"], false);
+        assert_eq!(env[2]["value"], "line1\nline2\n");
+    }
+}
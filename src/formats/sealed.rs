@@ -0,0 +1,303 @@
+// ============================================================================
+// formats/sealed.rs
+// ============================================================================
+
+//! Offline sealing for Bitnami Sealed Secrets.
+//!
+//! Encrypts values the way `kubeseal` does, with the controller's public
+//! certificate (`kubeseal --fetch-cert`), so a committable `SealedSecret`
+//! needs no cluster access. Each value is sealed on its own:
+//!
+//! - a random 32-byte session key encrypts the value with AES-256-GCM and a
+//!   zero nonce, which is safe because the key is used once;
+//! - RSA-OAEP with SHA-256 encrypts the session key, labelled with the scope:
+//!   `namespace/name` (strict), `namespace` (namespace-wide) or nothing
+//!   (cluster-wide);
+//! - the sealed value is the RSA ciphertext length (two bytes, big-endian),
+//!   the RSA ciphertext, then the AES ciphertext with its tag.
+//!
+//! The controller only unseals a value into a Secret whose name and namespace
+//! match the label, so a sealed value cannot be copied into another Secret.
+
+use super::escape::{hash_comments, yaml_key, yaml_scalar};
+use super::kubernetes::labels;
+use crate::core::converter::{ConvertOptions, Converter, Variable};
+use aes_gcm::{aead::Aead, Aes256Gcm, KeyInit, Nonce};
+use anyhow::{anyhow, Context, Result};
+use base64::{engine::general_purpose, Engine as _};
+use rsa::pkcs8::DecodePublicKey;
+use rsa::rand_core::{OsRng, RngCore};
+use rsa::{Oaep, RsaPublicKey};
+use sha2::Sha256;
+use x509_cert::der::{DecodePem, Encode};
+use x509_cert::Certificate;
+
+/// Which Secrets a sealed value may be unsealed into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SealingScope {
+    /// Only a Secret with this name in this namespace
+    #[default]
+    Strict,
+    /// Any Secret in this namespace
+    NamespaceWide,
+    /// Any Secret in any namespace
+    ClusterWide,
+}
+
+impl SealingScope {
+    /// Parse a `--scope` name
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "strict" => Some(SealingScope::Strict),
+            "namespace-wide" => Some(SealingScope::NamespaceWide),
+            "cluster-wide" => Some(SealingScope::ClusterWide),
+            _ => None,
+        }
+    }
+}
+
+pub struct SealedSecretConverter {
+    /// The controller's public key
+    pub key: RsaPublicKey,
+    pub name: String,
+    /// Required unless the scope is cluster-wide
+    pub namespace: Option<String>,
+    pub labels: Vec<(String, String)>,
+    pub scope: SealingScope,
+}
+
+/// RSA public key of a PEM certificate, as `kubeseal --fetch-cert` prints
+/// it, or of a PEM public key.
+pub fn public_key(pem: &str) -> Result<RsaPublicKey> {
+    if pem.contains("-----BEGIN CERTIFICATE-----") {
+        let cert = Certificate::from_pem(pem.trim().as_bytes()).context("Invalid certificate")?;
+        let der = cert
+            .tbs_certificate
+            .subject_public_key_info
+            .to_der()
+            .context("Invalid certificate")?;
+        RsaPublicKey::from_public_key_der(&der).context("The certificate does not hold an RSA key")
+    } else {
+        RsaPublicKey::from_public_key_pem(pem.trim())
+            .context("Expected a PEM certificate or RSA public key")
+    }
+}
+
+/// `plaintext` sealed for `label` (see the module docs for the layout).
+pub fn seal(key: &RsaPublicKey, plaintext: &[u8], label: &str) -> Result<Vec<u8>> {
+    let mut session_key = [0u8; 32];
+    OsRng.fill_bytes(&mut session_key);
+
+    let padding = Oaep::new_with_label::<Sha256, _>(label);
+    let sealed_key = key
+        .encrypt(&mut OsRng, padding, &session_key)
+        .context("RSA encryption failed")?;
+
+    let cipher = Aes256Gcm::new_from_slice(&session_key).map_err(|_| anyhow!("Invalid key"))?;
+    let ciphertext = cipher
+        .encrypt(&Nonce::default(), plaintext)
+        .map_err(|_| anyhow!("AES-GCM encryption failed"))?;
+
+    let mut sealed = Vec::with_capacity(2 + sealed_key.len() + ciphertext.len());
+    sealed.extend_from_slice(&(sealed_key.len() as u16).to_be_bytes());
+    sealed.extend_from_slice(&sealed_key);
+    sealed.extend_from_slice(&ciphertext);
+    Ok(sealed)
+}
+
+impl SealedSecretConverter {
+    /// OAEP label binding values to the scope.
+    fn label(&self) -> Result<String> {
+        let namespace = || {
+            self.namespace
+                .as_deref()
+                .ok_or_else(|| anyhow!("Strict and namespace-wide SealedSecrets need a namespace"))
+        };
+        Ok(match self.scope {
+            SealingScope::Strict => format!("{}/{}", namespace()?, self.name),
+            SealingScope::NamespaceWide => namespace()?.to_string(),
+            SealingScope::ClusterWide => String::new(),
+        })
+    }
+
+    /// `metadata:` body lines, each prefixed by `indent`.
+    fn metadata(&self, indent: &str) -> String {
+        let width = indent.len() + 2;
+        let mut output = format!("{}name: {}\n", indent, yaml_scalar(&self.name, width));
+        if let Some(namespace) = &self.namespace {
+            output.push_str(&format!(
+                "{}namespace: {}\n",
+                indent,
+                yaml_scalar(namespace, width)
+            ));
+        }
+        let annotation = match self.scope {
+            SealingScope::Strict => None,
+            SealingScope::NamespaceWide => Some("sealedsecrets.bitnami.com/namespace-wide"),
+            SealingScope::ClusterWide => Some("sealedsecrets.bitnami.com/cluster-wide"),
+        };
+        if let Some(annotation) = annotation {
+            output.push_str(&format!("{}annotations:\n", indent));
+            output.push_str(&format!("{}  {}: \"true\"\n", indent, annotation));
+        }
+        if !self.labels.is_empty() {
+            output.push_str(&format!("{}labels:\n", indent));
+            output.push_str(&labels(&self.labels, &format!("{}  ", indent)));
+        }
+        output
+    }
+}
+
+impl Converter for SealedSecretConverter {
+    fn convert(&self, vars: &[Variable], options: &ConvertOptions) -> Result<String> {
+        let filtered = options.filter_vars(vars);
+        let label = self.label()?;
+
+        let mut output = String::new();
+        output.push_str("apiVersion: bitnami.com/v1alpha1\n");
+        output.push_str("kind: SealedSecret\n");
+        output.push_str("metadata:\n");
+        output.push_str(&self.metadata("  "));
+        output.push_str("spec:\n");
+
+        if filtered.is_empty() {
+            output.push_str("  encryptedData: {}\n");
+        } else {
+            output.push_str("  encryptedData:\n");
+        }
+        for (i, var) in filtered.iter().enumerate() {
+            // The Secret gets the plain value; --base64 has nothing to encode.
+            let key = options.transform_key(&var.key);
+            let sealed = seal(&self.key, var.value.as_bytes(), &label)
+                .with_context(|| format!("Failed to seal {}", key))?;
+            output.push_str(&hash_comments(&filtered, i, "    "));
+            output.push_str(&format!(
+                "    {}: {}\n",
+                yaml_key(&key),
+                general_purpose::STANDARD.encode(sealed)
+            ));
+        }
+
+        output.push_str("  template:\n");
+        output.push_str("    metadata:\n");
+        output.push_str(&self.metadata("      "));
+        output.push_str("    type: Opaque\n");
+        Ok(output)
+    }
+
+    fn name(&self) -> &str {
+        "sealed-secret"
+    }
+
+    fn description(&self) -> &str {
+        "Bitnami SealedSecret, sealed offline with the controller certificate"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rsa::traits::PublicKeyParts;
+    use rsa::RsaPrivateKey;
+    use std::sync::OnceLock;
+
+    /// A controller certificate; its private key is not kept anywhere.
+    const CERT: &str = include_str!("../../tests/fixtures/sealed-secrets/cert.pem");
+
+    /// The controller's key pair, generated once per test run so that no
+    /// private key is committed.
+    fn controller_key() -> &'static RsaPrivateKey {
+        static KEY: OnceLock<RsaPrivateKey> = OnceLock::new();
+        KEY.get_or_init(|| RsaPrivateKey::new(&mut OsRng, 2048).unwrap())
+    }
+
+    /// What the controller does with a sealed value.
+    fn unseal(sealed: &[u8], label: &str) -> Result<Vec<u8>> {
+        let private = controller_key();
+        let len = u16::from_be_bytes([sealed[0], sealed[1]]) as usize;
+        let session_key = private.decrypt(
+            Oaep::new_with_label::<Sha256, _>(label),
+            &sealed[2..2 + len],
+        )?;
+        Aes256Gcm::new_from_slice(&session_key)
+            .unwrap()
+            .decrypt(&Nonce::default(), &sealed[2 + len..])
+            .map_err(|_| anyhow!("AES-GCM decryption failed"))
+    }
+
+    fn converter(scope: SealingScope, namespace: Option<&str>) -> SealedSecretConverter {
+        SealedSecretConverter {
+            key: controller_key().to_public_key(),
+            name: "app-secrets".into(),
+            namespace: namespace.map(String::from),
+            labels: vec![("app".into(), "web".into())],
+            scope,
+        }
+    }
+
+    fn sealed_value(output: &str, key: &str) -> Vec<u8> {
+        let doc: serde_yaml::Value = serde_yaml::from_str(output).unwrap();
+        let value = doc["spec"]["encryptedData"][key].as_str().unwrap();
+        general_purpose::STANDARD.decode(value).unwrap()
+    }
+
+    #[test]
+    fn test_sealed_values_unseal_for_their_scope_only() {
+        let vars = vec![
+            Variable::new("API_KEY", "sk_live_abc"),
+            Variable::new("PEM", "-----BEGIN KEY-----\nabc\n-----END KEY-----\n"),
+        ];
+
+        let output = converter(SealingScope::Strict, Some("prod"))
+            .convert(&vars, &ConvertOptions::default())
+            .unwrap();
+        let doc: serde_yaml::Value = serde_yaml::from_str(&output).unwrap();
+        assert_eq!(doc["kind"], "SealedSecret");
+        assert_eq!(doc["metadata"]["namespace"], "prod");
+        assert_eq!(doc["spec"]["template"]["metadata"]["labels"]["app"], "web");
+        assert_eq!(doc["spec"]["template"]["type"], "Opaque");
+        assert!(!output.contains("sk_live_abc"));
+
+        let sealed = sealed_value(&output, "PEM");
+        assert_eq!(
+            unseal(&sealed, "prod/app-secrets").unwrap(),
+            vars[1].value.as_bytes()
+        );
+        assert!(unseal(&sealed, "prod/other").is_err());
+        assert!(unseal(&sealed, "prod").is_err());
+
+        let output = converter(SealingScope::NamespaceWide, Some("prod"))
+            .convert(&vars, &ConvertOptions::default())
+            .unwrap();
+        assert!(output.contains("sealedsecrets.bitnami.com/namespace-wide: \"true\""));
+        assert_eq!(
+            unseal(&sealed_value(&output, "API_KEY"), "prod").unwrap(),
+            b"sk_live_abc"
+        );
+
+        let output = converter(SealingScope::ClusterWide, None)
+            .convert(&vars, &ConvertOptions::default())
+            .unwrap();
+        assert_eq!(
+            unseal(&sealed_value(&output, "API_KEY"), "").unwrap(),
+            b"sk_live_abc"
+        );
+
+        // Strict and namespace-wide sealing are bound to a namespace.
+        assert!(converter(SealingScope::Strict, None)
+            .convert(&vars, &ConvertOptions::default())
+            .is_err());
+    }
+
+    #[test]
+    fn test_public_key_formats() {
+        let from_cert = public_key(CERT).unwrap();
+        assert_eq!(from_cert.size(), 256);
+
+        use rsa::pkcs8::{EncodePublicKey, LineEnding};
+        let generated = controller_key().to_public_key();
+        let pem = generated.to_public_key_pem(LineEnding::LF).unwrap();
+        assert_eq!(public_key(&pem).unwrap(), generated);
+        assert!(public_key("not a key").is_err());
+    }
+}
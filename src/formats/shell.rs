@@ -2,27 +2,27 @@
 // formats/shell.rs
 // ============================================================================
 
-use crate::core::converter::{ConvertOptions, Converter};
-use anyhow::Result;
-use std::collections::HashMap;
+//! Scripts that set the variables in a shell: POSIX `export` lines, fish,
+//! PowerShell, cmd, Nushell and direnv `.envrc` files. Each dialect quotes
+//! values with its own rules (see [`super::escape`]).
+
+use super::escape::{
+    cmd_value, fish_quote, hash_comments, nu_string, powershell_quote, shell_quote,
+};
+use super::import::is_identifier;
+use crate::core::converter::{ConvertOptions, Converter, Variable};
+use anyhow::{bail, Result};
 
 pub struct ShellExportConverter;
 
 impl Converter for ShellExportConverter {
-    fn convert(&self, vars: &HashMap<String, String>, options: &ConvertOptions) -> Result<String> {
+    fn convert(&self, vars: &[Variable], options: &ConvertOptions) -> Result<String> {
         let filtered = options.filter_vars(vars);
 
         let mut output = String::new();
         output.push_str("#!/bin/bash\n");
         output.push_str("# Generated by evnx\n\n");
-
-        for (k, v) in filtered.iter() {
-            let key = options.transform_key(k);
-            let value = options.transform_value(v);
-            // Escape quotes in values
-            let escaped_value = value.replace('"', "\\\"");
-            output.push_str(&format!("export {}=\"{}\"\n", key, escaped_value));
-        }
+        output.push_str(&exports(&filtered, options));
 
         Ok(output)
     }
@@ -35,3 +35,239 @@ impl Converter for ShellExportConverter {
         "Shell export script"
     }
 }
+
+/// `export KEY=value` lines with their comments.
+fn exports(vars: &[Variable], options: &ConvertOptions) -> String {
+    let mut output = String::new();
+    for (i, var) in vars.iter().enumerate() {
+        let key = options.transform_key(&var.key);
+        let value = options.transform_value(&var.value);
+        output.push_str(&hash_comments(vars, i, ""));
+        output.push_str(&format!("export {}={}\n", key, shell_quote(&value)));
+    }
+    output
+}
+
+/// fish script, for `source`.
+pub struct FishConverter;
+
+impl Converter for FishConverter {
+    fn convert(&self, vars: &[Variable], options: &ConvertOptions) -> Result<String> {
+        let filtered = options.filter_vars(vars);
+
+        let mut output = String::from("# Generated by evnx\n\n");
+        for (i, var) in filtered.iter().enumerate() {
+            let key = options.transform_key(&var.key);
+            let value = options.transform_value(&var.value);
+            output.push_str(&hash_comments(&filtered, i, ""));
+            output.push_str(&format!("set -gx {} {}\n", key, fish_quote(&value)));
+        }
+
+        Ok(output)
+    }
+
+    fn name(&self) -> &str {
+        "fish"
+    }
+
+    fn description(&self) -> &str {
+        "fish script (set -gx)"
+    }
+}
+
+/// PowerShell script, for dot-sourcing (`. ./env.ps1`).
+pub struct PowerShellConverter;
+
+impl Converter for PowerShellConverter {
+    fn convert(&self, vars: &[Variable], options: &ConvertOptions) -> Result<String> {
+        let filtered = options.filter_vars(vars);
+
+        let mut output = String::from("# Generated by evnx\n\n");
+        for (i, var) in filtered.iter().enumerate() {
+            let key = options.transform_key(&var.key);
+            let value = options.transform_value(&var.value);
+            output.push_str(&hash_comments(&filtered, i, ""));
+
+            // `$env:NAME` takes identifiers only; `${env:...}` takes any
+            // name, with `{`, `}` and the backtick escaped by a backtick.
+            let variable = if is_identifier(&key) {
+                format!("$env:{}", key)
+            } else {
+                let escaped: String = key
+                    .chars()
+                    .flat_map(|c| match c {
+                        '{' | '}' | '`' => vec!['`', c],
+                        c => vec![c],
+                    })
+                    .collect();
+                format!("${{env:{}}}", escaped)
+            };
+            output.push_str(&format!("{} = {}\n", variable, powershell_quote(&value)));
+        }
+
+        Ok(output)
+    }
+
+    fn name(&self) -> &str {
+        "powershell"
+    }
+
+    fn description(&self) -> &str {
+        "PowerShell script ($env:)"
+    }
+}
+
+/// Windows batch file, for `call`.
+pub struct CmdConverter;
+
+impl Converter for CmdConverter {
+    fn convert(&self, vars: &[Variable], options: &ConvertOptions) -> Result<String> {
+        let filtered = options.filter_vars(vars);
+
+        let mut output = String::from("@echo off\r\nREM Generated by evnx\r\n\r\n");
+        for (i, var) in filtered.iter().enumerate() {
+            let key = options.transform_key(&var.key);
+            let value = options.transform_value(&var.value);
+            let Some(value) = cmd_value(&value) else {
+                bail!("{} spans several lines, which cmd cannot set", key);
+            };
+
+            for line in hash_comments(&filtered, i, "").lines() {
+                match line.strip_prefix("# ") {
+                    Some(comment) => output.push_str(&format!("REM {}\r\n", comment)),
+                    None => output.push_str("\r\n"),
+                }
+            }
+            output.push_str(&format!("set \"{}={}\"\r\n", key, value));
+        }
+
+        Ok(output)
+    }
+
+    fn name(&self) -> &str {
+        "cmd"
+    }
+
+    fn description(&self) -> &str {
+        "Windows batch file (set)"
+    }
+}
+
+/// Nushell script, for `source-env`.
+pub struct NushellConverter;
+
+impl Converter for NushellConverter {
+    fn convert(&self, vars: &[Variable], options: &ConvertOptions) -> Result<String> {
+        let filtered = options.filter_vars(vars);
+
+        let mut output = String::from("# Generated by evnx\n\n");
+        for (i, var) in filtered.iter().enumerate() {
+            let key = options.transform_key(&var.key);
+            let value = options.transform_value(&var.value);
+            output.push_str(&hash_comments(&filtered, i, ""));
+
+            let key = if is_identifier(&key) {
+                key
+            } else {
+                nu_string(&key)
+            };
+            output.push_str(&format!("$env.{} = {}\n", key, nu_string(&value)));
+        }
+
+        Ok(output)
+    }
+
+    fn name(&self) -> &str {
+        "nushell"
+    }
+
+    fn description(&self) -> &str {
+        "Nushell script ($env.)"
+    }
+}
+
+/// direnv `.envrc`: the values inlined as exports or, with `dotenv` set,
+/// a `dotenv_if_exists` line that loads that file.
+#[derive(Default)]
+pub struct DirenvConverter {
+    /// Path of the `.env` file, relative to the `.envrc`
+    pub dotenv: Option<String>,
+}
+
+impl Converter for DirenvConverter {
+    fn convert(&self, vars: &[Variable], options: &ConvertOptions) -> Result<String> {
+        let mut output = String::from("# Generated by evnx\n\n");
+        match &self.dotenv {
+            // direnv reads the file itself, so filters and transforms do
+            // not apply.
+            Some(path) => output.push_str(&format!("dotenv_if_exists {}\n", shell_quote(path))),
+            None => output.push_str(&exports(&options.filter_vars(vars), options)),
+        }
+
+        Ok(output)
+    }
+
+    fn name(&self) -> &str {
+        "direnv"
+    }
+
+    fn description(&self) -> &str {
+        "direnv .envrc"
+    }
+}
+
+/// The `--to` format for the shell at `shell` (`$SHELL`): fish, powershell,
+/// nushell or shell; powershell on Windows, where `$SHELL` is usually unset.
+pub fn detect(shell: Option<&str>) -> &'static str {
+    let Some(shell) = shell.filter(|s| !s.is_empty()) else {
+        return if cfg!(windows) { "powershell" } else { "shell" };
+    };
+    let name = shell.rsplit(['/', '\\']).next().unwrap_or(shell);
+    let name = name.strip_suffix(".exe").unwrap_or(name);
+    match name {
+        "fish" => "fish",
+        "pwsh" | "powershell" => "powershell",
+        "nu" | "nushell" => "nushell",
+        "cmd" => "cmd",
+        _ => "shell",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(detect(Some("/usr/bin/fish")), "fish");
+        assert_eq!(detect(Some("/opt/homebrew/bin/nu")), "nushell");
+        assert_eq!(detect(Some("/usr/local/bin/pwsh")), "powershell");
+        assert_eq!(detect(Some(r"C:\Windows\System32\cmd.exe")), "cmd");
+        assert_eq!(detect(Some("/bin/zsh")), "shell");
+    }
+
+    #[test]
+    fn test_direnv() {
+        let vars = vec![Variable::new("A", "it's")];
+        let inline = DirenvConverter::default()
+            .convert(&vars, &ConvertOptions::default())
+            .unwrap();
+        assert!(inline.ends_with("export A='it'\\''s'\n"));
+
+        let dotenv = DirenvConverter {
+            dotenv: Some("config/.env".into()),
+        }
+        .convert(&vars, &ConvertOptions::default())
+        .unwrap();
+        assert!(dotenv.ends_with("dotenv_if_exists config/.env\n"));
+        assert!(!dotenv.contains("it's"));
+    }
+
+    #[test]
+    fn test_cmd_rejects_multiline() {
+        let vars = vec![Variable::new("PEM", "a\nb")];
+        assert!(CmdConverter
+            .convert(&vars, &ConvertOptions::default())
+            .is_err());
+    }
+}
@@ -0,0 +1,129 @@
+// ============================================================================
+// formats/spring.rs
+// ============================================================================
+
+use super::yaml::document;
+use crate::core::converter::{ConvertOptions, Converter, Variable};
+use crate::core::keypath::{typed, Node};
+use crate::core::{KeyPath, SegmentCase};
+use anyhow::Result;
+
+/// Spring Boot `application.yml`. Keys map to properties the way Spring's
+/// relaxed binding reads environment variables: `_` separates segments and
+/// names are lowercase, so `SPRING_DATASOURCE_URL` becomes
+/// `spring.datasource.url` and `MY_HOSTS_0` the first item of `my.hosts`.
+/// `--nest` and `--key-case` override the mapping.
+///
+/// A property may be both a value and the parent of others
+/// (`DATABASE_URL`, `DATABASE_URL_REPLICA`), which nested YAML cannot hold;
+/// the children are written as flat dotted keys (`database.url.replica`)
+/// after the nested ones, which Spring binds the same way.
+pub struct SpringConverter;
+
+impl Converter for SpringConverter {
+    fn convert(&self, vars: &[Variable], options: &ConvertOptions) -> Result<String> {
+        let path = options
+            .nest
+            .clone()
+            .unwrap_or_else(|| KeyPath::new("_", SegmentCase::Lower));
+
+        let vars = options.filter_vars(vars);
+        let paths: Vec<Vec<String>> = vars
+            .iter()
+            .map(|var| path.split(&options.transform_key(&var.key)))
+            .collect();
+        let under_value = |segments: &Vec<String>| {
+            paths
+                .iter()
+                .any(|other| other.len() < segments.len() && segments.starts_with(other))
+        };
+
+        let (mut nested, mut flat) = (Vec::new(), Vec::new());
+        for (var, segments) in vars.iter().zip(&paths) {
+            if under_value(segments) {
+                flat.push((
+                    segments.join("."),
+                    Node::Leaf {
+                        value: typed(
+                            &options.transform_value(&var.value),
+                            options.coerce && !options.base64,
+                        ),
+                        description: var.description.clone(),
+                    },
+                ));
+            } else {
+                nested.push(var.clone());
+            }
+        }
+
+        let mut entries = match options.nested_as(&nested, &path)? {
+            Node::Map(entries) => entries,
+            Node::List(items) => items
+                .into_iter()
+                .enumerate()
+                .map(|(i, item)| (i.to_string(), item))
+                .collect(),
+            Node::Leaf { .. } => unreachable!("a nested document is a map or a list"),
+        };
+        entries.extend(flat);
+        Ok(document(&Node::Map(entries)))
+    }
+
+    fn name(&self) -> &str {
+        "spring"
+    }
+
+    fn description(&self) -> &str {
+        "Spring Boot application.yml (relaxed binding)"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spring_converter() {
+        let mut url = Variable::new("SPRING_DATASOURCE_URL", "jdbc:postgresql://db/app");
+        url.description = Some("Primary database".into());
+        let vars = vec![
+            url,
+            Variable::new("SPRING_DATASOURCE_USERNAME", "app"),
+            Variable::new("SERVER_PORT", "8080"),
+            Variable::new("MY_HOSTS_0", "a"),
+            Variable::new("MY_HOSTS_1", "b"),
+        ];
+        let output = SpringConverter
+            .convert(&vars, &ConvertOptions::default())
+            .unwrap();
+        assert!(output.contains("    # Primary database\n    url: "));
+
+        let yaml: serde_yaml::Value = serde_yaml::from_str(&output).unwrap();
+        assert_eq!(
+            yaml["spring"]["datasource"]["url"],
+            "jdbc:postgresql://db/app"
+        );
+        assert_eq!(yaml["server"]["port"], "8080");
+        assert_eq!(yaml["my"]["hosts"][1], "b");
+
+        // `database.url` is both a value and a parent: its children go flat.
+        let vars = vec![
+            Variable::new("DATABASE_URL_REPLICA", "b"),
+            Variable::new("DATABASE_URL", "a"),
+            Variable::new("DATABASE_POOL", "5"),
+            Variable::new("REDIS_URL", "c"),
+            Variable::new("REDIS_URL_TLS", "d"),
+        ];
+        let output = SpringConverter
+            .convert(&vars, &ConvertOptions::default())
+            .unwrap();
+        assert_eq!(
+            output,
+            "database:\n  url: a\n  pool: \"5\"\nredis:\n  url: c\n\
+             database.url.replica: b\nredis.url.tls: d\n"
+        );
+        let yaml: serde_yaml::Value = serde_yaml::from_str(&output).unwrap();
+        assert_eq!(yaml["database"]["url"], "a");
+        assert_eq!(yaml["database.url.replica"], "b");
+    }
+}
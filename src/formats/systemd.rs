@@ -0,0 +1,138 @@
+// ============================================================================
+// formats/systemd.rs
+// ============================================================================
+
+//! systemd: a file for `EnvironmentFile=`, or a drop-in unit with
+//! `Environment=` lines. Values in a drop-in show in `systemctl show`, so
+//! secrets belong in the environment file, readable by root only.
+
+use super::escape::{hash_comments, systemd_env_value, systemd_environment};
+use super::kubernetes::is_secret;
+use crate::core::converter::{ConvertOptions, Converter, Variable};
+use anyhow::Result;
+
+/// File for a unit's `EnvironmentFile=`.
+pub struct SystemdEnvFileConverter;
+
+impl Converter for SystemdEnvFileConverter {
+    fn convert(&self, vars: &[Variable], options: &ConvertOptions) -> Result<String> {
+        let filtered = options.filter_vars(vars);
+
+        let mut output = String::from("# Generated by evnx\n\n");
+        for (i, var) in filtered.iter().enumerate() {
+            let key = options.transform_key(&var.key);
+            let value = options.transform_value(&var.value);
+            output.push_str(&hash_comments(&filtered, i, ""));
+            output.push_str(&format!("{}={}\n", key, systemd_env_value(&value)));
+        }
+
+        Ok(output)
+    }
+
+    fn name(&self) -> &str {
+        "systemd"
+    }
+
+    fn description(&self) -> &str {
+        "systemd EnvironmentFile"
+    }
+}
+
+/// Drop-in unit (`<unit>.service.d/env.conf`) setting `Environment=`.
+///
+/// Secret values (see [`is_secret`]) are not written into the unit: it
+/// reads them from an `EnvironmentFile=`, and the header lists the keys
+/// that file must hold.
+pub struct SystemdDropInConverter;
+
+/// Environment file the drop-in reads secrets from.
+const SECRETS_FILE: &str = "/etc/<unit>/secrets.env";
+
+impl Converter for SystemdDropInConverter {
+    fn convert(&self, vars: &[Variable], options: &ConvertOptions) -> Result<String> {
+        let filtered = options.filter_vars(vars);
+        let (secrets, config): (Vec<Variable>, Vec<Variable>) =
+            filtered.into_iter().partition(is_secret);
+
+        let mut output = String::new();
+        output.push_str("# Generated by evnx\n");
+        output.push_str("# Install as /etc/systemd/system/<unit>.service.d/env.conf,\n");
+        output.push_str("# then run: systemctl daemon-reload\n");
+        if !secrets.is_empty() {
+            output.push_str(&format!(
+                "# Secrets are read from {}, readable by root only\n",
+                SECRETS_FILE
+            ));
+            output.push_str("# (chmod 600; `evnx convert --to systemd` writes the format):\n");
+            for var in &secrets {
+                output.push_str(&format!("#   {}=...\n", options.transform_key(&var.key)));
+            }
+        }
+        output.push_str("\n[Service]\n");
+        if !secrets.is_empty() {
+            output.push_str(&format!("EnvironmentFile={}\n", SECRETS_FILE));
+        }
+        for (i, var) in config.iter().enumerate() {
+            let key = options.transform_key(&var.key);
+            let value = options.transform_value(&var.value);
+            output.push_str(&hash_comments(&config, i, ""));
+            output.push_str(&format!(
+                "Environment={}\n",
+                systemd_environment(&key, &value)
+            ));
+        }
+
+        Ok(output)
+    }
+
+    fn name(&self) -> &str {
+        "systemd-dropin"
+    }
+
+    fn description(&self) -> &str {
+        "systemd drop-in unit (Environment=, secrets via EnvironmentFile=)"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_systemd_outputs() {
+        let vars = vec![
+            Variable::new("PORT", "8080"),
+            Variable::new("GREETING", "it's 100% \"fine\""),
+        ];
+
+        let output = SystemdEnvFileConverter
+            .convert(&vars, &ConvertOptions::default())
+            .unwrap();
+        assert!(output.contains("PORT=8080\n"));
+        assert!(output.contains("GREETING=\"it's 100% \\\"fine\\\"\"\n"));
+
+        let output = SystemdDropInConverter
+            .convert(&vars, &ConvertOptions::default())
+            .unwrap();
+        assert!(output.contains("[Service]\nEnvironment=\"PORT=8080\"\n"));
+        assert!(output.contains("Environment=\"GREETING=it's 100%% \\\"fine\\\"\"\n"));
+        assert!(!output.contains("EnvironmentFile="));
+    }
+
+    #[test]
+    fn test_dropin_reads_secrets_from_environment_file() {
+        let vars = vec![
+            Variable::new("PORT", "8080"),
+            Variable::new("DB_PASSWORD", "Xk9vQ2mL7pR4"),
+        ];
+
+        let output = SystemdDropInConverter
+            .convert(&vars, &ConvertOptions::default())
+            .unwrap();
+        assert!(!output.contains("Xk9vQ2mL7pR4"));
+        assert!(output.contains("#   DB_PASSWORD=...\n"));
+        assert!(output.contains(
+            "[Service]\nEnvironmentFile=/etc/<unit>/secrets.env\nEnvironment=\"PORT=8080\"\n"
+        ));
+    }
+}
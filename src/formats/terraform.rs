@@ -2,25 +2,24 @@
 // formats/terraform.rs
 // ============================================================================
 
-use crate::core::converter::{ConvertOptions, Converter};
+use super::escape::{hash_comments, hcl_string};
+use crate::core::converter::{ConvertOptions, Converter, Variable};
 use anyhow::Result;
-use std::collections::HashMap;
 
 pub struct TerraformConverter;
 
 impl Converter for TerraformConverter {
-    fn convert(&self, vars: &HashMap<String, String>, options: &ConvertOptions) -> Result<String> {
+    fn convert(&self, vars: &[Variable], options: &ConvertOptions) -> Result<String> {
         let filtered = options.filter_vars(vars);
 
         let mut output = String::new();
         output.push_str("# Generated by evnx\n\n");
 
-        for (k, v) in filtered.iter() {
-            let key = options.transform_key(k).to_lowercase();
-            let value = options.transform_value(v);
-            // Escape quotes and backslashes
-            let escaped_value = value.replace('\\', "\\\\").replace('"', "\\\"");
-            output.push_str(&format!("{} = \"{}\"\n", key, escaped_value));
+        for (i, var) in filtered.iter().enumerate() {
+            let key = options.transform_key(&var.key).to_lowercase();
+            let value = options.transform_value(&var.value);
+            output.push_str(&hash_comments(&filtered, i, ""));
+            output.push_str(&format!("{} = {}\n", key, hcl_string(&value)));
         }
 
         Ok(output)
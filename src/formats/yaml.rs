@@ -2,27 +2,33 @@
 // formats/yaml.rs
 // ============================================================================
 
-use crate::core::converter::{ConvertOptions, Converter};
+use super::escape::{hash_comments, yaml_key, yaml_scalar};
+use crate::core::converter::{ConvertOptions, Converter, Variable};
+use crate::core::keypath::Node;
 use anyhow::Result;
-use std::collections::HashMap;
 
 pub struct YamlConverter;
 
 impl Converter for YamlConverter {
-    fn convert(&self, vars: &HashMap<String, String>, options: &ConvertOptions) -> Result<String> {
+    fn convert(&self, vars: &[Variable], options: &ConvertOptions) -> Result<String> {
+        if let Some(doc) = options.nested(vars)? {
+            return Ok(document(&doc));
+        }
+
         let filtered = options.filter_vars(vars);
+        if filtered.is_empty() {
+            return Ok("{}\n".to_string());
+        }
 
-        let transformed: HashMap<String, String> = filtered
-            .iter()
-            .map(|(k, v)| {
-                let key = options.transform_key(k);
-                let value = options.transform_value(v);
-                (key, value)
-            })
-            .collect();
+        let mut output = String::new();
+        for (i, var) in filtered.iter().enumerate() {
+            let key = options.transform_key(&var.key);
+            let value = options.transform_value(&var.value);
+            output.push_str(&hash_comments(&filtered, i, ""));
+            output.push_str(&format!("{}: {}\n", yaml_key(&key), yaml_scalar(&value, 2)));
+        }
 
-        let yaml = serde_yaml::to_string(&transformed)?;
-        Ok(yaml)
+        Ok(output)
     }
 
     fn name(&self) -> &str {
@@ -33,3 +39,70 @@ impl Converter for YamlConverter {
         "Generic YAML key-value format"
     }
 }
+
+/// YAML document of a nested `doc`, with descriptions as comments.
+pub(crate) fn document(doc: &Node) -> String {
+    match doc {
+        Node::Map(entries) if !entries.is_empty() => {
+            let mut output = String::new();
+            write_map(entries, 0, &mut output);
+            output
+        }
+        _ => "{}\n".to_string(),
+    }
+}
+
+/// Block mapping at `depth`; values keep the description of their variable.
+fn write_map(entries: &[(String, Node)], depth: usize, out: &mut String) {
+    let indent = "  ".repeat(depth);
+    for (key, node) in entries {
+        write_description(node, &indent, out);
+        out.push_str(&format!("{}{}:", indent, yaml_key(key)));
+        write_value(node, depth, out);
+    }
+}
+
+fn write_list(items: &[Node], depth: usize, out: &mut String) {
+    let indent = "  ".repeat(depth);
+    for item in items {
+        write_description(item, &indent, out);
+        out.push_str(&format!("{}-", indent));
+        write_value(item, depth, out);
+    }
+}
+
+/// The value after `key:` or `-` at `depth`.
+fn write_value(node: &Node, depth: usize, out: &mut String) {
+    match node {
+        Node::Leaf { value, .. } => {
+            let text = match value {
+                serde_json::Value::String(s) => yaml_scalar(s, (depth + 1) * 2),
+                // JSON is YAML flow syntax.
+                other => other.to_string(),
+            };
+            out.push_str(&format!(" {}\n", text));
+        }
+        Node::Map(entries) if entries.is_empty() => out.push_str(" {}\n"),
+        Node::List(items) if items.is_empty() => out.push_str(" []\n"),
+        Node::Map(entries) => {
+            out.push('\n');
+            write_map(entries, depth + 1, out);
+        }
+        Node::List(items) => {
+            out.push('\n');
+            write_list(items, depth + 1, out);
+        }
+    }
+}
+
+fn write_description(node: &Node, indent: &str, out: &mut String) {
+    if let Node::Leaf {
+        description: Some(description),
+        ..
+    } = node
+    {
+        for line in description.lines() {
+            out.push_str(&format!("{}# {}\n", indent, line));
+        }
+    }
+}
@@ -18,6 +18,7 @@
 //!
 //! - `schema/` — Reusable core: JSON schema, resolver, formatter
 //! - `commands/` — CLI handlers (init, add, validate, etc.)
+//! - `codegen/` — Typed config loaders generated from .env.example
 //! - `utils/` — Shared utilities (file I/O, formatting)
 
 // ─────────────────────────────────────────────────────────────
@@ -25,6 +26,7 @@
 // ─────────────────────────────────────────────────────────────
 
 pub mod cli;
+pub mod codegen;
 pub mod commands;
 pub mod core;
 pub mod formats;
@@ -4,7 +4,7 @@ use anyhow::Result;
 use clap::Parser;
 // use colored::*;
 
-use evnx::cli::{Cli, Commands};
+use evnx::cli::{Cli, Commands, HooksAction};
 use evnx::commands;
 
 fn main() -> Result<()> {
@@ -31,7 +31,19 @@ fn main() -> Result<()> {
             fix,
             format,
             exit_zero,
-        } => commands::validate::run(env, example, strict, fix, format, exit_zero, cli.verbose),
+            check_example,
+            allow_default,
+        } => commands::validate::run(
+            env,
+            example,
+            strict,
+            fix,
+            format,
+            exit_zero,
+            check_example,
+            allow_default,
+            cli.verbose,
+        ),
 
         Commands::Scan {
             path,
@@ -40,6 +52,13 @@ fn main() -> Result<()> {
             ignore_placeholders,
             format,
             exit_zero,
+            known_values,
+            image_tar,
+            host,
+            examples,
+            allow_default,
+            remediate,
+            yes,
         } => commands::scan::run(
             path,
             exclude,
@@ -47,16 +66,62 @@ fn main() -> Result<()> {
             ignore_placeholders,
             format,
             exit_zero,
+            known_values,
+            image_tar,
+            host,
+            examples,
+            allow_default,
+            remediate,
+            yes,
             cli.verbose,
         ),
 
+        Commands::Redact {
+            files,
+            env,
+            extra,
+            mask,
+        } => commands::redact::run(files, env, extra, mask, cli.verbose),
+
         Commands::Diff {
             env,
             example,
             show_values,
             format,
             reverse,
-        } => commands::diff::run(env, example, show_values, format, reverse, cli.verbose),
+            from,
+            to,
+            hashed,
+            fingerprint,
+            shapes,
+            key,
+        } => commands::diff::run(
+            env,
+            example,
+            show_values,
+            format,
+            reverse,
+            from,
+            to,
+            hashed,
+            fingerprint,
+            shapes,
+            key,
+            cli.verbose,
+        ),
+
+        Commands::Merge {
+            base,
+            ours,
+            theirs,
+            output,
+        } => commands::merge::run(base, ours, theirs, output, cli.verbose),
+
+        Commands::Hooks { action } => match action {
+            HooksAction::Install { merge_driver } => {
+                commands::hooks::run_install(merge_driver, cli.verbose)
+            }
+        },
 
         Commands::Convert {
             env,
@@ -67,6 +132,13 @@ fn main() -> Result<()> {
             base64,
             prefix,
             transform,
+            example,
+            sort,
+            nest,
+            key_case,
+            coerce,
+            profile,
+            k8s,
         } => commands::convert::run(
             env,
             to,
@@ -76,6 +148,34 @@ fn main() -> Result<()> {
             base64,
             prefix,
             transform,
+            example,
+            sort,
+            nest,
+            key_case,
+            coerce,
+            profile,
+            *k8s,
+            cli.verbose,
+        ),
+
+        Commands::Import {
+            file,
+            from,
+            output,
+            on_conflict,
+            service,
+            transform,
+            nest,
+            key_case,
+        } => commands::import::run(
+            file,
+            from,
+            output,
+            on_conflict,
+            service,
+            transform,
+            nest,
+            key_case,
             cli.verbose,
         ),
 
@@ -110,6 +210,13 @@ fn main() -> Result<()> {
             placeholder,
         } => commands::sync::run(direction, placeholder, cli.verbose),
 
+        Commands::Codegen {
+            lang,
+            example,
+            output,
+            package,
+        } => commands::codegen::run(lang, example, output, package, cli.verbose),
+
         Commands::Template { input, output, env } => {
             commands::template::run(input, output, env, cli.verbose)
         }
@@ -13,7 +13,7 @@ pub use loader::{
     list_blueprints, schema,
 };
 pub use models::{Schema, VarCollection, VarMetadata, VarSource};
-pub use query::{filter_by_tag, list_tags, search_frameworks, search_services};
+pub use query::{describe_var, filter_by_tag, list_tags, search_frameworks, search_services};
 pub use resolver::{
     resolve_architect_selection, resolve_blueprint, resolve_framework, resolve_service,
 };
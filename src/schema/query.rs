@@ -93,3 +93,42 @@ pub fn list_tags() -> Vec<String> {
     tags.dedup();
     tags
 }
+
+/// Description of `var_name` in the schema, from the first service,
+/// infrastructure entry or framework (by id) that describes it
+pub fn describe_var(var_name: &str) -> Option<String> {
+    let schema = loader::schema().ok()?;
+
+    let mut sources: Vec<(&String, &std::collections::HashMap<String, String>)> = Vec::new();
+    for services in [
+        &schema.services.databases,
+        &schema.services.messaging_queues,
+        &schema.services.auth_providers,
+        &schema.services.storage,
+        &schema.services.monitoring_logging,
+        &schema.services.payments,
+        &schema.services.ai_ml,
+        &schema.services.email_sms,
+    ] {
+        sources.extend(services.iter().map(|(id, s)| (id, &s.descriptions)));
+    }
+    sources.extend(
+        schema
+            .infrastructure
+            .iter()
+            .map(|(id, i)| (id, &i.descriptions)),
+    );
+    for language in schema.languages.values() {
+        sources.extend(
+            language
+                .frameworks
+                .iter()
+                .map(|(id, f)| (id, &f.descriptions)),
+        );
+    }
+
+    sources.sort_by_key(|(id, _)| *id);
+    sources
+        .into_iter()
+        .find_map(|(_, descriptions)| descriptions.get(var_name).cloned())
+}
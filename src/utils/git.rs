@@ -130,6 +130,53 @@ pub fn is_clean() -> bool {
         .unwrap_or(false)
 }
 
+/// Read `path` as of revision `rev` (`git show REV:PATH`).
+///
+/// `path` follows git's rules: relative to the repository root, or to the
+/// current directory when it starts with `./`.
+///
+/// # Errors
+///
+/// Returns an error if git is not available, the revision is unknown or the
+/// file does not exist at that revision.
+pub fn show_file(rev: &str, path: &str) -> Result<String> {
+    let output = Command::new("git")
+        .args(["show", &format!("{}:{}", rev, path)])
+        .output()?;
+
+    if !output.status.success() {
+        return Err(anyhow!(
+            "Cannot read {} at {}: {}",
+            path,
+            rev,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    Ok(String::from_utf8(output.stdout)?)
+}
+
+/// Set `key` to `value` in the repository's local git config.
+///
+/// # Errors
+///
+/// Returns an error if git is not available or the current directory is not
+/// a repository.
+pub fn set_config(key: &str, value: &str) -> Result<()> {
+    let output = Command::new("git")
+        .args(["config", "--local", key, value])
+        .output()?;
+
+    if !output.status.success() {
+        return Err(anyhow!(
+            "git config failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
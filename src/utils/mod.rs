@@ -2,6 +2,8 @@ pub mod file_ops;
 pub mod fs;
 pub mod git;
 pub mod patterns;
+pub mod sarif;
+pub mod scoring;
 pub mod string;
 pub mod ui;
 
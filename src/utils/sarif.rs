@@ -0,0 +1,225 @@
+//! SARIF 2.1.0 output shared by `scan` and `validate`.
+//!
+//! Commands describe their checks as [`SarifRule`]s and their findings as
+//! [`SarifResult`]s; [`sarif_log`] assembles a complete log with
+//! `tool.driver.rules`, `ruleIndex`, line/column regions and
+//! `partialFingerprints`, as expected by GitHub code scanning.
+
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+
+/// Schema URI written to the `$schema` property.
+pub const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// Key used in `partialFingerprints`.
+pub const FINGERPRINT_KEY: &str = "evnx/v1";
+
+/// Metadata for one check, emitted in `tool.driver.rules`.
+#[derive(Debug, Clone)]
+pub struct SarifRule {
+    pub id: String,
+    pub name: String,
+    pub short_description: String,
+    pub full_description: String,
+    pub help: String,
+    pub help_uri: String,
+    /// Default level: `error`, `warning` or `note`.
+    pub level: &'static str,
+    pub tags: Vec<&'static str>,
+}
+
+/// A single reported problem.
+#[derive(Debug, Clone)]
+pub struct SarifResult {
+    pub rule_id: String,
+    pub level: &'static str,
+    pub message: String,
+    /// File path as reported by the command; converted with [`artifact_uri`].
+    pub path: String,
+    /// 1-based line and column (in Unicode code points). Line 0 means the
+    /// position is unknown, and the result has no region.
+    pub line: usize,
+    pub column: usize,
+    /// Value for [`FINGERPRINT_KEY`], see [`fingerprint`].
+    pub fingerprint: String,
+}
+
+/// Build a complete SARIF log for a single run of `tool_name`.
+pub fn sarif_log(tool_name: &str, rules: &[SarifRule], results: &[SarifResult]) -> Value {
+    let rules_json: Vec<Value> = rules
+        .iter()
+        .map(|rule| {
+            json!({
+                "id": rule.id,
+                "name": rule.name,
+                "shortDescription": { "text": rule.short_description },
+                "fullDescription": { "text": rule.full_description },
+                "help": { "text": rule.help },
+                "helpUri": rule.help_uri,
+                "defaultConfiguration": { "level": rule.level },
+                "properties": { "tags": rule.tags }
+            })
+        })
+        .collect();
+
+    let results_json: Vec<Value> = results
+        .iter()
+        .map(|result| {
+            let mut value = json!({
+                "ruleId": result.rule_id,
+                "level": result.level,
+                "message": { "text": result.message },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": artifact_uri(&result.path) }
+                    }
+                }],
+                "partialFingerprints": { FINGERPRINT_KEY: result.fingerprint }
+            });
+            if result.line > 0 {
+                value["locations"][0]["physicalLocation"]["region"] = json!({
+                    "startLine": result.line,
+                    "startColumn": result.column.max(1)
+                });
+            }
+            if let Some(index) = rules.iter().position(|r| r.id == result.rule_id) {
+                value["ruleIndex"] = json!(index);
+            }
+            value
+        })
+        .collect();
+
+    json!({
+        "$schema": SARIF_SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": tool_name,
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": env!("CARGO_PKG_REPOSITORY"),
+                    "rules": rules_json
+                }
+            },
+            "columnKind": "unicodeCodePoints",
+            "results": results_json
+        }]
+    })
+}
+
+/// Turn a rule name into a stable id segment (`"GitHub Token"` → `github-token`).
+pub fn slug(name: &str) -> String {
+    let mut slug = String::with_capacity(name.len());
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.trim_end_matches('-').to_string()
+}
+
+/// Stable fingerprint over the given parts.
+///
+/// Callers pass what identifies a problem independently of its line (rule,
+/// file, variable, ...), so results survive unrelated edits to the file.
+pub fn fingerprint(parts: &[&str]) -> String {
+    let mut hasher = Sha256::new();
+    for part in parts {
+        hasher.update(part.as_bytes());
+        hasher.update([0]);
+    }
+    hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Convert a file path into a SARIF `artifactLocation.uri`.
+///
+/// Relative paths stay relative (with `/` separators and without a leading
+/// `./`), absolute paths become `file://` URIs; unsafe characters are
+/// percent-encoded.
+pub fn artifact_uri(path: &str) -> String {
+    let path = path.replace('\\', "/");
+    let path = path.trim_start_matches("./");
+
+    let encoded: String = path
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect();
+
+    if path.starts_with('/') {
+        format!("file://{}", encoded)
+    } else {
+        encoded
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slug() {
+        assert_eq!(slug("GitHub Token"), "github-token");
+        assert_eq!(slug("Known .env value (base64)"), "known-env-value-base64");
+        assert_eq!(slug("missing_variable"), "missing-variable");
+    }
+
+    #[test]
+    fn test_artifact_uri() {
+        assert_eq!(artifact_uri("./src/app.py"), "src/app.py");
+        assert_eq!(artifact_uri("config dir\\.env"), "config%20dir/.env");
+        assert_eq!(artifact_uri("/tmp/app/.env"), "file:///tmp/app/.env");
+    }
+
+    #[test]
+    fn test_fingerprint_is_stable_and_separated() {
+        assert_eq!(fingerprint(&["a", "bc"]), fingerprint(&["a", "bc"]));
+        assert_ne!(fingerprint(&["a", "bc"]), fingerprint(&["ab", "c"]));
+    }
+
+    #[test]
+    fn test_result_references_rule_index() {
+        let rule = SarifRule {
+            id: "secret/github-token".to_string(),
+            name: "GitHub Token".to_string(),
+            short_description: "GitHub Token detected".to_string(),
+            full_description: "A GitHub token was found.".to_string(),
+            help: "Revoke it.".to_string(),
+            help_uri: "https://github.com/settings/tokens".to_string(),
+            level: "error",
+            tags: vec!["security"],
+        };
+        let result = SarifResult {
+            rule_id: rule.id.clone(),
+            level: "error",
+            message: "GitHub Token detected".to_string(),
+            path: "./.env".to_string(),
+            line: 3,
+            column: 14,
+            fingerprint: fingerprint(&["x"]),
+        };
+
+        let log = sarif_log("evnx scan", &[rule], &[result]);
+        let result = &log["runs"][0]["results"][0];
+
+        assert_eq!(result["ruleIndex"], 0);
+        assert_eq!(
+            result["locations"][0]["physicalLocation"]["region"]["startColumn"],
+            14
+        );
+        assert_eq!(
+            result["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+            ".env"
+        );
+    }
+}
@@ -0,0 +1,315 @@
+//! Confidence scoring for secret candidates.
+//!
+//! A single Shannon threshold treats a 40-character hex digest, a UUID and a
+//! base64 blob alike, although their alphabets cap entropy at very different
+//! values (4 bits per character for hex, 6 for base64). Values are therefore
+//! classified by [`Charset`] and compared against a per-charset threshold,
+//! and known non-secret [`Shape`]s (UUIDs, versions, paths, plain URLs) are
+//! penalised.
+//!
+//! # Score
+//!
+//! [`ScoreModel::score`] adds up points from three signals:
+//!
+//! | Signal                                         | Default points |
+//! |------------------------------------------------|----------------|
+//! | Rule match (high / medium / low rule)          | 75 / 50 / 25   |
+//! | Key name suggests a credential                 | +20            |
+//! | Entropy above the charset threshold, no rule   | +25 (hex: +15) |
+//! | Non-secret shape, no rule                      | −40            |
+//!
+//! Rules carry their own entropy floor, so entropy and shape only count for
+//! values no rule matched. Hex earns fewer points because digests and ids
+//! are hex too: a hex value needs a credential-like key name to be reported.
+//! The total maps to a [`Confidence`] with [`ScoreModel::confidence`]
+//! (defaults: high ≥ 75, medium ≥ 45, low ≥ 25, otherwise not reported).
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use super::patterns::{calculate_entropy, has_real_url_password, Confidence};
+
+lazy_static! {
+    static ref UUID: Regex = Regex::new(
+        r"^[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}$"
+    )
+    .unwrap();
+    static ref SEMVER: Regex =
+        Regex::new(r"^v?\d+\.\d+\.\d+(?:-[0-9A-Za-z.-]+)?(?:\+[0-9A-Za-z.-]+)?$").unwrap();
+}
+
+/// Alphabet a value is drawn from, narrowest first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Charset {
+    /// `0-9a-f` (either case).
+    Hex,
+    /// `A-Za-z0-9`.
+    Alphanumeric,
+    /// `A-Za-z0-9-_` (URL-safe base64, JWT segments).
+    Base64Url,
+    /// `A-Za-z0-9+/` with optional `=` padding.
+    Base64,
+    /// Anything else (punctuation, non-ASCII).
+    Other,
+}
+
+impl Charset {
+    /// Classify `value` by the narrowest alphabet containing all its characters.
+    pub fn of(value: &str) -> Self {
+        let body = value.trim_end_matches('=');
+        let padded = body.len() != value.len();
+
+        if !padded && !body.is_empty() && body.chars().all(|c| c.is_ascii_hexdigit()) {
+            Charset::Hex
+        } else if !padded && body.chars().all(|c| c.is_ascii_alphanumeric()) {
+            Charset::Alphanumeric
+        } else if !padded
+            && body
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            Charset::Base64Url
+        } else if body
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '+' || c == '/')
+        {
+            Charset::Base64
+        } else {
+            Charset::Other
+        }
+    }
+
+    /// Size of the alphabet, which bounds entropy at `log2(size)` bits.
+    fn alphabet_size(self) -> f64 {
+        match self {
+            Charset::Hex => 16.0,
+            Charset::Alphanumeric => 62.0,
+            Charset::Base64Url | Charset::Base64 => 64.0,
+            Charset::Other => 94.0,
+        }
+    }
+
+    /// Fraction of the attainable entropy a random value reliably exceeds.
+    ///
+    /// Mixed punctuation is also what source code looks like, so `Other`
+    /// needs nearly the maximum.
+    fn entropy_ratio(self) -> f64 {
+        match self {
+            Charset::Hex => 0.85,
+            Charset::Alphanumeric | Charset::Base64Url | Charset::Base64 => 0.9,
+            Charset::Other => 0.93,
+        }
+    }
+
+    /// Entropy a value of this charset and `len` characters must exceed.
+    ///
+    /// Short strings cannot reach the alphabet's bound (at most `log2(len)`
+    /// distinct symbols fit), so the threshold follows the lower of the two.
+    pub fn entropy_threshold(self, len: usize) -> f64 {
+        let attainable = self.alphabet_size().log2().min((len.max(1) as f64).log2());
+        attainable * self.entropy_ratio()
+    }
+}
+
+impl std::fmt::Display for Charset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Charset::Hex => "hex",
+            Charset::Alphanumeric => "alphanumeric",
+            Charset::Base64Url => "base64url",
+            Charset::Base64 => "base64",
+            Charset::Other => "mixed",
+        })
+    }
+}
+
+/// Value shapes that look random but are not secrets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shape {
+    Uuid,
+    Semver,
+    Path,
+    /// URL without a real inline password.
+    Url,
+}
+
+impl Shape {
+    pub fn of(value: &str) -> Option<Self> {
+        let v = value.trim();
+        if UUID.is_match(v) {
+            Some(Shape::Uuid)
+        } else if SEMVER.is_match(v) {
+            Some(Shape::Semver)
+        } else if v.contains("://") {
+            (!has_real_url_password(v)).then_some(Shape::Url)
+        } else if v.starts_with(['/', '~'])
+            || v.starts_with("./")
+            || v.starts_with("../")
+            || v.get(1..3) == Some(":\\")
+        {
+            Some(Shape::Path)
+        } else {
+            None
+        }
+    }
+}
+
+/// Points and thresholds of the confidence score; see the module docs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScoreModel {
+    pub rule_high: i32,
+    pub rule_medium: i32,
+    pub rule_low: i32,
+    pub key_hint: i32,
+    pub entropy: i32,
+    pub entropy_hex: i32,
+    pub shape_penalty: i32,
+    /// Minimum length for the entropy signal.
+    pub min_entropy_len: usize,
+    pub high: i32,
+    pub medium: i32,
+    pub low: i32,
+}
+
+impl Default for ScoreModel {
+    fn default() -> Self {
+        Self {
+            rule_high: 75,
+            rule_medium: 50,
+            rule_low: 25,
+            key_hint: 20,
+            entropy: 25,
+            entropy_hex: 15,
+            shape_penalty: 40,
+            min_entropy_len: super::patterns::GENERIC_MIN_LEN,
+            high: 75,
+            medium: 45,
+            low: 25,
+        }
+    }
+}
+
+impl ScoreModel {
+    /// Score a value given the confidence of the rule it matched (if any)
+    /// and whether its key name suggests a credential.
+    pub fn score(&self, value: &str, rule: Option<&Confidence>, key_hint: bool) -> i32 {
+        let key_points = if key_hint { self.key_hint } else { 0 };
+
+        if let Some(rule) = rule {
+            return key_points
+                + match rule {
+                    Confidence::High => self.rule_high,
+                    Confidence::Medium => self.rule_medium,
+                    Confidence::Low => self.rule_low,
+                };
+        }
+
+        let mut score = key_points + self.entropy_points(value);
+        if Shape::of(value).is_some() {
+            score -= self.shape_penalty;
+        }
+        score
+    }
+
+    /// Points for an entropy above the value's charset threshold.
+    pub fn entropy_points(&self, value: &str) -> i32 {
+        let len = value.chars().count();
+        if len < self.min_entropy_len {
+            return 0;
+        }
+
+        let charset = Charset::of(value);
+        if calculate_entropy(value) <= charset.entropy_threshold(len) {
+            0
+        } else if charset == Charset::Hex {
+            self.entropy_hex
+        } else {
+            self.entropy
+        }
+    }
+
+    /// Map a score to a confidence; `None` below the reporting threshold.
+    pub fn confidence(&self, score: i32) -> Option<Confidence> {
+        if score >= self.high {
+            Some(Confidence::High)
+        } else if score >= self.medium {
+            Some(Confidence::Medium)
+        } else if score >= self.low {
+            Some(Confidence::Low)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_charset_classification() {
+        assert_eq!(Charset::of("9f86d081884c7d65"), Charset::Hex);
+        assert_eq!(Charset::of("aB3dE5fG7hJ9"), Charset::Alphanumeric);
+        assert_eq!(Charset::of("aB3d-E5f_G7h"), Charset::Base64Url);
+        assert_eq!(Charset::of("aB3d+E5f/G7h=="), Charset::Base64);
+        assert_eq!(Charset::of("p@ss:word!"), Charset::Other);
+    }
+
+    #[test]
+    fn test_threshold_follows_alphabet_and_length() {
+        assert!(Charset::Hex.entropy_threshold(64) < 4.0);
+        assert!(Charset::Base64.entropy_threshold(128) > Charset::Hex.entropy_threshold(128));
+        assert!(Charset::Base64.entropy_threshold(8) <= 3.0);
+    }
+
+    #[test]
+    fn test_shapes() {
+        assert_eq!(
+            Shape::of("123e4567-e89b-12d3-a456-426614174000"),
+            Some(Shape::Uuid)
+        );
+        assert_eq!(Shape::of("v1.24.3-rc.1"), Some(Shape::Semver));
+        assert_eq!(Shape::of("/var/lib/app/data"), Some(Shape::Path));
+        assert_eq!(Shape::of("https://example.com/a?b=c"), Some(Shape::Url));
+        assert_eq!(Shape::of("postgres://app:Xk9vQ2mL7pR4@db/app"), None);
+        assert_eq!(Shape::of("Xk9vQ2mL7pR4"), None);
+    }
+
+    #[test]
+    fn test_score_mapping() {
+        let model = ScoreModel::default();
+        let random = "Zq8wN3vB6yT1mK4xR7cP2sL5hJ9dF0gA";
+        let digest = "9f86d081884c7d65aa2f6b1b3e0c4d5f9f86d081884c7d65aa2f6b1b3e0c4d5f";
+
+        // Rules keep their confidence; a key hint lifts medium and low ones.
+        assert_eq!(
+            model.confidence(model.score(random, Some(&Confidence::High), false)),
+            Some(Confidence::High)
+        );
+        assert_eq!(
+            model.confidence(model.score(random, Some(&Confidence::Low), true)),
+            Some(Confidence::Medium)
+        );
+
+        // Entropy alone is low confidence, with a credential key medium.
+        assert_eq!(
+            model.confidence(model.score(random, None, false)),
+            Some(Confidence::Low)
+        );
+        assert_eq!(
+            model.confidence(model.score(random, None, true)),
+            Some(Confidence::Medium)
+        );
+
+        // Hex digests need a key hint.
+        assert_eq!(model.confidence(model.score(digest, None, false)), None);
+        assert_eq!(
+            model.confidence(model.score(digest, None, true)),
+            Some(Confidence::Low)
+        );
+
+        // Shapes are penalised even under a credential key.
+        let url = "https://cdn.example.com/assets/Zq8wN3vB6yT1mK4xR7cP2sL5hJ9dF0gA";
+        assert_eq!(model.confidence(model.score(url, None, true)), None);
+    }
+}
@@ -3,6 +3,8 @@
 //! These helpers are used throughout the CLI for consistent presentation
 //! of values in terminal output.
 
+use std::sync::OnceLock;
+
 /// Truncate a string to `max_len` characters.
 ///
 /// If the string is longer than `max_len`, it is cut and `...` is appended.
@@ -39,10 +41,72 @@ pub fn truncate(s: &str, max_len: usize) -> String {
 /// assert_eq!(redact("secretkey123"), "secr...****");
 /// ```
 pub fn redact(s: &str) -> String {
-    if s.len() <= 8 {
-        "*".repeat(s.len())
+    let len = s.chars().count();
+    if len <= 8 {
+        "*".repeat(len)
     } else {
-        format!("{}...{}", &s[..4], "*".repeat(4))
+        format!(
+            "{}...{}",
+            s.chars().take(4).collect::<String>(),
+            "*".repeat(4)
+        )
+    }
+}
+
+/// How [`mask`] hides a sensitive value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MaskFormat {
+    /// Replace the whole value with `[REDACTED]`.
+    Full,
+    /// Keep a short prefix, as [`redact`] does.
+    Partial,
+    /// Replace with a short SHA-256 digest, so equal values stay recognisable.
+    /// The digest is salted per run: it cannot be checked against guessed
+    /// values, nor matched with the output of another run.
+    Hash,
+}
+
+impl std::str::FromStr for MaskFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "full" => Ok(MaskFormat::Full),
+            "partial" => Ok(MaskFormat::Partial),
+            "hash" => Ok(MaskFormat::Hash),
+            other => Err(anyhow::anyhow!(
+                "Unknown mask format '{}' (expected full, partial or hash)",
+                other
+            )),
+        }
+    }
+}
+
+/// Mask a sensitive value according to `format`.
+///
+/// # Examples
+///
+/// ```
+/// use evnx::utils::string::{mask, MaskFormat};
+/// assert_eq!(mask("secretkey123", MaskFormat::Full), "[REDACTED]");
+/// assert_eq!(mask("secretkey123", MaskFormat::Partial), "secr...****");
+/// assert!(mask("secretkey123", MaskFormat::Hash).starts_with("[sha256:"));
+/// ```
+pub fn mask(s: &str, format: MaskFormat) -> String {
+    match format {
+        MaskFormat::Full => "[REDACTED]".to_string(),
+        MaskFormat::Partial => redact(s),
+        MaskFormat::Hash => {
+            use sha2::{Digest, Sha256};
+            static SALT: OnceLock<[u8; 16]> = OnceLock::new();
+            let salt = SALT.get_or_init(rand::random);
+            let digest = Sha256::new()
+                .chain_update(salt)
+                .chain_update(s.as_bytes())
+                .finalize();
+            let hex: String = digest[..6].iter().map(|b| format!("{:02x}", b)).collect();
+            format!("[sha256:{}]", hex)
+        }
     }
 }
 
@@ -98,6 +162,28 @@ mod tests {
         assert_eq!(redact("12345678"), "********");
     }
 
+    #[test]
+    fn test_redact_multibyte() {
+        assert_eq!(redact("pässwörd-lång"), "päss...****");
+    }
+
+    #[test]
+    fn test_mask_hash_is_stable() {
+        let a = mask("secretkey123", MaskFormat::Hash);
+        assert_eq!(a, mask("secretkey123", MaskFormat::Hash));
+        assert_ne!(a, mask("secretkey124", MaskFormat::Hash));
+        assert_eq!(a.len(), "[sha256:]".len() + 12);
+
+        // Salted: not the plain digest of the value (sha256("secretkey123")).
+        assert_ne!(a, "[sha256:dd02b8d024c4]");
+    }
+
+    #[test]
+    fn test_mask_format_from_str() {
+        assert_eq!("FULL".parse::<MaskFormat>().unwrap(), MaskFormat::Full);
+        assert!("stars".parse::<MaskFormat>().is_err());
+    }
+
     #[test]
     fn test_pluralize_singular() {
         assert_eq!(pluralize(1, "file", "files"), "1 file");
@@ -1,6 +1,10 @@
 // tests/common/mod.rs
 
 //! Shared utilities for integration tests.
+//!
+//! Each integration test binary uses a different subset of these helpers.
+
+#![allow(dead_code)]
 
 use anyhow::Result;
 use std::fs;
@@ -17,6 +17,7 @@ use tempfile::TempDir;
 
 use common::{count_env_vars, read_env_example};
 
+#[allow(dead_code)]
 fn debug_print_content(label: &str, content: &str) {
     eprintln!("\n=== {} ===", label);
     eprintln!("{}", content);
@@ -116,7 +117,7 @@ fn init_blueprint_t3_modern_generates_expected_vars() {
     // Check for reasonable variable count (blueprints have 10-30 vars)
     let var_count = count_env_vars(&example);
     assert!(
-        var_count >= 5 && var_count <= 50,
+        (5..=50).contains(&var_count),
         "Should have 5-50 variables, got {}",
         var_count
     );